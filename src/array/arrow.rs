@@ -0,0 +1,421 @@
+//! Conversion between arrays and [Apache Arrow](https://arrow.apache.org) arrays.
+//!
+//! The layouts of primitive and variable-length arrays are close to Arrow's, so their values
+//! are copied in bulk. Dictionary arrays are converted to Arrow dictionary arrays.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use ::arrow::array::{
+    as_boolean_array, as_dictionary_array, as_generic_binary_array, as_primitive_array,
+    as_string_array, make_array, Array as ArrowArray, ArrayData, ArrayRef, BooleanArray,
+    PrimitiveArray as ArrowPrimitiveArray, StringArray,
+};
+use ::arrow::buffer::Buffer;
+use ::arrow::compute::cast;
+use ::arrow::datatypes::{
+    ArrowPrimitiveType, DataType as ArrowDataType, Date32Type, Decimal128Type, Field, Float64Type,
+    Int16Type, Int32Type, Int64Type, IntervalMonthDayNanoType, IntervalUnit, SchemaRef, TimeUnit,
+    TimestampMicrosecondType, UInt32Type,
+};
+use ::arrow::error::ArrowError;
+use ::arrow::record_batch::RecordBatch;
+use bitvec::slice::BitSlice;
+use itertools::Itertools;
+
+use super::*;
+
+/// The scale of Arrow decimals converted from decimals of unspecified scale. Decimals with
+/// more fractional digits are rounded.
+const DECIMAL_SCALE: u32 = 9;
+
+/// The precision of Arrow decimals, which can hold any decimal with [`DECIMAL_SCALE`].
+const DECIMAL_PRECISION: u8 = 38;
+
+/// Returns the Arrow type that columns of type `ty` are converted to.
+pub fn to_arrow_type(ty: &DataType) -> ArrowDataType {
+    match ty.kind().canonical() {
+        DataTypeKind::Boolean => ArrowDataType::Boolean,
+        DataTypeKind::SmallInt(_) => ArrowDataType::Int16,
+        DataTypeKind::Int(_) => ArrowDataType::Int32,
+        DataTypeKind::BigInt(_) => ArrowDataType::Int64,
+        kind if kind.is_float() => ArrowDataType::Float64,
+        kind if kind.is_decimal() => match kind.decimal_precision_scale() {
+            Some((Some(precision), Some(scale))) => {
+                ArrowDataType::Decimal128(precision as u8, scale as i8)
+            }
+            _ => ArrowDataType::Decimal128(DECIMAL_PRECISION, DECIMAL_SCALE as i8),
+        },
+        kind if kind.is_string() => ArrowDataType::Utf8,
+        DataTypeKind::Bytea => ArrowDataType::Binary,
+        DataTypeKind::Date => ArrowDataType::Date32,
+        DataTypeKind::Timestamp(..) => ArrowDataType::Timestamp(TimeUnit::Microsecond, None),
+        DataTypeKind::Interval => ArrowDataType::Interval(IntervalUnit::MonthDayNano),
+        kind => panic!("unsupported data type: {}", kind),
+    }
+}
+
+/// Returns the Arrow field of a column named `name` of type `ty`.
+pub fn to_arrow_field(name: &str, ty: &DataType) -> Field {
+    Field::new(name, to_arrow_type(ty), ty.is_nullable())
+}
+
+impl ArrayImpl {
+    /// Convert the array to an Arrow array.
+    ///
+    /// Decimals are converted to `Decimal128(38, 9)` and dictionary arrays to dictionary
+    /// arrays with `UInt32` keys.
+    pub fn to_arrow(&self) -> Result<ArrayRef, ArrowError> {
+        Ok(match self {
+            Self::Bool(a) => Arc::new(BooleanArray::from(
+                a.iter().map(|v| v.copied()).collect_vec(),
+            )),
+            Self::Int16(a) => make_array(primitive_data::<Int16Type>(a.values(), a.validity())?),
+            Self::Int32(a) => make_array(primitive_data::<Int32Type>(a.values(), a.validity())?),
+            Self::Int64(a) => make_array(primitive_data::<Int64Type>(a.values(), a.validity())?),
+            Self::Float64(a) => {
+                make_array(primitive_data::<Float64Type>(a.values(), a.validity())?)
+            }
+            Self::Decimal(a) => {
+                let values = a.values().iter().map(|v| decimal_to_i128(*v)).collect_vec();
+                let data = primitive_data::<Decimal128Type>(&values, a.validity())?;
+                let array = ArrowPrimitiveArray::<Decimal128Type>::from(data)
+                    .with_precision_and_scale(DECIMAL_PRECISION, DECIMAL_SCALE as i8)?;
+                Arc::new(array)
+            }
+            Self::Utf8(a) => make_array(var_data(ArrowDataType::Utf8, a)?),
+            Self::Bytes(a) => make_array(var_data(ArrowDataType::Binary, a)?),
+            Self::Dictionary(a) => {
+                let codes = a.codes();
+                let keys = primitive_data::<UInt32Type>(codes.values(), codes.validity())?;
+                let values = var_data(ArrowDataType::Utf8, a.dictionary())?;
+                Arc::new(::arrow::array::DictionaryArray::<UInt32Type>::try_new(
+                    &keys.into(),
+                    &StringArray::from(values),
+                )?)
+            }
+            Self::Date(a) => {
+                let values = a.values().iter().map(|v| v.days()).collect_vec();
+                make_array(primitive_data::<Date32Type>(&values, a.validity())?)
+            }
+            Self::Timestamp(a) => {
+                let values = a.values().iter().map(|v| v.micros()).collect_vec();
+                make_array(primitive_data::<TimestampMicrosecondType>(
+                    &values,
+                    a.validity(),
+                )?)
+            }
+            Self::Interval(a) => {
+                let values = (a.values().iter())
+                    .map(|v| {
+                        let nanos = v.micros().checked_mul(1000).ok_or_else(|| {
+                            ArrowError::ComputeError("interval out of range".into())
+                        })?;
+                        Ok(IntervalMonthDayNanoType::make_value(
+                            v.months(),
+                            v.days(),
+                            nanos,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, ArrowError>>()?;
+                make_array(primitive_data::<IntervalMonthDayNanoType>(
+                    &values,
+                    a.validity(),
+                )?)
+            }
+        })
+    }
+
+    /// Convert an Arrow array to an array.
+    ///
+    /// Arrow types without a counterpart are converted to the closest wider type, e.g. `Int8`
+    /// to `Int16` and timestamps in other units to microseconds.
+    pub fn from_arrow(array: &dyn ArrowArray) -> Result<Self, ArrowError> {
+        let unsupported = || {
+            let ty = array.data_type();
+            ArrowError::CastError(format!("unsupported arrow type: {}", ty))
+        };
+        let ty = canonical_arrow_type(array.data_type()).ok_or_else(unsupported)?;
+        let casted;
+        let array = if array.data_type() != &ty {
+            casted = cast(&make_array(array.data().clone()), &ty)?;
+            casted.as_ref()
+        } else {
+            array
+        };
+        Ok(match ty {
+            ArrowDataType::Boolean => Self::Bool(as_boolean_array(array).iter().collect()),
+            ArrowDataType::Int16 => {
+                Self::Int16(as_primitive_array::<Int16Type>(array).iter().collect())
+            }
+            ArrowDataType::Int32 => {
+                Self::Int32(as_primitive_array::<Int32Type>(array).iter().collect())
+            }
+            ArrowDataType::Int64 => {
+                Self::Int64(as_primitive_array::<Int64Type>(array).iter().collect())
+            }
+            ArrowDataType::Float64 => {
+                Self::Float64(as_primitive_array::<Float64Type>(array).iter().collect())
+            }
+            ArrowDataType::Decimal128(_, scale) => {
+                let out_of_range = || ArrowError::CastError("decimal out of range".into());
+                let values = as_primitive_array::<Decimal128Type>(array).iter();
+                let values = values.map(|v| {
+                    v.map(|v| decimal_from_i128(v, scale as u32).ok_or_else(out_of_range))
+                        .transpose()
+                });
+                Self::Decimal(values.collect::<Result<_, _>>()?)
+            }
+            ArrowDataType::Utf8 => Self::Utf8(as_string_array(array).iter().collect()),
+            ArrowDataType::Binary => {
+                Self::Bytes(as_generic_binary_array::<i32>(array).iter().collect())
+            }
+            ArrowDataType::Dictionary(..) => dictionary_from_arrow(array),
+            ArrowDataType::Date32 => {
+                let out_of_range = || ArrowError::CastError("date out of range".into());
+                let values = as_primitive_array::<Date32Type>(array).iter();
+                let values = values.map(|v| v.map(|v| Date::from_days(v).ok_or_else(out_of_range)));
+                Self::Date(values.map(Option::transpose).collect::<Result<_, _>>()?)
+            }
+            ArrowDataType::Timestamp(..) => {
+                let out_of_range = || ArrowError::CastError("timestamp out of range".into());
+                let values = as_primitive_array::<TimestampMicrosecondType>(array).iter();
+                let values =
+                    values.map(|v| v.map(|v| Timestamp::from_micros(v).ok_or_else(out_of_range)));
+                Self::Timestamp(values.map(Option::transpose).collect::<Result<_, _>>()?)
+            }
+            ArrowDataType::Interval(_) => {
+                let values = as_primitive_array::<IntervalMonthDayNanoType>(array).iter();
+                Self::Interval(
+                    values
+                        .map(|v| {
+                            let (months, days, nanos) = IntervalMonthDayNanoType::to_parts(v?);
+                            Some(Interval::new(months, days, nanos / 1000))
+                        })
+                        .collect(),
+                )
+            }
+            _ => return Err(unsupported()),
+        })
+    }
+}
+
+impl DataChunk {
+    /// Convert the visible rows of the chunk to an Arrow record batch. Columns are casted to
+    /// the types of the fields in `schema` if they differ.
+    pub fn to_record_batch(&self, schema: SchemaRef) -> Result<RecordBatch, ArrowError> {
+        let chunk = self.clone().compact();
+        let columns = (chunk.arrays().iter().zip(schema.fields()))
+            .map(|(array, field)| {
+                let array = array.to_arrow()?;
+                match array.data_type() == field.data_type() {
+                    true => Ok(array),
+                    false => cast(&array, field.data_type()),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        RecordBatch::try_new(schema, columns)
+    }
+
+    /// Convert an Arrow record batch to a chunk.
+    pub fn from_record_batch(batch: &RecordBatch) -> Result<Self, ArrowError> {
+        if batch.num_columns() == 0 {
+            return Err(ArrowError::InvalidArgumentError(
+                "record batch must have at least one column".into(),
+            ));
+        }
+        (batch.columns().iter())
+            .map(|array| ArrayImpl::from_arrow(array.as_ref()))
+            .collect()
+    }
+}
+
+/// Returns the Arrow type that arrays of type `ty` are casted to before they are converted,
+/// or `None` if they can not be converted.
+fn canonical_arrow_type(ty: &ArrowDataType) -> Option<ArrowDataType> {
+    use ArrowDataType::*;
+    Some(match ty {
+        Boolean | Int16 | Int32 | Int64 | Float64 | Utf8 | Binary | Date32 => ty.clone(),
+        Int8 | UInt8 => Int16,
+        UInt16 => Int32,
+        UInt32 => Int64,
+        Float16 | Float32 => Float64,
+        Decimal128(_, scale) if *scale >= 0 => ty.clone(),
+        LargeUtf8 => Utf8,
+        LargeBinary => Binary,
+        Date64 => Date32,
+        Timestamp(..) => Timestamp(TimeUnit::Microsecond, None),
+        Interval(_) => Interval(IntervalUnit::MonthDayNano),
+        Dictionary(_, value) if matches!(**value, Utf8 | LargeUtf8) => {
+            Dictionary(Box::new(UInt32), Box::new(Utf8))
+        }
+        _ => return None,
+    })
+}
+
+/// Convert an Arrow dictionary array of strings. The dictionary is kept if its strings are
+/// distinct and not null, and the strings are decoded otherwise.
+fn dictionary_from_arrow(array: &dyn ArrowArray) -> ArrayImpl {
+    let array = as_dictionary_array::<UInt32Type>(array);
+    let values = as_string_array(array.values());
+    let mut distinct = HashSet::new();
+    if values.null_count() == 0 && values.iter().all(|s| distinct.insert(s)) {
+        let codes = array.keys().iter().collect();
+        let dict = values.iter().collect::<Utf8Array>();
+        return ArrayImpl::Dictionary(DictionaryArray::new(codes, dict.into()));
+    }
+    let strings = (array.keys_iter())
+        .map(|code| code.and_then(|code| values.is_valid(code).then(|| values.value(code))));
+    ArrayImpl::Utf8(strings.collect())
+}
+
+/// Build the data of an Arrow primitive array by copying the values.
+fn primitive_data<T: ArrowPrimitiveType>(
+    values: &[T::Native],
+    validity: &BitSlice,
+) -> Result<ArrayData, ArrowError> {
+    ArrayData::builder(T::DATA_TYPE)
+        .len(values.len())
+        .add_buffer(Buffer::from_slice_ref(values))
+        .null_bit_buffer(null_buffer(validity))
+        .build()
+}
+
+/// Build the data of an Arrow string or binary array by copying the bytes.
+fn var_data<T: VarItem + ?Sized>(
+    ty: ArrowDataType,
+    array: &VarArray<T>,
+) -> Result<ArrayData, ArrowError> {
+    let offsets = (array.offsets().iter())
+        .map(|offset| i32::try_from(*offset))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| ArrowError::ComputeError("array too large".into()))?;
+    ArrayData::builder(ty)
+        .len(array.len())
+        .add_buffer(Buffer::from_slice_ref(offsets))
+        .add_buffer(Buffer::from_slice_ref(array.data()))
+        .null_bit_buffer(null_buffer(array.validity()))
+        .build()
+}
+
+/// Returns the validity bitmap in Arrow's layout, or `None` if there are no NULLs.
+fn null_buffer(validity: &BitSlice) -> Option<Buffer> {
+    match validity.all() {
+        true => None,
+        false => Some(validity.iter().by_vals().collect()),
+    }
+}
+
+/// Returns the value of the decimal rounded to [`DECIMAL_SCALE`], in units of its last digit.
+fn decimal_to_i128(v: Decimal) -> i128 {
+    let v = v.round_dp(DECIMAL_SCALE);
+    v.mantissa() * 10i128.pow(DECIMAL_SCALE - v.scale())
+}
+
+/// Returns the decimal of `v` in units of the `scale`-th fractional digit, dropping trailing
+/// zeros if there are too many digits, or `None` if it is still out of range.
+fn decimal_from_i128(mut v: i128, mut scale: u32) -> Option<Decimal> {
+    loop {
+        if let Ok(v) = Decimal::try_from_i128_with_scale(v, scale) {
+            return Some(v);
+        }
+        if scale == 0 || v % 10 != 0 {
+            return None;
+        }
+        v /= 10;
+        scale -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(array: ArrayImpl) {
+        let arrow = array.to_arrow().unwrap();
+        assert_eq!(arrow.len(), array.len());
+        assert!(ArrayImpl::from_arrow(arrow.as_ref()).unwrap() == array);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        roundtrip(ArrayImpl::Bool(
+            [Some(true), None, Some(false)].into_iter().collect(),
+        ));
+        roundtrip(ArrayImpl::Int16(
+            [Some(1), None, Some(-3)].into_iter().collect(),
+        ));
+        roundtrip(ArrayImpl::Int32(
+            (0..100).map(|i| (i % 3 != 0).then_some(i)).collect(),
+        ));
+        roundtrip(ArrayImpl::Int64([i64::MIN, i64::MAX].into_iter().collect()));
+        roundtrip(ArrayImpl::Float64([Some(1.5), None].into_iter().collect()));
+        let decimals = ["1.5", "-0.000000001", "79228162514264337593543950335"];
+        let decimals = decimals.map(|s| Some(s.parse::<Decimal>().unwrap()));
+        roundtrip(ArrayImpl::Decimal(
+            decimals.into_iter().chain([None]).collect(),
+        ));
+        roundtrip(ArrayImpl::Utf8(
+            [Some("a"), None, Some(""), Some("é")].into_iter().collect(),
+        ));
+        roundtrip(ArrayImpl::Bytes(
+            [Some(&[0u8, 255][..]), None].into_iter().collect(),
+        ));
+        let strings = [Some("x"), None, Some("y"), Some("x")];
+        roundtrip(ArrayImpl::Dictionary(DictionaryArray::encode(
+            &strings.into_iter().collect(),
+        )));
+        let date = Date::from_days(19000).unwrap();
+        roundtrip(ArrayImpl::Date([Some(date), None].into_iter().collect()));
+        let timestamp = Timestamp::from_micros(1_600_000_000_123_456).unwrap();
+        roundtrip(ArrayImpl::Timestamp(
+            [None, Some(timestamp)].into_iter().collect(),
+        ));
+        let interval = Interval::new(1, -2, 3_000_000);
+        roundtrip(ArrayImpl::Interval(
+            [Some(interval), None].into_iter().collect(),
+        ));
+    }
+
+    #[test]
+    fn test_from_arrow_casts() {
+        let array = ::arrow::array::Int8Array::from(vec![Some(1), None]);
+        let expected = [Some(1), None].into_iter().collect::<I16Array>();
+        assert!(ArrayImpl::from_arrow(&array).unwrap() == expected.into());
+
+        // a dictionary with duplicated strings is decoded
+        let keys = ::arrow::array::Int8Array::from(vec![0, 1, 2]);
+        let values = StringArray::from(vec!["a", "b", "a"]);
+        let array = ::arrow::array::DictionaryArray::try_new(&keys, &values).unwrap();
+        let expected = ["a", "b", "a"].map(Some).into_iter().collect::<Utf8Array>();
+        assert!(ArrayImpl::from_arrow(&array).unwrap() == expected.into());
+    }
+
+    #[test]
+    fn test_record_batch() {
+        let chunk: DataChunk = [
+            ArrayImpl::Int32([1, 2, 3].into_iter().collect()),
+            ArrayImpl::Decimal(
+                ["1.25", "2", "3.5"]
+                    .map(|s| s.parse::<Decimal>().unwrap())
+                    .into_iter()
+                    .collect(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let chunk = chunk.with_visibility(&[true, false, true]);
+        let schema = Arc::new(::arrow::datatypes::Schema::new(vec![
+            Field::new("a", ArrowDataType::Int32, false),
+            Field::new("b", ArrowDataType::Decimal128(10, 2), false),
+        ]));
+        let batch = chunk.to_record_batch(schema).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(
+            batch.column(1).data_type(),
+            &ArrowDataType::Decimal128(10, 2)
+        );
+        let expected = chunk.compact();
+        assert!(DataChunk::from_record_batch(&batch).unwrap() == expected);
+    }
+}
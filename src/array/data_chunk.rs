@@ -5,9 +5,15 @@ use itertools::Itertools;
 
 use super::*;
 
+/// Chunks that are filtered to a lower ratio of visible rows are compacted.
+const MIN_DENSITY: f64 = 0.5;
+
 #[derive(PartialEq, Clone)]
 pub struct DataChunk {
     arrays: Arc<[ArrayImpl]>,
+    /// Whether each row is visible, or `None` if all rows are. Rows that are filtered out are
+    /// kept in the arrays until the chunk is compacted.
+    visibility: Option<Arc<[bool]>>,
 }
 
 impl FromIterator<ArrayImpl> for DataChunk {
@@ -19,7 +25,10 @@ impl FromIterator<ArrayImpl> for DataChunk {
             arrays.iter().map(|a| a.len()).all(|l| l == cardinality),
             "all arrays must have the same length"
         );
-        DataChunk { arrays }
+        DataChunk {
+            arrays,
+            visibility: None,
+        }
     }
 }
 
@@ -29,17 +38,89 @@ impl DataChunk {
             arrays: [ArrayImpl::Int32([item].into_iter().collect())]
                 .into_iter()
                 .collect(),
+            visibility: None,
         }
     }
 
+    /// Returns the number of visible rows.
     pub fn cardinality(&self) -> usize {
+        match &self.visibility {
+            Some(visibility) => visibility.iter().filter(|v| **v).count(),
+            None => self.capacity(),
+        }
+    }
+
+    /// Returns the number of rows in the arrays, including the invisible ones.
+    pub fn capacity(&self) -> usize {
         self.arrays[0].len()
     }
 
+    /// Returns the arrays, which include the invisible rows.
     pub fn arrays(&self) -> &[ArrayImpl] {
         &self.arrays
     }
 
+    /// Returns whether each row is visible, or `None` if all rows are.
+    pub fn visibility(&self) -> Option<&[bool]> {
+        self.visibility.as_deref()
+    }
+
+    pub fn is_visible(&self, idx: usize) -> bool {
+        self.visibility.as_ref().map_or(true, |v| v[idx])
+    }
+
+    /// Returns the indexes of the visible rows.
+    pub fn visible_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.capacity()).filter(|i| self.is_visible(*i))
+    }
+
+    /// Returns the values of the row at `idx`, which counts the invisible rows.
+    pub fn row(&self, idx: usize) -> Vec<DataValue> {
+        self.arrays.iter().map(|a| a.get(idx)).collect()
+    }
+
+    /// Keep the rows whose visibility is `true` and copy them into new arrays. `visibility`
+    /// covers all rows, and the visibility of the chunk is ignored.
+    pub fn filter(&self, visibility: &[bool]) -> Self {
+        self.arrays.iter().map(|a| a.filter(visibility)).collect()
+    }
+
+    /// Hide the rows whose visibility is `false` without copying the arrays, unless few rows
+    /// would be visible, in which case the chunk is compacted.
+    pub fn with_visibility(self, visibility: &[bool]) -> Self {
+        assert_eq!(
+            visibility.len(),
+            self.capacity(),
+            "visibility length mismatch"
+        );
+        let visibility: Arc<[bool]> = match &self.visibility {
+            Some(old) => old.iter().zip(visibility).map(|(a, b)| *a && *b).collect(),
+            None => visibility.into(),
+        };
+        let visible = visibility.iter().filter(|v| **v).count();
+        if visible == visibility.len() {
+            return DataChunk {
+                visibility: None,
+                ..self
+            };
+        }
+        if (visible as f64) < visibility.len() as f64 * MIN_DENSITY {
+            return self.filter(&visibility);
+        }
+        DataChunk {
+            visibility: Some(visibility),
+            ..self
+        }
+    }
+
+    /// Copy the visible rows into new arrays, so that all rows of the chunk are visible.
+    pub fn compact(self) -> Self {
+        match &self.visibility {
+            Some(visibility) => self.filter(visibility),
+            None => self,
+        }
+    }
+
     pub fn concat(chunks: &[DataChunk]) -> Self {
         assert!(!chunks.is_empty(), "must concat at least one chunk");
         let mut builders = chunks[0]
@@ -48,6 +129,7 @@ impl DataChunk {
             .map(ArrayBuilderImpl::from_type_of_array)
             .collect_vec();
         for chunk in chunks {
+            let chunk = chunk.clone().compact();
             for (array, builder) in chunk.arrays.iter().zip(builders.iter_mut()) {
                 builder.append(array);
             }
@@ -61,7 +143,7 @@ impl fmt::Display for DataChunk {
         use comfy_table::{Row, Table};
         let mut table = Table::new();
         table.load_preset("||--+-++|    ++++++");
-        for i in 0..self.cardinality() {
+        for i in self.visible_rows() {
             let row = self
                 .arrays
                 .iter()
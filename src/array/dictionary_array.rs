@@ -0,0 +1,226 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::*;
+use crate::types::ConvertError;
+
+/// A string array stored as codes into a dictionary of distinct strings.
+///
+/// The dictionary is shared by the arrays derived from this one, e.g. by filtering, and the
+/// strings are only copied out of it when the array is decoded.
+#[derive(Clone, PartialEq)]
+pub struct DictionaryArray {
+    codes: PrimitiveArray<u32>,
+    dict: Arc<Utf8Array>,
+}
+
+impl DictionaryArray {
+    /// Create an array from its codes and dictionary. The strings in the dictionary must be
+    /// distinct and not null.
+    pub fn new(codes: PrimitiveArray<u32>, dict: Arc<Utf8Array>) -> Self {
+        debug_assert!(
+            codes.iter().flatten().all(|c| (*c as usize) < dict.len()),
+            "dictionary code out of range"
+        );
+        DictionaryArray { codes, dict }
+    }
+
+    /// Dictionary-encode a string array.
+    pub fn encode(array: &Utf8Array) -> Self {
+        let mut builder = DictionaryArrayBuilder::with_capacity(array.len());
+        for s in array.iter() {
+            builder.push(s);
+        }
+        builder.finish()
+    }
+
+    /// Returns an array of `len` copies of `value`, whose dictionary has a single string.
+    pub fn repeat(value: Option<&str>, len: usize) -> Self {
+        let dict = Arc::new(value.into_iter().map(Some).collect::<Utf8Array>());
+        let codes = (0..len).map(|_| value.map(|_| 0)).collect();
+        DictionaryArray { codes, dict }
+    }
+
+    pub fn codes(&self) -> &PrimitiveArray<u32> {
+        &self.codes
+    }
+
+    pub fn dictionary(&self) -> &Arc<Utf8Array> {
+        &self.dict
+    }
+
+    /// Copy the strings out of the dictionary.
+    pub fn decode(&self) -> Utf8Array {
+        self.iter().collect()
+    }
+
+    /// Keep the rows whose visibility is `true`. The dictionary is shared with this array.
+    pub fn filter(&self, visibility: &[bool]) -> Self {
+        DictionaryArray {
+            codes: filter(&self.codes, visibility),
+            dict: self.dict.clone(),
+        }
+    }
+
+    /// Apply `f` on each string. `f` is called only once for each distinct string.
+    pub fn map<O, F>(&self, mut f: F) -> Result<O, ConvertError>
+    where
+        O: Array,
+        F: FnMut(&str) -> Result<<O::Item as ToOwned>::Owned, ConvertError>,
+    {
+        let mut results = (0..self.dict.len()).map(|_| None).collect::<Vec<_>>();
+        let mut builder = O::Builder::with_capacity(self.len());
+        for code in self.codes.iter() {
+            let Some(&code) = code else {
+                builder.push(None);
+                continue;
+            };
+            let result = match &mut results[code as usize] {
+                Some(result) => result,
+                result => result.insert(f(self.dict.get(code as usize).unwrap())?),
+            };
+            builder.push(Some((*result).borrow()));
+        }
+        Ok(builder.finish())
+    }
+
+    /// Apply `f` on each pair of strings, or returns `None` if there may be more distinct
+    /// pairs than rows, in which case it is cheaper to decode the arrays.
+    pub fn zip_map<F>(&self, other: &Self, f: F) -> Option<BoolArray>
+    where
+        F: Fn(&str, &str) -> bool,
+    {
+        assert_eq!(self.len(), other.len(), "array length mismatch");
+        let (width, height) = (self.dict.len(), other.dict.len());
+        if width.saturating_mul(height) > self.len() {
+            return None;
+        }
+        let mut results = vec![None; width * height];
+        let pairs = self.codes.iter().zip(other.codes.iter());
+        let array = pairs
+            .map(|(a, b)| {
+                let (a, b) = (*a? as usize, *b? as usize);
+                let result = results[a * height + b].get_or_insert_with(|| {
+                    f(self.dict.get(a).unwrap(), other.dict.get(b).unwrap())
+                });
+                Some(*result)
+            })
+            .collect();
+        Some(array)
+    }
+}
+
+impl Array for DictionaryArray {
+    type Item = str;
+    type Builder = DictionaryArrayBuilder;
+
+    fn get(&self, idx: usize) -> Option<&str> {
+        let code = *self.codes.get(idx)?;
+        self.dict.get(code as usize)
+    }
+
+    fn len(&self) -> usize {
+        self.codes.len()
+    }
+}
+
+pub struct DictionaryArrayBuilder {
+    codes: PrimitiveArrayBuilder<u32>,
+    dict: Utf8ArrayBuilder,
+    index: HashMap<String, u32>,
+    /// The dictionary of the last appended array and the codes of its strings in this
+    /// builder, so that appending arrays with the same dictionary looks up each string once.
+    appended: Option<(Arc<Utf8Array>, Vec<Option<u32>>)>,
+}
+
+impl DictionaryArrayBuilder {
+    fn code_of(&mut self, s: &str) -> u32 {
+        if let Some(code) = self.index.get(s) {
+            return *code;
+        }
+        let code = self.index.len() as u32;
+        self.index.insert(s.to_string(), code);
+        self.dict.push(Some(s));
+        code
+    }
+}
+
+impl ArrayBuilder for DictionaryArrayBuilder {
+    type Array = DictionaryArray;
+
+    fn with_capacity(capacity: usize) -> Self {
+        DictionaryArrayBuilder {
+            codes: PrimitiveArrayBuilder::with_capacity(capacity),
+            dict: Utf8ArrayBuilder::with_capacity(0),
+            index: HashMap::new(),
+            appended: None,
+        }
+    }
+
+    fn push(&mut self, value: Option<&str>) {
+        let code = value.map(|s| self.code_of(s));
+        self.codes.push(code.as_ref());
+    }
+
+    fn append(&mut self, other: &DictionaryArray) {
+        let mut appended = match self.appended.take() {
+            Some((dict, codes)) if Arc::ptr_eq(&dict, &other.dict) => (dict, codes),
+            _ => (other.dict.clone(), vec![None; other.dict.len()]),
+        };
+        for code in other.codes.iter() {
+            let code = code.map(|&code| match appended.1[code as usize] {
+                Some(code) => code,
+                None => {
+                    let new_code = self.code_of(other.dict.get(code as usize).unwrap());
+                    appended.1[code as usize] = Some(new_code);
+                    new_code
+                }
+            });
+            self.codes.push(code.as_ref());
+        }
+        self.appended = Some(appended);
+    }
+
+    fn finish(self) -> DictionaryArray {
+        DictionaryArray {
+            codes: self.codes.finish(),
+            dict: Arc::new(self.dict.finish()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let strings = [Some("a"), None, Some("b"), Some("a"), Some("b")];
+        let array = DictionaryArray::encode(&strings.into_iter().collect());
+        assert_eq!(array.dictionary().len(), 2);
+        assert_eq!(array.iter().collect::<Vec<_>>(), strings);
+        assert_eq!(array.decode().iter().collect::<Vec<_>>(), strings);
+
+        let filtered = array.filter(&[true, true, false, false, true]);
+        assert!(Arc::ptr_eq(filtered.dictionary(), array.dictionary()));
+        assert_eq!(
+            filtered.iter().collect::<Vec<_>>(),
+            [Some("a"), None, Some("b")]
+        );
+    }
+
+    #[test]
+    fn test_append() {
+        let a = DictionaryArray::encode(&[Some("x"), Some("y")].into_iter().collect());
+        let b = DictionaryArray::encode(&[Some("y"), Some("z")].into_iter().collect());
+        let mut builder = DictionaryArrayBuilder::with_capacity(0);
+        builder.append(&a);
+        builder.append(&b);
+        builder.append(&a);
+        let array = builder.finish();
+        assert_eq!(array.dictionary().len(), 3);
+        let strings = ["x", "y", "y", "z", "x", "y"].map(Some);
+        assert_eq!(array.iter().collect::<Vec<_>>(), strings);
+    }
+}
@@ -0,0 +1,155 @@
+//! Pattern matching for `LIKE` and `ILIKE`.
+
+use std::borrow::Cow;
+
+use super::*;
+use crate::types::ConvertError;
+
+/// A compiled `LIKE` pattern.
+///
+/// `%` matches any sequence of characters and `_` matches any single character. Patterns that
+/// only have `%` at either end are matched with plain string searches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LikePattern {
+    matcher: Matcher,
+    case_insensitive: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Matcher {
+    /// `abc`
+    Exact(String),
+    /// `abc%`
+    Prefix(String),
+    /// `%abc`
+    Suffix(String),
+    /// `%abc%`
+    Contains(String),
+    /// Any other pattern.
+    Wildcard(Vec<Token>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Char(char),
+    /// `_`
+    AnyChar,
+    /// `%`
+    AnyString,
+}
+
+impl LikePattern {
+    /// Compile a pattern. The escape character is `\` by default.
+    pub fn new(
+        pattern: &str,
+        escape_char: Option<char>,
+        case_insensitive: bool,
+    ) -> Result<Self, ConvertError> {
+        let escape_char = escape_char.unwrap_or('\\');
+        let pattern = match case_insensitive {
+            true => Cow::Owned(pattern.to_lowercase()),
+            false => Cow::Borrowed(pattern),
+        };
+        let mut tokens = vec![];
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            tokens.push(match c {
+                c if c == escape_char => match chars.next() {
+                    Some(c) => Token::Char(c),
+                    None => {
+                        return Err(ConvertError::InvalidArgument(
+                            "LIKE pattern must not end with escape character".into(),
+                        ))
+                    }
+                },
+                '%' => Token::AnyString,
+                '_' => Token::AnyChar,
+                c => Token::Char(c),
+            });
+        }
+        Ok(LikePattern {
+            matcher: Matcher::new(tokens),
+            case_insensitive,
+        })
+    }
+
+    /// Returns true if `s` matches the pattern.
+    pub fn matches(&self, s: &str) -> bool {
+        let s = match self.case_insensitive {
+            true => Cow::Owned(s.to_lowercase()),
+            false => Cow::Borrowed(s),
+        };
+        match &self.matcher {
+            Matcher::Exact(p) => s == p.as_str(),
+            Matcher::Prefix(p) => s.starts_with(p.as_str()),
+            Matcher::Suffix(p) => s.ends_with(p.as_str()),
+            Matcher::Contains(p) => s.contains(p.as_str()),
+            Matcher::Wildcard(tokens) => wildcard_match(tokens, &s),
+        }
+    }
+}
+
+impl Matcher {
+    fn new(tokens: Vec<Token>) -> Self {
+        let is_any_string = |t: &Token| *t == Token::AnyString;
+        let leading = tokens.iter().take_while(|t| is_any_string(t)).count();
+        let trailing = tokens[leading..]
+            .iter()
+            .rev()
+            .take_while(|t| is_any_string(t))
+            .count();
+        let literal = tokens[leading..tokens.len() - trailing]
+            .iter()
+            .map(|t| match t {
+                Token::Char(c) => Some(*c),
+                _ => None,
+            })
+            .collect::<Option<String>>();
+        match (literal, leading > 0, trailing > 0) {
+            (Some(s), false, false) => Matcher::Exact(s),
+            (Some(s), false, true) => Matcher::Prefix(s),
+            (Some(s), true, false) => Matcher::Suffix(s),
+            (Some(s), true, true) => Matcher::Contains(s),
+            (None, _, _) => Matcher::Wildcard(tokens),
+        }
+    }
+}
+
+/// Match `s` against the tokens. On a mismatch, the last `%` is retried with one more
+/// character, so it runs in O(len(s) * len(tokens)) at worst.
+fn wildcard_match(tokens: &[Token], s: &str) -> bool {
+    let (mut t, mut i) = (0, 0);
+    // the position of the last `%` and the start of the string it matches
+    let mut backtrack = None;
+    while i < s.len() {
+        let c = s[i..].chars().next().unwrap();
+        match tokens.get(t) {
+            Some(Token::AnyString) => {
+                backtrack = Some((t, i));
+                t += 1;
+                continue;
+            }
+            Some(Token::AnyChar) => {}
+            Some(Token::Char(p)) if *p == c => {}
+            _ => match backtrack {
+                Some((bt, bi)) => {
+                    let next = bi + s[bi..].chars().next().unwrap().len_utf8();
+                    backtrack = Some((bt, next));
+                    (t, i) = (bt + 1, next);
+                    continue;
+                }
+                None => return false,
+            },
+        }
+        t += 1;
+        i += c.len_utf8();
+    }
+    tokens[t..].iter().all(|t| *t == Token::AnyString)
+}
+
+impl Utf8Array {
+    /// Returns whether each string matches the pattern.
+    pub fn like(&self, pattern: &LikePattern) -> BoolArray {
+        self.iter().map(|s| s.map(|s| pattern.matches(s))).collect()
+    }
+}
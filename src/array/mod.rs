@@ -1,16 +1,26 @@
 use std::convert::TryFrom;
 
-use crate::types::{DataType, DataTypeKind, DataValue};
+use crate::types::{
+    DataType, DataTypeKind, DataTypeKindExt, DataValue, Date, Decimal, Interval, Timestamp,
+};
 
+mod arrow;
 mod data_chunk;
+mod dictionary_array;
 mod iter;
+mod like;
+mod ops;
 mod primitive_array;
-mod utf8_array;
+mod var_array;
 
+pub use self::arrow::*;
 pub use self::data_chunk::*;
+pub use self::dictionary_array::*;
 pub use self::iter::ArrayIter;
+pub use self::like::*;
+pub use self::ops::*;
 pub use self::primitive_array::*;
-pub use self::utf8_array::*;
+pub use self::var_array::*;
 
 pub trait ArrayBuilder: Send + Sync + 'static {
     type Array: Array<Builder = Self>;
@@ -43,26 +53,54 @@ pub trait Array: Sized + Send + Sync + 'static {
 }
 
 pub type BoolArray = PrimitiveArray<bool>;
+pub type I16Array = PrimitiveArray<i16>;
 pub type I32Array = PrimitiveArray<i32>;
+pub type I64Array = PrimitiveArray<i64>;
 pub type F64Array = PrimitiveArray<f64>;
+pub type DecimalArray = PrimitiveArray<Decimal>;
+pub type DateArray = PrimitiveArray<Date>;
+pub type TimestampArray = PrimitiveArray<Timestamp>;
+pub type IntervalArray = PrimitiveArray<Interval>;
 
 #[derive(Clone, PartialEq)]
 pub enum ArrayImpl {
     Bool(BoolArray),
+    Int16(I16Array),
     Int32(I32Array),
+    Int64(I64Array),
     Float64(F64Array),
+    Decimal(DecimalArray),
     Utf8(Utf8Array),
+    Bytes(BytesArray),
+    Dictionary(DictionaryArray),
+    Date(DateArray),
+    Timestamp(TimestampArray),
+    Interval(IntervalArray),
 }
 
 pub type BoolArrayBuilder = PrimitiveArrayBuilder<bool>;
+pub type I16ArrayBuilder = PrimitiveArrayBuilder<i16>;
 pub type I32ArrayBuilder = PrimitiveArrayBuilder<i32>;
+pub type I64ArrayBuilder = PrimitiveArrayBuilder<i64>;
 pub type F64ArrayBuilder = PrimitiveArrayBuilder<f64>;
+pub type DecimalArrayBuilder = PrimitiveArrayBuilder<Decimal>;
+pub type DateArrayBuilder = PrimitiveArrayBuilder<Date>;
+pub type TimestampArrayBuilder = PrimitiveArrayBuilder<Timestamp>;
+pub type IntervalArrayBuilder = PrimitiveArrayBuilder<Interval>;
 
 pub enum ArrayBuilderImpl {
     Bool(BoolArrayBuilder),
+    Int16(I16ArrayBuilder),
     Int32(I32ArrayBuilder),
+    Int64(I64ArrayBuilder),
     Float64(F64ArrayBuilder),
+    Decimal(DecimalArrayBuilder),
     Utf8(Utf8ArrayBuilder),
+    Bytes(BytesArrayBuilder),
+    Dictionary(DictionaryArrayBuilder),
+    Date(DateArrayBuilder),
+    Timestamp(TimestampArrayBuilder),
+    Interval(IntervalArrayBuilder),
 }
 
 #[derive(Debug, Clone)]
@@ -101,21 +139,36 @@ macro_rules! impl_into {
 }
 
 impl_into! { PrimitiveArray<bool>, Bool }
+impl_into! { PrimitiveArray<i16>, Int16 }
 impl_into! { PrimitiveArray<i32>, Int32 }
+impl_into! { PrimitiveArray<i64>, Int64 }
 impl_into! { PrimitiveArray<f64>, Float64 }
+impl_into! { PrimitiveArray<Decimal>, Decimal }
 impl_into! { Utf8Array, Utf8 }
+impl_into! { BytesArray, Bytes }
+impl_into! { DictionaryArray, Dictionary }
+impl_into! { PrimitiveArray<Date>, Date }
+impl_into! { PrimitiveArray<Timestamp>, Timestamp }
+impl_into! { PrimitiveArray<Interval>, Interval }
 
 impl ArrayBuilderImpl {
     pub fn with_capacity(capacity: usize, ty: &DataType) -> Self {
-        match ty.kind() {
+        match ty.kind().canonical() {
             DataTypeKind::Boolean => Self::Bool(BoolArrayBuilder::with_capacity(capacity)),
+            DataTypeKind::SmallInt(_) => Self::Int16(I16ArrayBuilder::with_capacity(capacity)),
             DataTypeKind::Int(_) => Self::Int32(I32ArrayBuilder::with_capacity(capacity)),
-            DataTypeKind::Float(_) | DataTypeKind::Double => {
-                Self::Float64(F64ArrayBuilder::with_capacity(capacity))
+            DataTypeKind::BigInt(_) => Self::Int64(I64ArrayBuilder::with_capacity(capacity)),
+            kind if kind.is_float() => Self::Float64(F64ArrayBuilder::with_capacity(capacity)),
+            kind if kind.is_decimal() => {
+                Self::Decimal(DecimalArrayBuilder::with_capacity(capacity))
             }
-            DataTypeKind::Char(_) | DataTypeKind::Varchar(_) | DataTypeKind::String => {
-                Self::Utf8(Utf8ArrayBuilder::with_capacity(capacity))
+            kind if kind.is_string() => Self::Utf8(Utf8ArrayBuilder::with_capacity(capacity)),
+            DataTypeKind::Bytea => Self::Bytes(BytesArrayBuilder::with_capacity(capacity)),
+            DataTypeKind::Date => Self::Date(DateArrayBuilder::with_capacity(capacity)),
+            DataTypeKind::Timestamp(..) => {
+                Self::Timestamp(TimestampArrayBuilder::with_capacity(capacity))
             }
+            DataTypeKind::Interval => Self::Interval(IntervalArrayBuilder::with_capacity(capacity)),
             _ => panic!("unsupported data type"),
         }
     }
@@ -123,22 +176,46 @@ impl ArrayBuilderImpl {
     pub fn from_type_of_array(array: &ArrayImpl) -> Self {
         match array {
             ArrayImpl::Bool(_) => Self::Bool(BoolArrayBuilder::with_capacity(0)),
+            ArrayImpl::Int16(_) => Self::Int16(I16ArrayBuilder::with_capacity(0)),
             ArrayImpl::Int32(_) => Self::Int32(I32ArrayBuilder::with_capacity(0)),
+            ArrayImpl::Int64(_) => Self::Int64(I64ArrayBuilder::with_capacity(0)),
             ArrayImpl::Float64(_) => Self::Float64(F64ArrayBuilder::with_capacity(0)),
+            ArrayImpl::Decimal(_) => Self::Decimal(DecimalArrayBuilder::with_capacity(0)),
             ArrayImpl::Utf8(_) => Self::Utf8(Utf8ArrayBuilder::with_capacity(0)),
+            ArrayImpl::Bytes(_) => Self::Bytes(BytesArrayBuilder::with_capacity(0)),
+            ArrayImpl::Dictionary(_) => Self::Dictionary(DictionaryArrayBuilder::with_capacity(0)),
+            ArrayImpl::Date(_) => Self::Date(DateArrayBuilder::with_capacity(0)),
+            ArrayImpl::Timestamp(_) => Self::Timestamp(TimestampArrayBuilder::with_capacity(0)),
+            ArrayImpl::Interval(_) => Self::Interval(IntervalArrayBuilder::with_capacity(0)),
         }
     }
 
     pub fn push(&mut self, v: &DataValue) {
         match (self, v) {
             (Self::Bool(a), DataValue::Bool(v)) => a.push(Some(v)),
+            (Self::Int16(a), DataValue::Int16(v)) => a.push(Some(v)),
             (Self::Int32(a), DataValue::Int32(v)) => a.push(Some(v)),
+            (Self::Int64(a), DataValue::Int64(v)) => a.push(Some(v)),
             (Self::Float64(a), DataValue::Float64(v)) => a.push(Some(v)),
+            (Self::Decimal(a), DataValue::Decimal(v)) => a.push(Some(v)),
             (Self::Utf8(a), DataValue::String(v)) => a.push(Some(v)),
+            (Self::Bytes(a), DataValue::Bytes(v)) => a.push(Some(v)),
+            (Self::Dictionary(a), DataValue::String(v)) => a.push(Some(v)),
+            (Self::Date(a), DataValue::Date(v)) => a.push(Some(v)),
+            (Self::Timestamp(a), DataValue::Timestamp(v)) => a.push(Some(v)),
+            (Self::Interval(a), DataValue::Interval(v)) => a.push(Some(v)),
             (Self::Bool(a), DataValue::Null) => a.push(None),
+            (Self::Int16(a), DataValue::Null) => a.push(None),
             (Self::Int32(a), DataValue::Null) => a.push(None),
+            (Self::Int64(a), DataValue::Null) => a.push(None),
             (Self::Float64(a), DataValue::Null) => a.push(None),
+            (Self::Decimal(a), DataValue::Null) => a.push(None),
             (Self::Utf8(a), DataValue::Null) => a.push(None),
+            (Self::Bytes(a), DataValue::Null) => a.push(None),
+            (Self::Dictionary(a), DataValue::Null) => a.push(None),
+            (Self::Date(a), DataValue::Null) => a.push(None),
+            (Self::Timestamp(a), DataValue::Null) => a.push(None),
+            (Self::Interval(a), DataValue::Null) => a.push(None),
             _ => panic!("failed to push value: type mismatch"),
         }
     }
@@ -146,9 +223,24 @@ impl ArrayBuilderImpl {
     pub fn append(&mut self, array_impl: &ArrayImpl) {
         match (self, array_impl) {
             (Self::Bool(builder), ArrayImpl::Bool(arr)) => builder.append(arr),
+            (Self::Int16(builder), ArrayImpl::Int16(arr)) => builder.append(arr),
             (Self::Int32(builder), ArrayImpl::Int32(arr)) => builder.append(arr),
+            (Self::Int64(builder), ArrayImpl::Int64(arr)) => builder.append(arr),
             (Self::Float64(builder), ArrayImpl::Float64(arr)) => builder.append(arr),
+            (Self::Decimal(builder), ArrayImpl::Decimal(arr)) => builder.append(arr),
             (Self::Utf8(builder), ArrayImpl::Utf8(arr)) => builder.append(arr),
+            (Self::Bytes(builder), ArrayImpl::Bytes(arr)) => builder.append(arr),
+            (Self::Dictionary(builder), ArrayImpl::Dictionary(arr)) => builder.append(arr),
+            // strings may be dictionary-encoded or not
+            (Self::Utf8(builder), ArrayImpl::Dictionary(arr)) => {
+                arr.iter().for_each(|s| builder.push(s))
+            }
+            (Self::Dictionary(builder), ArrayImpl::Utf8(arr)) => {
+                arr.iter().for_each(|s| builder.push(s))
+            }
+            (Self::Date(builder), ArrayImpl::Date(arr)) => builder.append(arr),
+            (Self::Timestamp(builder), ArrayImpl::Timestamp(arr)) => builder.append(arr),
+            (Self::Interval(builder), ArrayImpl::Interval(arr)) => builder.append(arr),
             _ => panic!("failed to push value: type mismatch"),
         }
     }
@@ -156,9 +248,17 @@ impl ArrayBuilderImpl {
     pub fn finish(self) -> ArrayImpl {
         match self {
             Self::Bool(a) => ArrayImpl::Bool(a.finish()),
+            Self::Int16(a) => ArrayImpl::Int16(a.finish()),
             Self::Int32(a) => ArrayImpl::Int32(a.finish()),
+            Self::Int64(a) => ArrayImpl::Int64(a.finish()),
             Self::Float64(a) => ArrayImpl::Float64(a.finish()),
+            Self::Decimal(a) => ArrayImpl::Decimal(a.finish()),
             Self::Utf8(a) => ArrayImpl::Utf8(a.finish()),
+            Self::Bytes(a) => ArrayImpl::Bytes(a.finish()),
+            Self::Dictionary(a) => ArrayImpl::Dictionary(a.finish()),
+            Self::Date(a) => ArrayImpl::Date(a.finish()),
+            Self::Timestamp(a) => ArrayImpl::Timestamp(a.finish()),
+            Self::Interval(a) => ArrayImpl::Interval(a.finish()),
         }
     }
 }
@@ -170,31 +270,113 @@ impl ArrayImpl {
                 Some(val) => DataValue::Bool(*val),
                 None => DataValue::Null,
             },
+            Self::Int16(a) => match a.get(idx) {
+                Some(val) => DataValue::Int16(*val),
+                None => DataValue::Null,
+            },
             Self::Int32(a) => match a.get(idx) {
                 Some(val) => DataValue::Int32(*val),
                 None => DataValue::Null,
             },
+            Self::Int64(a) => match a.get(idx) {
+                Some(val) => DataValue::Int64(*val),
+                None => DataValue::Null,
+            },
             Self::Float64(a) => match a.get(idx) {
                 Some(val) => DataValue::Float64(*val),
                 None => DataValue::Null,
             },
+            Self::Decimal(a) => match a.get(idx) {
+                Some(val) => DataValue::Decimal(*val),
+                None => DataValue::Null,
+            },
             Self::Utf8(a) => match a.get(idx) {
                 Some(val) => DataValue::String(val.to_string()),
                 None => DataValue::Null,
             },
+            Self::Bytes(a) => match a.get(idx) {
+                Some(val) => DataValue::Bytes(val.to_vec()),
+                None => DataValue::Null,
+            },
+            Self::Dictionary(a) => match a.get(idx) {
+                Some(val) => DataValue::String(val.to_string()),
+                None => DataValue::Null,
+            },
+            Self::Date(a) => match a.get(idx) {
+                Some(val) => DataValue::Date(*val),
+                None => DataValue::Null,
+            },
+            Self::Timestamp(a) => match a.get(idx) {
+                Some(val) => DataValue::Timestamp(*val),
+                None => DataValue::Null,
+            },
+            Self::Interval(a) => match a.get(idx) {
+                Some(val) => DataValue::Interval(*val),
+                None => DataValue::Null,
+            },
+        }
+    }
+
+    pub fn is_null(&self, idx: usize) -> bool {
+        match self {
+            Self::Bool(a) => a.get(idx).is_none(),
+            Self::Int16(a) => a.get(idx).is_none(),
+            Self::Int32(a) => a.get(idx).is_none(),
+            Self::Int64(a) => a.get(idx).is_none(),
+            Self::Float64(a) => a.get(idx).is_none(),
+            Self::Decimal(a) => a.get(idx).is_none(),
+            Self::Utf8(a) => a.get(idx).is_none(),
+            Self::Bytes(a) => a.get(idx).is_none(),
+            Self::Dictionary(a) => a.get(idx).is_none(),
+            Self::Date(a) => a.get(idx).is_none(),
+            Self::Timestamp(a) => a.get(idx).is_none(),
+            Self::Interval(a) => a.get(idx).is_none(),
         }
     }
 
     pub fn len(&self) -> usize {
         match self {
             Self::Bool(a) => a.len(),
+            Self::Int16(a) => a.len(),
             Self::Int32(a) => a.len(),
+            Self::Int64(a) => a.len(),
             Self::Float64(a) => a.len(),
+            Self::Decimal(a) => a.len(),
             Self::Utf8(a) => a.len(),
+            Self::Bytes(a) => a.len(),
+            Self::Dictionary(a) => a.len(),
+            Self::Date(a) => a.len(),
+            Self::Timestamp(a) => a.len(),
+            Self::Interval(a) => a.len(),
         }
     }
 
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns an array with `len` copies of `value`. Strings are dictionary-encoded, so that
+    /// they are not copied and can be compared with other dictionary arrays by their codes.
+    pub fn repeat(value: &DataValue, ty: &DataType, len: usize) -> Self {
+        if ty.kind().is_string() {
+            let value = match value {
+                DataValue::String(s) => Some(s.as_str()),
+                _ => None,
+            };
+            return Self::Dictionary(DictionaryArray::repeat(value, len));
+        }
+        let mut builder = ArrayBuilderImpl::with_capacity(len, ty);
+        for _ in 0..len {
+            builder.push(value);
+        }
+        builder.finish()
+    }
+
+    /// Decode a dictionary array into a plain string array. Other arrays are returned as is.
+    pub fn decode(self) -> Self {
+        match self {
+            Self::Dictionary(a) => Self::Utf8(a.decode()),
+            a => a,
+        }
+    }
 }
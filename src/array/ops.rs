@@ -0,0 +1,357 @@
+use std::borrow::Borrow;
+use std::sync::Arc;
+
+use super::*;
+use crate::parser::{BinaryOperator, UnaryOperator};
+use crate::types::{ConvertError, Date, Decimal};
+
+/// Apply `f` on each pair of items. The output is null if either input is null.
+pub fn binary_op<A, B, O, F>(a: &A, b: &B, f: F) -> Result<O, ConvertError>
+where
+    A: Array,
+    B: Array,
+    O: Array,
+    F: Fn(&A::Item, &B::Item) -> Result<<O::Item as ToOwned>::Owned, ConvertError>,
+{
+    assert_eq!(a.len(), b.len(), "array length mismatch");
+    let mut builder = O::Builder::with_capacity(a.len());
+    for (a, b) in a.iter().zip(b.iter()) {
+        match (a, b) {
+            (Some(a), Some(b)) => builder.push(Some(f(a, b)?.borrow())),
+            _ => builder.push(None),
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Apply `f` on each item. The output is null if the input is null.
+pub fn unary_op<A, O, F>(a: &A, f: F) -> Result<O, ConvertError>
+where
+    A: Array,
+    O: Array,
+    F: Fn(&A::Item) -> Result<<O::Item as ToOwned>::Owned, ConvertError>,
+{
+    let mut builder = O::Builder::with_capacity(a.len());
+    for a in a.iter() {
+        match a {
+            Some(a) => builder.push(Some(f(a)?.borrow())),
+            None => builder.push(None),
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Keep the items whose visibility is `true`.
+pub fn filter<A: Array>(a: &A, visibility: &[bool]) -> A {
+    assert_eq!(a.len(), visibility.len(), "array length mismatch");
+    let mut builder = A::Builder::with_capacity(visibility.iter().filter(|v| **v).count());
+    for (item, &visible) in a.iter().zip(visibility) {
+        if visible {
+            builder.push(item);
+        }
+    }
+    builder.finish()
+}
+
+macro_rules! cmp {
+    ($a:expr, $b:expr, $op:tt) => {
+        match ($a, $b) {
+            (ArrayImpl::Bool(a), ArrayImpl::Bool(b)) => {
+                ArrayImpl::Bool(binary_op(a, b, |a, b| Ok(a $op b))?)
+            }
+            (ArrayImpl::Int16(a), ArrayImpl::Int16(b)) => {
+                ArrayImpl::Bool(binary_op(a, b, |a, b| Ok(a $op b))?)
+            }
+            (ArrayImpl::Int32(a), ArrayImpl::Int32(b)) => {
+                ArrayImpl::Bool(binary_op(a, b, |a, b| Ok(a $op b))?)
+            }
+            (ArrayImpl::Int64(a), ArrayImpl::Int64(b)) => {
+                ArrayImpl::Bool(binary_op(a, b, |a, b| Ok(a $op b))?)
+            }
+            (ArrayImpl::Float64(a), ArrayImpl::Float64(b)) => {
+                ArrayImpl::Bool(binary_op(a, b, |a, b| Ok(a $op b))?)
+            }
+            (ArrayImpl::Decimal(a), ArrayImpl::Decimal(b)) => {
+                ArrayImpl::Bool(binary_op(a, b, |a, b| Ok(a $op b))?)
+            }
+            (ArrayImpl::Utf8(a), ArrayImpl::Utf8(b)) => {
+                ArrayImpl::Bool(binary_op(a, b, |a, b| Ok(a $op b))?)
+            }
+            (ArrayImpl::Bytes(a), ArrayImpl::Bytes(b)) => {
+                ArrayImpl::Bool(binary_op(a, b, |a, b| Ok(a $op b))?)
+            }
+            (ArrayImpl::Date(a), ArrayImpl::Date(b)) => {
+                ArrayImpl::Bool(binary_op(a, b, |a, b| Ok(a $op b))?)
+            }
+            (ArrayImpl::Timestamp(a), ArrayImpl::Timestamp(b)) => {
+                ArrayImpl::Bool(binary_op(a, b, |a, b| Ok(a $op b))?)
+            }
+            (ArrayImpl::Interval(a), ArrayImpl::Interval(b)) => {
+                ArrayImpl::Bool(binary_op(a, b, |a, b| Ok(a $op b))?)
+            }
+            _ => panic!("type mismatch in comparison"),
+        }
+    };
+}
+
+macro_rules! arith {
+    ($a:expr, $b:expr, $int_op:ident, $float_op:tt) => {
+        match ($a, $b) {
+            (ArrayImpl::Int16(a), ArrayImpl::Int16(b)) => ArrayImpl::Int16(binary_op(
+                a,
+                b,
+                |a: &i16, b: &i16| a.$int_op(*b).ok_or(ConvertError::Overflow),
+            )?),
+            (ArrayImpl::Int32(a), ArrayImpl::Int32(b)) => ArrayImpl::Int32(binary_op(
+                a,
+                b,
+                |a: &i32, b: &i32| a.$int_op(*b).ok_or(ConvertError::Overflow),
+            )?),
+            (ArrayImpl::Int64(a), ArrayImpl::Int64(b)) => ArrayImpl::Int64(binary_op(
+                a,
+                b,
+                |a: &i64, b: &i64| a.$int_op(*b).ok_or(ConvertError::Overflow),
+            )?),
+            (ArrayImpl::Float64(a), ArrayImpl::Float64(b)) => {
+                ArrayImpl::Float64(binary_op(a, b, |a, b| Ok(a $float_op b))?)
+            }
+            (ArrayImpl::Decimal(a), ArrayImpl::Decimal(b)) => ArrayImpl::Decimal(binary_op(
+                a,
+                b,
+                |a: &Decimal, b: &Decimal| a.$int_op(*b).ok_or(ConvertError::Overflow),
+            )?),
+            _ => panic!("type mismatch in arithmetic"),
+        }
+    };
+}
+
+impl ArrayImpl {
+    /// Evaluate a binary operator. Both arrays must have the same type, except in the
+    /// arithmetic of dates, timestamps and intervals.
+    pub fn binary_op(&self, op: &BinaryOperator, other: &ArrayImpl) -> Result<Self, ConvertError> {
+        use BinaryOperator::*;
+        if let Some(result) = self.temporal_arith(op, other) {
+            return result;
+        }
+        if let Some(result) = self.dictionary_op(op, other) {
+            return result;
+        }
+        Ok(match op {
+            Plus => arith!(self, other, checked_add, +),
+            Minus => arith!(self, other, checked_sub, -),
+            Multiply => arith!(self, other, checked_mul, *),
+            Divide | Modulo => {
+                if other.contains_zero() {
+                    return Err(ConvertError::DivisionByZero);
+                }
+                match op {
+                    Divide => arith!(self, other, checked_div, /),
+                    _ => arith!(self, other, checked_rem, %),
+                }
+            }
+            Eq => cmp!(self, other, ==),
+            NotEq => cmp!(self, other, !=),
+            Gt => cmp!(self, other, >),
+            GtEq => cmp!(self, other, >=),
+            Lt => cmp!(self, other, <),
+            LtEq => cmp!(self, other, <=),
+            And | Or => {
+                let (a, b) = match (self, other) {
+                    (ArrayImpl::Bool(a), ArrayImpl::Bool(b)) => (a, b),
+                    _ => panic!("type mismatch in logical operator"),
+                };
+                // three-valued logic: `false AND NULL` is false, `true OR NULL` is true
+                let short_circuit = matches!(op, Or);
+                ArrayImpl::Bool(
+                    a.iter()
+                        .zip(b.iter())
+                        .map(|(a, b)| match (a, b) {
+                            (Some(&a), _) if a == short_circuit => Some(a),
+                            (_, Some(&b)) if b == short_circuit => Some(b),
+                            (Some(_), Some(_)) => Some(!short_circuit),
+                            _ => None,
+                        })
+                        .collect(),
+                )
+            }
+            _ => panic!("unsupported binary operator: {}", op),
+        })
+    }
+
+    /// Evaluate a unary operator.
+    pub fn unary_op(&self, op: &UnaryOperator) -> Result<Self, ConvertError> {
+        Ok(match (op, self) {
+            (UnaryOperator::Plus, a) => a.clone(),
+            (UnaryOperator::Minus, ArrayImpl::Int16(a)) => ArrayImpl::Int16(unary_op(a, |a| {
+                a.checked_neg().ok_or(ConvertError::Overflow)
+            })?),
+            (UnaryOperator::Minus, ArrayImpl::Int32(a)) => ArrayImpl::Int32(unary_op(a, |a| {
+                a.checked_neg().ok_or(ConvertError::Overflow)
+            })?),
+            (UnaryOperator::Minus, ArrayImpl::Int64(a)) => ArrayImpl::Int64(unary_op(a, |a| {
+                a.checked_neg().ok_or(ConvertError::Overflow)
+            })?),
+            (UnaryOperator::Minus, ArrayImpl::Float64(a)) => {
+                ArrayImpl::Float64(unary_op(a, |a| Ok(-a))?)
+            }
+            (UnaryOperator::Minus, ArrayImpl::Decimal(a)) => {
+                ArrayImpl::Decimal(unary_op(a, |a| Ok(-a))?)
+            }
+            (UnaryOperator::Minus, ArrayImpl::Interval(a)) => {
+                ArrayImpl::Interval(unary_op(a, |a| {
+                    a.checked_neg().ok_or(ConvertError::Overflow)
+                })?)
+            }
+            (UnaryOperator::Not, ArrayImpl::Bool(a)) => ArrayImpl::Bool(unary_op(a, |a| Ok(!a))?),
+            _ => panic!("unsupported unary operator: {}", op),
+        })
+    }
+
+    /// Cast the array to the given type.
+    pub fn cast(&self, ty: &DataType) -> Result<Self, ConvertError> {
+        let mut builder = ArrayBuilderImpl::with_capacity(self.len(), ty);
+        let kind = ty.kind();
+        for i in 0..self.len() {
+            builder.push(&self.get(i).cast(&kind)?);
+        }
+        Ok(builder.finish())
+    }
+
+    /// Keep the rows whose visibility is `true`.
+    pub fn filter(&self, visibility: &[bool]) -> Self {
+        match self {
+            Self::Bool(a) => Self::Bool(filter(a, visibility)),
+            Self::Int16(a) => Self::Int16(filter(a, visibility)),
+            Self::Int32(a) => Self::Int32(filter(a, visibility)),
+            Self::Int64(a) => Self::Int64(filter(a, visibility)),
+            Self::Float64(a) => Self::Float64(filter(a, visibility)),
+            Self::Decimal(a) => Self::Decimal(filter(a, visibility)),
+            Self::Utf8(a) => Self::Utf8(filter(a, visibility)),
+            Self::Bytes(a) => Self::Bytes(filter(a, visibility)),
+            Self::Dictionary(a) => Self::Dictionary(a.filter(visibility)),
+            Self::Date(a) => Self::Date(filter(a, visibility)),
+            Self::Timestamp(a) => Self::Timestamp(filter(a, visibility)),
+            Self::Interval(a) => Self::Interval(filter(a, visibility)),
+        }
+    }
+
+    /// The inverse of `filter`: place the rows at the positions whose visibility is `true`,
+    /// with NULLs at the other positions.
+    pub fn expand(&self, visibility: &[bool]) -> Self {
+        let mut builder = ArrayBuilderImpl::from_type_of_array(self);
+        let mut rows = 0..self.len();
+        for visible in visibility {
+            match visible {
+                true => builder.push(&self.get(rows.next().expect("too few rows to expand"))),
+                false => builder.push(&DataValue::Null),
+            }
+        }
+        builder.finish()
+    }
+
+    /// Evaluate an arithmetic operator on dates, timestamps or intervals, or returns `None`
+    /// for other operators and types. The operand types are given by the binder.
+    fn temporal_arith(
+        &self,
+        op: &BinaryOperator,
+        other: &ArrayImpl,
+    ) -> Option<Result<Self, ConvertError>> {
+        use BinaryOperator::*;
+        let overflow = || ConvertError::Overflow;
+        let result = match (self, op, other) {
+            (Self::Date(a), Plus, Self::Int32(b)) => {
+                binary_op(a, b, |a, b| a.checked_add_days(*b).ok_or_else(overflow)).map(Self::Date)
+            }
+            (Self::Int32(a), Plus, Self::Date(b)) => {
+                binary_op(a, b, |a, b| b.checked_add_days(*a).ok_or_else(overflow)).map(Self::Date)
+            }
+            (Self::Date(a), Minus, Self::Int32(b)) => {
+                binary_op(a, b, |a, b| a.checked_sub_days(*b).ok_or_else(overflow)).map(Self::Date)
+            }
+            (Self::Date(a), Minus, Self::Date(b)) => {
+                binary_op(a, b, |a: &Date, b| Ok(a.days_since(*b))).map(Self::Int32)
+            }
+            (Self::Timestamp(a), Plus, Self::Interval(b)) => {
+                binary_op(a, b, |a, b| a.checked_add_interval(*b).ok_or_else(overflow))
+                    .map(Self::Timestamp)
+            }
+            (Self::Interval(a), Plus, Self::Timestamp(b)) => {
+                binary_op(a, b, |a, b| b.checked_add_interval(*a).ok_or_else(overflow))
+                    .map(Self::Timestamp)
+            }
+            (Self::Timestamp(a), Minus, Self::Interval(b)) => {
+                binary_op(a, b, |a, b| a.checked_sub_interval(*b).ok_or_else(overflow))
+                    .map(Self::Timestamp)
+            }
+            (Self::Timestamp(a), Minus, Self::Timestamp(b)) => {
+                binary_op(a, b, |a, b| a.checked_sub(*b).ok_or_else(overflow)).map(Self::Interval)
+            }
+            (Self::Interval(a), Plus, Self::Interval(b)) => {
+                binary_op(a, b, |a, b| a.checked_add(*b).ok_or_else(overflow)).map(Self::Interval)
+            }
+            (Self::Interval(a), Minus, Self::Interval(b)) => {
+                binary_op(a, b, |a, b| a.checked_sub(*b).ok_or_else(overflow)).map(Self::Interval)
+            }
+            (Self::Interval(a), Multiply, Self::Float64(b)) => {
+                binary_op(a, b, |a, b| a.checked_mul(*b).ok_or_else(overflow)).map(Self::Interval)
+            }
+            (Self::Float64(a), Multiply, Self::Interval(b)) => {
+                binary_op(a, b, |a, b| b.checked_mul(*a).ok_or_else(overflow)).map(Self::Interval)
+            }
+            (Self::Interval(a), Divide, Self::Float64(b)) => binary_op(a, b, |a, b| match b {
+                b if *b == 0.0 => Err(ConvertError::DivisionByZero),
+                b => a.checked_div(*b).ok_or_else(overflow),
+            })
+            .map(Self::Interval),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    /// Evaluate a binary operator if either operand is dictionary-encoded, or returns `None`
+    /// otherwise. Strings are compared by their codes if possible, or decoded otherwise.
+    fn dictionary_op(
+        &self,
+        op: &BinaryOperator,
+        other: &ArrayImpl,
+    ) -> Option<Result<Self, ConvertError>> {
+        use BinaryOperator::*;
+        match (self, other) {
+            (Self::Dictionary(a), Self::Dictionary(b)) => {
+                // the strings of a dictionary are distinct, so equal strings have equal codes
+                if Arc::ptr_eq(a.dictionary(), b.dictionary()) && matches!(op, Eq | NotEq) {
+                    let eq = matches!(op, Eq);
+                    let result = binary_op(a.codes(), b.codes(), |a, b| Ok((a == b) == eq));
+                    return Some(result.map(Self::Bool));
+                }
+                let result = match op {
+                    Eq => a.zip_map(b, |a, b| a == b),
+                    NotEq => a.zip_map(b, |a, b| a != b),
+                    Gt => a.zip_map(b, |a, b| a > b),
+                    GtEq => a.zip_map(b, |a, b| a >= b),
+                    Lt => a.zip_map(b, |a, b| a < b),
+                    LtEq => a.zip_map(b, |a, b| a <= b),
+                    _ => None,
+                };
+                match result {
+                    Some(result) => Some(Ok(Self::Bool(result))),
+                    None => Some(Self::Utf8(a.decode()).binary_op(op, &Self::Utf8(b.decode()))),
+                }
+            }
+            (Self::Dictionary(a), b) => Some(Self::Utf8(a.decode()).binary_op(op, b)),
+            (a, Self::Dictionary(b)) => Some(a.binary_op(op, &Self::Utf8(b.decode()))),
+            _ => None,
+        }
+    }
+
+    fn contains_zero(&self) -> bool {
+        match self {
+            Self::Int16(a) => a.iter().any(|v| v == Some(&0)),
+            Self::Int32(a) => a.iter().any(|v| v == Some(&0)),
+            Self::Int64(a) => a.iter().any(|v| v == Some(&0)),
+            Self::Float64(a) => a.iter().any(|v| v == Some(&0.0)),
+            Self::Decimal(a) => a.iter().any(|v| v.map_or(false, |v| v.is_zero())),
+            _ => false,
+        }
+    }
+}
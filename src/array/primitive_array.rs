@@ -1,9 +1,11 @@
 use std::fmt::Debug;
 use std::iter::FromIterator;
 
+use bitvec::slice::BitSlice;
 use bitvec::vec::BitVec;
 
 use super::{Array, ArrayBuilder};
+use crate::types::{Date, Decimal, Interval, Timestamp};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct PrimitiveArray<T: Primitive> {
@@ -21,7 +23,10 @@ macro_rules! impl_primitive {
         $(impl Primitive for $t {})*
     };
 }
-impl_primitive!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64, bool);
+impl_primitive!(
+    u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64, bool, Decimal, Date, Timestamp,
+    Interval
+);
 
 impl<T: Primitive> FromIterator<Option<T>> for PrimitiveArray<T> {
     fn from_iter<I: IntoIterator<Item = Option<T>>>(iter: I) -> Self {
@@ -40,6 +45,18 @@ impl<T: Primitive> FromIterator<T> for PrimitiveArray<T> {
     }
 }
 
+impl<T: Primitive> PrimitiveArray<T> {
+    /// Returns the values of all rows, where the values of NULLs are the default value.
+    pub fn values(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Returns whether each row is not NULL.
+    pub fn validity(&self) -> &BitSlice {
+        &self.valid
+    }
+}
+
 impl<T: Primitive> Array for PrimitiveArray<T> {
     type Item = T;
     type Builder = PrimitiveArrayBuilder<T>;
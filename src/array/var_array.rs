@@ -0,0 +1,204 @@
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+
+use bitvec::slice::BitSlice;
+use bitvec::vec::BitVec;
+
+use super::{Array, ArrayBuilder};
+
+/// A variable-length value that is stored as raw bytes.
+pub trait VarItem: ToOwned + Send + Sync + 'static {
+    fn as_bytes(&self) -> &[u8];
+
+    /// Returns `None` if `bytes` is not a valid value, e.g. not UTF-8 for strings.
+    fn from_bytes(bytes: &[u8]) -> Option<&Self>;
+
+    /// # Safety
+    ///
+    /// `bytes` must have been produced by [`VarItem::as_bytes`] of the same type.
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self;
+}
+
+impl VarItem for str {
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<&Self> {
+        std::str::from_utf8(bytes).ok()
+    }
+
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        std::str::from_utf8_unchecked(bytes)
+    }
+}
+
+impl VarItem for [u8] {
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<&Self> {
+        Some(bytes)
+    }
+
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        bytes
+    }
+}
+
+/// An array of variable-length values, i.e. strings or byte strings.
+pub struct VarArray<T: VarItem + ?Sized> {
+    offset: Vec<usize>,
+    valid: BitVec,
+    data: Vec<u8>,
+    _type: PhantomData<Box<T>>,
+}
+
+pub type Utf8Array = VarArray<str>;
+pub type BytesArray = VarArray<[u8]>;
+
+// `derive` would require `T: Clone + PartialEq`, which unsized items can't satisfy.
+impl<T: VarItem + ?Sized> Clone for VarArray<T> {
+    fn clone(&self) -> Self {
+        Self {
+            offset: self.offset.clone(),
+            valid: self.valid.clone(),
+            data: self.data.clone(),
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T: VarItem + ?Sized> PartialEq for VarArray<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset && self.valid == other.valid && self.data == other.data
+    }
+}
+
+impl<T: VarItem + ?Sized> VarArray<T> {
+    /// Returns the offsets of the values in `data`, where the value of row `i` is in
+    /// `data[offsets[i]..offsets[i + 1]]`.
+    pub fn offsets(&self) -> &[usize] {
+        &self.offset
+    }
+
+    /// Returns the bytes of all values.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns whether each row is not NULL.
+    pub fn validity(&self) -> &BitSlice {
+        &self.valid
+    }
+}
+
+impl<T: VarItem + ?Sized> Array for VarArray<T> {
+    type Item = T;
+    type Builder = VarArrayBuilder<T>;
+
+    fn get(&self, idx: usize) -> Option<&T> {
+        if self.valid[idx] {
+            let data_slice = &self.data[self.offset[idx]..self.offset[idx + 1]];
+            Some(unsafe { T::from_bytes_unchecked(data_slice) })
+        } else {
+            None
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.valid.len()
+    }
+}
+
+pub struct VarArrayBuilder<T: VarItem + ?Sized> {
+    offset: Vec<usize>,
+    valid: BitVec,
+    data: Vec<u8>,
+    _type: PhantomData<Box<T>>,
+}
+
+pub type Utf8ArrayBuilder = VarArrayBuilder<str>;
+pub type BytesArrayBuilder = VarArrayBuilder<[u8]>;
+
+impl<T: VarItem + ?Sized> ArrayBuilder for VarArrayBuilder<T> {
+    type Array = VarArray<T>;
+
+    fn with_capacity(capacity: usize) -> Self {
+        let mut offset = Vec::with_capacity(capacity + 1);
+        offset.push(0);
+        Self {
+            offset,
+            data: Vec::with_capacity(capacity),
+            valid: BitVec::with_capacity(capacity),
+            _type: PhantomData,
+        }
+    }
+
+    fn push(&mut self, value: Option<&T>) {
+        self.valid.push(value.is_some());
+        if let Some(x) = value {
+            self.data.extend_from_slice(x.as_bytes());
+        }
+        self.offset.push(self.data.len());
+    }
+
+    fn append(&mut self, other: &VarArray<T>) {
+        self.valid.extend_from_bitslice(&other.valid);
+        self.data.extend_from_slice(&other.data);
+        let start = *self.offset.last().unwrap();
+        for other_offset in &other.offset[1..] {
+            self.offset.push(*other_offset + start);
+        }
+    }
+
+    fn finish(self) -> VarArray<T> {
+        VarArray {
+            valid: self.valid,
+            data: self.data,
+            offset: self.offset,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T: VarItem + ?Sized, S: AsRef<T>> FromIterator<Option<S>> for VarArray<T> {
+    fn from_iter<I: IntoIterator<Item = Option<S>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut builder = <Self as Array>::Builder::with_capacity(iter.size_hint().0);
+        for e in iter {
+            if let Some(s) = e {
+                builder.push(Some(s.as_ref()));
+            } else {
+                builder.push(None);
+            }
+        }
+        builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect() {
+        let iter = [None, Some("1"), None, Some("3")].into_iter();
+        let array = iter.clone().collect::<Utf8Array>();
+        assert_eq!(array.iter().collect::<Vec<_>>(), iter.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_collect_bytes() {
+        let iter = [Some(vec![0u8, 0xff]), None, Some(vec![])].into_iter();
+        let array = iter.clone().collect::<BytesArray>();
+        assert_eq!(
+            array
+                .iter()
+                .map(|v| v.map(<[u8]>::to_vec))
+                .collect::<Vec<_>>(),
+            iter.collect::<Vec<_>>()
+        );
+    }
+}
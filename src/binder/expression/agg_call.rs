@@ -0,0 +1,183 @@
+use super::*;
+use crate::function::AggregateFunction;
+use crate::parser::Function;
+use crate::types::{DataTypeExt, DataTypeKindExt, ExactNumberInfo};
+
+/// An aggregate function call without `OVER`.
+#[derive(PartialEq, Clone)]
+pub struct BoundAggCall {
+    pub kind: AggKind,
+    /// Whether duplicate values of the argument are aggregated only once.
+    pub distinct: bool,
+    /// The arguments, or nothing for `COUNT(*)`.
+    pub args: Vec<BoundExpr>,
+    pub return_type: DataType,
+}
+
+impl std::fmt::Debug for BoundAggCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let distinct = if self.distinct { "distinct " } else { "" };
+        write!(f, "{:?}({}{:?})", self.kind, distinct, self.args)
+    }
+}
+
+/// An aggregate function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AggKind {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+    /// A user-defined aggregate function.
+    User(AggregateFunction),
+}
+
+impl AggKind {
+    /// Returns the built-in aggregate function called `name`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "count" => Some(Self::Count),
+            "sum" => Some(Self::Sum),
+            "avg" => Some(Self::Avg),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            _ => None,
+        }
+    }
+
+    /// Returns the type of the result, or `None` if the function can not take an argument of
+    /// type `arg`. The argument of `COUNT(*)` has no type.
+    pub fn return_type(&self, arg: Option<&DataTypeKind>) -> Option<DataType> {
+        match (self, arg) {
+            (Self::User(f), _) => Some(f.return_type.clone().nullable()),
+            (Self::Count, _) => Some(DataTypeKind::BigInt(None).not_null()),
+            (_, None) => None,
+            (Self::Sum, Some(kind)) if kind.is_integer() => {
+                Some(DataTypeKind::BigInt(None).nullable())
+            }
+            (Self::Sum | Self::Avg, Some(kind)) if kind.is_decimal() => {
+                Some(DataTypeKind::Decimal(ExactNumberInfo::None).nullable())
+            }
+            (Self::Sum, Some(kind)) if kind.is_float() => Some(DataTypeKind::Double.nullable()),
+            (Self::Avg, Some(kind)) if kind.is_number() => Some(DataTypeKind::Double.nullable()),
+            (Self::Min | Self::Max, Some(kind)) => Some(kind.clone().nullable()),
+            _ => None,
+        }
+    }
+}
+
+impl Binder {
+    pub fn bind_agg_call(&mut self, func: &Function) -> Result<BoundExpr, BindError> {
+        let name = func.name.to_string().to_lowercase();
+        let mut args = self.bind_function_args(&name, &func.args)?;
+        let invalid_args = || BindError::InvalidFunctionArguments(name.clone());
+        if func.distinct && args.is_empty() {
+            return Err(invalid_args());
+        }
+        if args.iter().any(|arg| arg.contains_agg()) {
+            return Err(BindError::AggregateNotAllowed(
+                "aggregate function arguments",
+            ));
+        }
+        if args.iter().any(|arg| arg.contains_window()) {
+            return Err(BindError::WindowFunctionNotAllowed(
+                "aggregate function arguments",
+            ));
+        }
+        let kind = match AggKind::from_name(&name) {
+            Some(kind) => kind,
+            None => {
+                let mut call = (self.bind_user_agg(&name, args)?)
+                    .ok_or_else(|| BindError::FunctionNotFound(name.clone()))?;
+                call.distinct = func.distinct;
+                return Ok(BoundExpr::AggCall(call));
+            }
+        };
+        // `COUNT(*)` is the only call without arguments.
+        let expected_args = if kind == AggKind::Count { 0..=1 } else { 1..=1 };
+        if func.args.is_empty() || !expected_args.contains(&args.len()) {
+            return Err(invalid_args());
+        }
+        let arg_kind = args.first().map(|arg| {
+            arg.return_type()
+                .map_or(DataTypeKind::Int(None), |ty| ty.kind())
+        });
+        let return_type = kind
+            .return_type(arg_kind.as_ref())
+            .ok_or_else(invalid_args)?;
+        if let Some(arg) = args.first_mut() {
+            *arg = std::mem::replace(arg, BoundExpr::Constant(DataValue::Null))
+                .cast_if_null(arg_kind.unwrap());
+        }
+        Ok(BoundExpr::AggCall(BoundAggCall {
+            kind,
+            distinct: func.distinct,
+            args,
+            return_type,
+        }))
+    }
+
+    /// Bind a call of the user-defined aggregate function `name`, casting the arguments to the
+    /// types of the best matching overload. Returns `None` if there is no such function.
+    ///
+    /// Built-in aggregate functions take precedence over user-defined ones of the same name.
+    pub fn bind_user_agg(
+        &mut self,
+        name: &str,
+        args: Vec<BoundExpr>,
+    ) -> Result<Option<BoundAggCall>, BindError> {
+        let functions = self.catalog.functions();
+        if !functions.contains_aggregate(name) {
+            return Ok(None);
+        }
+        let arg_types = args
+            .iter()
+            .map(|arg| arg.return_type().map(|ty| ty.kind()))
+            .collect::<Vec<_>>();
+        let func = functions
+            .resolve_aggregate(name, &arg_types)
+            .ok_or_else(|| BindError::InvalidFunctionArguments(name.into()))?;
+        let args = (args.into_iter().zip(&func.arg_types))
+            .map(|(arg, ty)| arg.cast_to(ty))
+            .collect();
+        Ok(Some(BoundAggCall {
+            return_type: func.return_type.clone().nullable(),
+            kind: AggKind::User(func),
+            distinct: false,
+            args,
+        }))
+    }
+
+    /// Bind the arguments of a function call.
+    ///
+    /// A `*` argument is only allowed as the only argument of `COUNT(*)` and is bound to no
+    /// arguments.
+    pub fn bind_function_args(
+        &mut self,
+        name: &str,
+        args: &[FunctionArg],
+    ) -> Result<Vec<BoundExpr>, BindError> {
+        let mut exprs = vec![];
+        for arg in args {
+            match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => {
+                    exprs.push(self.bind_expr(expr)?)
+                }
+                FunctionArg::Unnamed(FunctionArgExpr::Wildcard)
+                    if name == "count" && args.len() == 1 => {}
+                _ => return Err(BindError::InvalidFunctionArguments(name.into())),
+            }
+        }
+        Ok(exprs)
+    }
+}
+
+impl BoundExpr {
+    /// Returns true if the expression contains an aggregate function call.
+    pub fn contains_agg(&self) -> bool {
+        let mut found = false;
+        self.visit(&mut |e| found |= matches!(e, BoundExpr::AggCall(_)));
+        found
+    }
+}
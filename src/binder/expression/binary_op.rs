@@ -0,0 +1,239 @@
+use super::*;
+use crate::types::{DataTypeExt, DataTypeKindExt, ExactNumberInfo, TimezoneInfo};
+
+#[derive(PartialEq, Clone)]
+pub struct BoundBinaryOp {
+    pub op: BinaryOperator,
+    pub left_expr: Box<BoundExpr>,
+    pub right_expr: Box<BoundExpr>,
+    pub return_type: Option<DataType>,
+}
+
+impl std::fmt::Debug for BoundBinaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({:?} {} {:?})",
+            self.left_expr, self.op, self.right_expr
+        )
+    }
+}
+
+impl Binder {
+    pub fn bind_binary_op(
+        &mut self,
+        left: &Expr,
+        op: &BinaryOperator,
+        right: &Expr,
+    ) -> Result<BoundExpr, BindError> {
+        let left = self.bind_expr(left)?;
+        let right = self.bind_expr(right)?;
+        build_binary_op(left, op.clone(), right)
+    }
+}
+
+/// Build a binary operation, casting the operands to a common type if necessary.
+pub fn build_binary_op(
+    left: BoundExpr,
+    op: BinaryOperator,
+    right: BoundExpr,
+) -> Result<BoundExpr, BindError> {
+    use BinaryOperator::*;
+
+    let nullable = [&left, &right]
+        .iter()
+        .any(|e| e.return_type().map_or(true, |ty| ty.is_nullable()));
+    let (left, right, return_kind) = match op {
+        And | Or => {
+            for e in [&left, &right] {
+                match e.return_type().map(|ty| ty.kind()) {
+                    None | Some(DataTypeKind::Boolean) => {}
+                    Some(kind) => return Err(BindError::NotBoolean("AND/OR", kind)),
+                }
+            }
+            let left = left.cast_if_null(DataTypeKind::Boolean);
+            let right = right.cast_if_null(DataTypeKind::Boolean);
+            (left, right, DataTypeKind::Boolean)
+        }
+        Plus | Minus | Multiply | Divide | Modulo
+            if [&left, &right]
+                .iter()
+                .any(|e| (e.return_type()).map_or(false, |ty| ty.kind().is_temporal())) =>
+        {
+            let (l, r) = (
+                left.return_type().map(|ty| ty.kind()),
+                right.return_type().map(|ty| ty.kind()),
+            );
+            let kinds = temporal_arith_types(l.as_ref(), &op, r.as_ref());
+            let Some([l_kind, r_kind, return_kind]) = kinds else {
+                let null = || DataTypeKind::Int(None);
+                let (l, r) = (l.unwrap_or_else(null), r.unwrap_or_else(null));
+                return Err(BindError::BinaryOpTypeMismatch(l, op, r));
+            };
+            (left.cast_to(&l_kind), right.cast_to(&r_kind), return_kind)
+        }
+        Plus | Minus | Multiply | Divide | Modulo | Eq | NotEq | Gt | GtEq | Lt | LtEq => {
+            let kind = match (left.return_type(), right.return_type()) {
+                (Some(l), Some(r)) => unify_types(&l.kind(), &r.kind()).ok_or_else(|| {
+                    BindError::BinaryOpTypeMismatch(l.kind(), op.clone(), r.kind())
+                })?,
+                (Some(ty), None) | (None, Some(ty)) => ty.kind(),
+                (None, None) => DataTypeKind::Int(None),
+            };
+            let is_arithmetic = matches!(op, Plus | Minus | Multiply | Divide | Modulo);
+            if is_arithmetic && !kind.is_number() {
+                let kind_of = |e: &BoundExpr| e.return_type().map_or(kind.clone(), |ty| ty.kind());
+                return Err(BindError::BinaryOpTypeMismatch(
+                    kind_of(&left),
+                    op,
+                    kind_of(&right),
+                ));
+            }
+            let left = left.cast_to(&kind);
+            let right = right.cast_to(&kind);
+            (
+                left,
+                right,
+                if is_arithmetic {
+                    kind
+                } else {
+                    DataTypeKind::Boolean
+                },
+            )
+        }
+        _ => return Err(BindError::NotSupported(format!("binary operator {}", op))),
+    };
+    Ok(BoundExpr::BinaryOp(BoundBinaryOp {
+        op,
+        left_expr: left.into(),
+        right_expr: right.into(),
+        return_type: Some(DataType::new(return_kind, nullable)),
+    }))
+}
+
+/// Returns the type that values of both types can be compared or computed in.
+pub fn unify_types(left: &DataTypeKind, right: &DataTypeKind) -> Option<DataTypeKind> {
+    if left.is_integer() && right.is_integer() {
+        // the wider of the two integer types
+        Some(std::cmp::max_by_key(left, right, |k| k.integer_bits()).canonical())
+    } else if left.is_number() && right.is_number() {
+        // exact numbers are computed in decimal unless mixed with floats
+        if left.is_float() || right.is_float() {
+            Some(DataTypeKind::Double)
+        } else {
+            Some(DataTypeKind::Decimal(ExactNumberInfo::None))
+        }
+    } else if left.is_string() && right.is_string() {
+        Some(DataTypeKind::Varchar(None))
+    } else if left == &DataTypeKind::Boolean && right == &DataTypeKind::Boolean {
+        Some(DataTypeKind::Boolean)
+    } else if left.is_binary() || right.is_binary() {
+        // strings are parsed as byte strings
+        let binary = |kind: &DataTypeKind| kind.is_binary() || kind.is_string();
+        (binary(left) && binary(right)).then_some(DataTypeKind::Bytea)
+    } else if left.is_temporal() || right.is_temporal() {
+        match (left.canonical(), right.canonical()) {
+            (l, r) if l == r => Some(l),
+            (DataTypeKind::Date, ts) | (ts, DataTypeKind::Date) if ts.is_timestamp() => Some(ts),
+            // strings are parsed as dates, timestamps or intervals
+            (s, t) | (t, s) if s.is_string() => Some(t),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Returns the types that the operands are casted to and the type of the result of an
+/// arithmetic operation on dates, timestamps or intervals, or `None` if it is not allowed.
+/// Untyped `NULL`s are `None`.
+fn temporal_arith_types(
+    left: Option<&DataTypeKind>,
+    op: &BinaryOperator,
+    right: Option<&DataTypeKind>,
+) -> Option<[DataTypeKind; 3]> {
+    use BinaryOperator::*;
+    const INT: DataTypeKind = DataTypeKind::Int(None);
+    const DOUBLE: DataTypeKind = DataTypeKind::Double;
+    const DATE: DataTypeKind = DataTypeKind::Date;
+    const TIMESTAMP: DataTypeKind = DataTypeKind::Timestamp(None, TimezoneInfo::None);
+    const INTERVAL: DataTypeKind = DataTypeKind::Interval;
+
+    // integers are computed as `INT` and other numbers as `DOUBLE`
+    let class = |kind: Option<&DataTypeKind>| {
+        kind.map(|kind| match kind {
+            kind if kind.is_integer() => INT,
+            kind if kind.is_number() => DOUBLE,
+            kind => kind.canonical(),
+        })
+    };
+    Some(match (class(left), op, class(right)) {
+        (Some(DATE), Plus | Minus, Some(INT) | None) => [DATE, INT, DATE],
+        (Some(INT) | None, Plus, Some(DATE)) => [INT, DATE, DATE],
+        (Some(DATE), Minus, Some(DATE)) => [DATE, DATE, INT],
+        (Some(DATE | TIMESTAMP), Plus | Minus, Some(INTERVAL)) => [TIMESTAMP, INTERVAL, TIMESTAMP],
+        (Some(TIMESTAMP), Plus | Minus, None) => [TIMESTAMP, INTERVAL, TIMESTAMP],
+        (Some(INTERVAL), Plus, Some(DATE | TIMESTAMP)) => [INTERVAL, TIMESTAMP, TIMESTAMP],
+        (None, Plus, Some(TIMESTAMP)) => [INTERVAL, TIMESTAMP, TIMESTAMP],
+        (Some(DATE | TIMESTAMP), Minus, Some(DATE | TIMESTAMP)) => [TIMESTAMP, TIMESTAMP, INTERVAL],
+        (Some(INTERVAL) | None, Plus | Minus, Some(INTERVAL) | None) => {
+            [INTERVAL, INTERVAL, INTERVAL]
+        }
+        (Some(INTERVAL), Multiply | Divide, Some(INT | DOUBLE) | None) => {
+            [INTERVAL, DOUBLE, INTERVAL]
+        }
+        (Some(INT | DOUBLE) | None, Multiply, Some(INTERVAL)) => [DOUBLE, INTERVAL, INTERVAL],
+        _ => return None,
+    })
+}
+
+/// Returns the type that the results of all expressions can be converted to, e.g. the branches
+/// of a `CASE`. Untyped `NULL`s are ignored. `context` names the construct in the error.
+pub fn unify_expr_types<'a>(
+    context: &'static str,
+    exprs: impl IntoIterator<Item = &'a BoundExpr>,
+) -> Result<DataTypeKind, BindError> {
+    let mut kind: Option<DataTypeKind> = None;
+    for ty in exprs.into_iter().filter_map(|e| e.return_type()) {
+        kind = Some(match kind {
+            Some(kind) => unify_types(&kind, &ty.kind())
+                .ok_or_else(|| BindError::TypeMismatch(context, kind, ty.kind()))?,
+            None => ty.kind(),
+        });
+    }
+    Ok(kind.unwrap_or(DataTypeKind::Int(None)))
+}
+
+impl BoundExpr {
+    /// Make a conjunction of the expressions. Returns `true` if the list is empty.
+    pub fn and_all(exprs: impl IntoIterator<Item = BoundExpr>) -> BoundExpr {
+        exprs
+            .into_iter()
+            .reduce(|l, r| {
+                BoundExpr::BinaryOp(BoundBinaryOp {
+                    op: BinaryOperator::And,
+                    return_type: Some(DataTypeKind::Boolean.nullable()),
+                    left_expr: l.into(),
+                    right_expr: r.into(),
+                })
+            })
+            .unwrap_or(BoundExpr::Constant(DataValue::Bool(true)))
+    }
+
+    /// Split a conjunction into its operands.
+    pub fn into_conjunctions(self) -> Vec<BoundExpr> {
+        match self {
+            BoundExpr::BinaryOp(BoundBinaryOp {
+                op: BinaryOperator::And,
+                left_expr,
+                right_expr,
+                ..
+            }) => {
+                let mut exprs = left_expr.into_conjunctions();
+                exprs.extend(right_expr.into_conjunctions());
+                exprs
+            }
+            expr => vec![expr],
+        }
+    }
+}
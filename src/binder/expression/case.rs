@@ -0,0 +1,62 @@
+use super::*;
+
+/// A searched `CASE` expression. A simple `CASE` is bound by comparing the operand with each
+/// `WHEN` value.
+#[derive(PartialEq, Clone)]
+pub struct BoundCase {
+    pub conditions: Vec<BoundExpr>,
+    pub results: Vec<BoundExpr>,
+    pub else_result: Option<Box<BoundExpr>>,
+    pub return_type: DataType,
+}
+
+impl std::fmt::Debug for BoundCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CASE")?;
+        for (cond, result) in self.conditions.iter().zip(&self.results) {
+            write!(f, " WHEN {:?} THEN {:?}", cond, result)?;
+        }
+        if let Some(else_result) = &self.else_result {
+            write!(f, " ELSE {:?}", else_result)?;
+        }
+        write!(f, " END")
+    }
+}
+
+impl Binder {
+    pub fn bind_case(
+        &mut self,
+        operand: Option<&Expr>,
+        conditions: &[Expr],
+        results: &[Expr],
+        else_result: Option<&Expr>,
+    ) -> Result<BoundExpr, BindError> {
+        let operand = operand.map(|e| self.bind_expr(e)).transpose()?;
+        let mut bound_conditions = vec![];
+        for cond in conditions {
+            let mut cond = self.bind_expr(cond)?;
+            if let Some(operand) = &operand {
+                cond = build_binary_op(operand.clone(), BinaryOperator::Eq, cond)?;
+            }
+            match cond.return_type().map(|ty| ty.kind()) {
+                None | Some(DataTypeKind::Boolean) => {}
+                Some(kind) => return Err(BindError::NotBoolean("CASE", kind)),
+            }
+            bound_conditions.push(cond.cast_if_null(DataTypeKind::Boolean));
+        }
+        let results = (results.iter())
+            .map(|e| self.bind_expr(e))
+            .collect::<Result<Vec<_>, _>>()?;
+        let else_result = else_result.map(|e| self.bind_expr(e)).transpose()?;
+
+        let kind = unify_expr_types("CASE", results.iter().chain(&else_result))?;
+        let nullable =
+            else_result.is_none() || results.iter().chain(&else_result).any(|e| e.is_nullable());
+        Ok(BoundExpr::Case(BoundCase {
+            conditions: bound_conditions,
+            results: results.into_iter().map(|e| e.cast_to(&kind)).collect(),
+            else_result: else_result.map(|e| e.cast_to(&kind).into()),
+            return_type: DataType::new(kind, nullable),
+        }))
+    }
+}
@@ -2,32 +2,47 @@ use super::*;
 
 #[derive(PartialEq, Clone)]
 pub struct BoundColumnRef {
-    pub column_ref_id: ColumnRefId,
+    /// The position of the column in the input row of the query block.
+    pub index: usize,
+    /// The number of query blocks to go outward to find the column.
+    /// Non-zero for references to outer queries in correlated subqueries.
+    pub depth: usize,
     pub return_type: DataType,
 }
 
 impl std::fmt::Debug for BoundColumnRef {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.column_ref_id)
+        match self.depth {
+            0 => write!(f, "#{}", self.index),
+            depth => write!(f, "#{}@outer{}", self.index, depth),
+        }
     }
 }
 
 impl Binder {
-    pub fn bind_all_column_refs(&mut self) -> Result<Vec<BoundExpr>, BindError> {
+    /// Bind all columns in the FROM clause, returning their names and references.
+    pub fn bind_all_column_refs(&mut self) -> Result<Vec<(String, BoundExpr)>, BindError> {
         let mut exprs = vec![];
-        for &table_ref_id in self.tables.values() {
-            let table = self.catalog.get_table(table_ref_id).unwrap();
-            for (col_id, col) in table.all_columns() {
-                let expr = BoundExpr::ColumnRef(BoundColumnRef {
-                    column_ref_id: ColumnRefId::from_table(table_ref_id, col_id),
-                    return_type: col.datatype(),
-                });
-                exprs.push(expr);
-            }
+        for relation in self.context().relations.iter() {
+            exprs.extend(relation.column_refs());
         }
         Ok(exprs)
     }
 
+    /// Bind all columns of the relation `name`, returning their names and references.
+    pub fn bind_qualified_column_refs(
+        &mut self,
+        name: &str,
+    ) -> Result<Vec<(String, BoundExpr)>, BindError> {
+        let relation = self
+            .context()
+            .relations
+            .iter()
+            .find(|r| r.name == name)
+            .ok_or_else(|| BindError::TableNotFound(name.into()))?;
+        Ok(relation.column_refs().collect())
+    }
+
     pub fn bind_column_ref(&mut self, idents: &[Ident]) -> Result<BoundExpr, BindError> {
         let (_schema_name, table_name, column_name) = match idents {
             [column] => (None, None, &column.value),
@@ -35,36 +50,72 @@ impl Binder {
             [schema, table, column] => (Some(&schema.value), Some(&table.value), &column.value),
             _ => return Err(BindError::InvalidTableName(idents.into())),
         };
-        if let Some(name) = table_name {
-            let table_ref_id = *self
-                .tables
-                .get(name)
-                .ok_or_else(|| BindError::TableNotFound(name.clone()))?;
-            let table = self.catalog.get_table(table_ref_id).unwrap();
-            let col = table
-                .get_column_by_name(column_name)
-                .ok_or_else(|| BindError::ColumnNotFound(column_name.clone()))?;
-            Ok(BoundExpr::ColumnRef(BoundColumnRef {
-                column_ref_id: ColumnRefId::from_table(table_ref_id, col.id()),
-                return_type: col.datatype(),
-            }))
-        } else {
+        // Search from the innermost query block to the outermost one.
+        for (depth, context) in self.contexts.iter().rev().enumerate() {
+            if let Some(name) = table_name {
+                let Some(relation) = context.relations.iter().find(|r| &r.name == name) else {
+                    continue;
+                };
+                let (idx, (_, ty)) = relation
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .find(|(_, (col, _))| col == column_name)
+                    .ok_or_else(|| BindError::ColumnNotFound(column_name.clone()))?;
+                return Ok(BoundExpr::ColumnRef(BoundColumnRef {
+                    index: relation.offset + idx,
+                    depth,
+                    return_type: ty.clone(),
+                }));
+            }
             let mut column_ref = None;
-            for &table_ref_id in self.tables.values() {
-                let table = self.catalog.get_table(table_ref_id).unwrap();
-                if let Some(col) = table.get_column_by_name(column_name) {
+            for relation in context.relations.iter() {
+                for (idx, (col, ty)) in relation.columns.iter().enumerate() {
+                    if col != column_name {
+                        continue;
+                    }
                     if column_ref.is_some() {
                         return Err(BindError::AmbiguousColumnName(column_name.into()));
                     }
                     column_ref = Some(BoundColumnRef {
-                        column_ref_id: ColumnRefId::from_table(table_ref_id, col.id()),
-                        return_type: col.datatype(),
+                        index: relation.offset + idx,
+                        depth,
+                        return_type: ty.clone(),
                     });
                 }
             }
-            Ok(BoundExpr::ColumnRef(column_ref.ok_or_else(|| {
-                BindError::ColumnNotFound(column_name.clone())
-            })?))
+            if let Some(column_ref) = column_ref {
+                return Ok(BoundExpr::ColumnRef(column_ref));
+            }
         }
+        match table_name {
+            Some(name) => Err(BindError::TableNotFound(name.clone())),
+            None => Err(BindError::ColumnNotFound(column_name.clone())),
+        }
+    }
+
+    /// Returns the qualified name of the column at `index` of the current query block.
+    pub fn column_name(&mut self, index: usize) -> String {
+        let relation = (self.context().relations.iter())
+            .find(|r| (r.offset..r.offset + r.columns.len()).contains(&index))
+            .expect("column index out of range");
+        format!(
+            "{}.{}",
+            relation.name,
+            relation.columns[index - relation.offset].0
+        )
+    }
+}
+
+impl Relation {
+    fn column_refs(&self) -> impl Iterator<Item = (String, BoundExpr)> + '_ {
+        self.columns.iter().enumerate().map(|(idx, (name, ty))| {
+            let expr = BoundExpr::ColumnRef(BoundColumnRef {
+                index: self.offset + idx,
+                depth: 0,
+                return_type: ty.clone(),
+            });
+            (name.clone(), expr)
+        })
     }
 }
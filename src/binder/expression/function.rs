@@ -0,0 +1,132 @@
+use super::*;
+use crate::function::ScalarFunction;
+use crate::parser::{DateTimeField, Function, TrimWhereField};
+use crate::types::DataTypeExt;
+
+/// A call of a scalar function.
+#[derive(PartialEq, Clone)]
+pub struct BoundFunctionCall {
+    pub func: ScalarFunction,
+    pub args: Vec<BoundExpr>,
+    pub return_type: DataType,
+}
+
+impl std::fmt::Debug for BoundFunctionCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({:?})", self.func.name, self.args)
+    }
+}
+
+impl Binder {
+    /// Bind a function call without `OVER`, which is either an aggregate function or a
+    /// scalar function.
+    pub fn bind_function(&mut self, func: &Function) -> Result<BoundExpr, BindError> {
+        let name = func.name.to_string().to_lowercase();
+        if let Some(value) = self.current_time(&name) {
+            if !func.args.is_empty() {
+                return Err(BindError::InvalidFunctionArguments(name));
+            }
+            return Ok(BoundExpr::Constant(value));
+        }
+        if AggKind::from_name(&name).is_some() || self.catalog.functions().contains_aggregate(&name)
+        {
+            return self.bind_agg_call(func);
+        }
+        if func.distinct {
+            return Err(BindError::InvalidFunctionArguments(name));
+        }
+        let args = self.bind_function_args(&name, &func.args)?;
+        self.bind_scalar_function(&name, args)
+    }
+
+    /// Returns the value of `NOW()`, `CURRENT_TIMESTAMP` or `CURRENT_DATE` in UTC, or `None`
+    /// if `name` is not one of them.
+    fn current_time(&self, name: &str) -> Option<DataValue> {
+        match name {
+            "now" | "current_timestamp" | "localtimestamp" => Some(DataValue::Timestamp(self.now)),
+            "current_date" => Some(DataValue::Date(self.now.date())),
+            _ => None,
+        }
+    }
+
+    /// Bind `CEIL`, `FLOOR`, `SUBSTRING`, `TRIM` and `EXTRACT`, which have special syntax.
+    pub fn bind_special_function(&mut self, expr: &Expr) -> Result<BoundExpr, BindError> {
+        let (name, args) = match expr {
+            Expr::Ceil { expr: arg, field } | Expr::Floor { expr: arg, field } => {
+                let name = match expr {
+                    Expr::Ceil { .. } => "ceil",
+                    _ => "floor",
+                };
+                if field != &DateTimeField::NoDateTime {
+                    return Err(BindError::NotSupported(format!("{} to {}", name, field)));
+                }
+                (name, vec![self.bind_expr(arg)?])
+            }
+            Expr::Substring {
+                expr,
+                substring_from,
+                substring_for,
+            } => {
+                let mut args = vec![self.bind_expr(expr)?];
+                args.push(match substring_from {
+                    Some(from) => self.bind_expr(from)?,
+                    None => BoundExpr::Constant(DataValue::Int32(1)),
+                });
+                if let Some(len) = substring_for {
+                    args.push(self.bind_expr(len)?);
+                }
+                ("substring", args)
+            }
+            Expr::Trim {
+                expr,
+                trim_where,
+                trim_what,
+            } => {
+                let name = match trim_where {
+                    None | Some(TrimWhereField::Both) => "btrim",
+                    Some(TrimWhereField::Leading) => "ltrim",
+                    Some(TrimWhereField::Trailing) => "rtrim",
+                };
+                let mut args = vec![self.bind_expr(expr)?];
+                if let Some(what) = trim_what {
+                    args.push(self.bind_expr(what)?);
+                }
+                (name, args)
+            }
+            Expr::Extract { field, expr } => {
+                let field = BoundExpr::Constant(DataValue::String(field.to_string()));
+                ("extract", vec![field, self.bind_expr(expr)?])
+            }
+            _ => unreachable!("not a special function: {:?}", expr),
+        };
+        self.bind_scalar_function(name, args)
+    }
+
+    /// Bind a call of the scalar function `name`, casting the arguments to the types of the
+    /// best matching overload.
+    pub fn bind_scalar_function(
+        &mut self,
+        name: &str,
+        args: Vec<BoundExpr>,
+    ) -> Result<BoundExpr, BindError> {
+        let functions = self.catalog.functions();
+        if !functions.contains_scalar(name) {
+            return Err(BindError::FunctionNotFound(name.into()));
+        }
+        let arg_types = args
+            .iter()
+            .map(|arg| arg.return_type().map(|ty| ty.kind()))
+            .collect::<Vec<_>>();
+        let func = functions
+            .resolve_scalar(name, &arg_types)
+            .ok_or_else(|| BindError::InvalidFunctionArguments(name.into()))?;
+        let args = (args.into_iter().enumerate())
+            .map(|(i, arg)| arg.cast_to(func.arg_type(i)))
+            .collect();
+        Ok(BoundExpr::FunctionCall(BoundFunctionCall {
+            return_type: func.return_type.clone().nullable(),
+            func,
+            args,
+        }))
+    }
+}
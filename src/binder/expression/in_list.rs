@@ -0,0 +1,81 @@
+use super::*;
+
+/// `expr [NOT] IN (list)`.
+#[derive(PartialEq, Clone)]
+pub struct BoundInList {
+    pub expr: Box<BoundExpr>,
+    pub list: Vec<BoundExpr>,
+    pub negated: bool,
+    pub return_type: DataType,
+}
+
+impl std::fmt::Debug for BoundInList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let not = if self.negated { "NOT " } else { "" };
+        write!(f, "({:?} {}IN {:?})", self.expr, not, self.list)
+    }
+}
+
+/// `expr [NOT] BETWEEN low AND high`.
+#[derive(PartialEq, Clone)]
+pub struct BoundBetween {
+    pub expr: Box<BoundExpr>,
+    pub low: Box<BoundExpr>,
+    pub high: Box<BoundExpr>,
+    pub negated: bool,
+    pub return_type: DataType,
+}
+
+impl std::fmt::Debug for BoundBetween {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let not = if self.negated { "NOT " } else { "" };
+        write!(
+            f,
+            "({:?} {}BETWEEN {:?} AND {:?})",
+            self.expr, not, self.low, self.high
+        )
+    }
+}
+
+impl Binder {
+    pub fn bind_in_list(
+        &mut self,
+        expr: &Expr,
+        list: &[Expr],
+        negated: bool,
+    ) -> Result<BoundExpr, BindError> {
+        let expr = self.bind_expr(expr)?;
+        let list = (list.iter())
+            .map(|e| self.bind_expr(e))
+            .collect::<Result<Vec<_>, _>>()?;
+        let kind = unify_expr_types("IN", std::iter::once(&expr).chain(&list))?;
+        let nullable = expr.is_nullable() || list.iter().any(|e| e.is_nullable());
+        Ok(BoundExpr::InList(BoundInList {
+            expr: expr.cast_to(&kind).into(),
+            list: list.into_iter().map(|e| e.cast_to(&kind)).collect(),
+            negated,
+            return_type: DataType::new(DataTypeKind::Boolean, nullable),
+        }))
+    }
+
+    pub fn bind_between(
+        &mut self,
+        expr: &Expr,
+        low: &Expr,
+        high: &Expr,
+        negated: bool,
+    ) -> Result<BoundExpr, BindError> {
+        let expr = self.bind_expr(expr)?;
+        let low = self.bind_expr(low)?;
+        let high = self.bind_expr(high)?;
+        let kind = unify_expr_types("BETWEEN", [&expr, &low, &high])?;
+        let nullable = [&expr, &low, &high].iter().any(|e| e.is_nullable());
+        Ok(BoundExpr::Between(BoundBetween {
+            expr: expr.cast_to(&kind).into(),
+            low: low.cast_to(&kind).into(),
+            high: high.cast_to(&kind).into(),
+            negated,
+            return_type: DataType::new(DataTypeKind::Boolean, nullable),
+        }))
+    }
+}
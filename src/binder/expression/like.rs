@@ -0,0 +1,60 @@
+use super::*;
+use crate::types::DataTypeKindExt;
+
+/// `expr [NOT] LIKE pattern [ESCAPE 'c']`, or `ILIKE` if `case_insensitive` is set.
+#[derive(PartialEq, Clone)]
+pub struct BoundLike {
+    pub expr: Box<BoundExpr>,
+    pub pattern: Box<BoundExpr>,
+    pub negated: bool,
+    pub case_insensitive: bool,
+    pub escape_char: Option<char>,
+    pub return_type: DataType,
+}
+
+impl std::fmt::Debug for BoundLike {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let not = if self.negated { "NOT " } else { "" };
+        let like = if self.case_insensitive {
+            "ILIKE"
+        } else {
+            "LIKE"
+        };
+        write!(f, "({:?} {}{} {:?}", self.expr, not, like, self.pattern)?;
+        if let Some(c) = self.escape_char {
+            write!(f, " ESCAPE '{}'", c)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Binder {
+    pub fn bind_like(
+        &mut self,
+        expr: &Expr,
+        pattern: &Expr,
+        negated: bool,
+        case_insensitive: bool,
+        escape_char: Option<char>,
+    ) -> Result<BoundExpr, BindError> {
+        let name = if case_insensitive { "ILIKE" } else { "LIKE" };
+        let mut args = [self.bind_expr(expr)?, self.bind_expr(pattern)?];
+        for arg in &mut args {
+            match arg.return_type().map(|ty| ty.kind()) {
+                None => *arg = arg.clone().cast_if_null(DataTypeKind::Varchar(None)),
+                Some(kind) if kind.is_string() => {}
+                Some(kind) => return Err(BindError::NotString(name, kind)),
+            }
+        }
+        let [expr, pattern] = args;
+        let nullable = expr.is_nullable() || pattern.is_nullable();
+        Ok(BoundExpr::Like(BoundLike {
+            expr: expr.into(),
+            pattern: pattern.into(),
+            negated,
+            case_insensitive,
+            escape_char,
+            return_type: DataType::new(DataTypeKind::Boolean, nullable),
+        }))
+    }
+}
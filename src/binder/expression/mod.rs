@@ -1,15 +1,47 @@
 use super::*;
-use crate::parser::{Expr, Value};
-use crate::types::{DataType, DataValue};
+use crate::parser::{Expr, FunctionArg, FunctionArgExpr, Value};
+use crate::types::{DataType, DataTypeExt, DataValue, Decimal};
 
+mod agg_call;
+mod binary_op;
+mod case;
 mod column_ref;
+mod function;
+mod in_list;
+mod like;
+mod subquery;
+mod type_cast;
+mod unary_op;
+mod window;
 
+pub use self::agg_call::*;
+pub use self::binary_op::*;
+pub use self::case::*;
 pub use self::column_ref::*;
+pub use self::function::*;
+pub use self::in_list::*;
+pub use self::like::*;
+pub use self::subquery::*;
+pub use self::type_cast::*;
+pub use self::unary_op::*;
+pub use self::window::*;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum BoundExpr {
     Constant(DataValue),
     ColumnRef(BoundColumnRef),
+    BinaryOp(BoundBinaryOp),
+    UnaryOp(BoundUnaryOp),
+    TypeCast(BoundTypeCast),
+    IsNull(BoundIsNull),
+    Case(BoundCase),
+    InList(BoundInList),
+    Between(BoundBetween),
+    Like(BoundLike),
+    Subquery(BoundSubquery),
+    FunctionCall(BoundFunctionCall),
+    AggCall(BoundAggCall),
+    Window(BoundWindow),
 }
 
 impl BoundExpr {
@@ -17,16 +49,197 @@ impl BoundExpr {
         match self {
             Self::Constant(v) => v.datatype(),
             Self::ColumnRef(c) => Some(c.return_type.clone()),
+            Self::BinaryOp(e) => e.return_type.clone(),
+            Self::UnaryOp(e) => e.return_type.clone(),
+            Self::TypeCast(e) => Some(e.ty.clone()),
+            Self::IsNull(_) => Some(DataTypeKind::Boolean.not_null()),
+            Self::Case(e) => Some(e.return_type.clone()),
+            Self::InList(e) => Some(e.return_type.clone()),
+            Self::Between(e) => Some(e.return_type.clone()),
+            Self::Like(e) => Some(e.return_type.clone()),
+            Self::Subquery(e) => Some(e.return_type()),
+            Self::FunctionCall(e) => Some(e.return_type.clone()),
+            Self::AggCall(e) => Some(e.return_type.clone()),
+            Self::Window(e) => Some(e.return_type.clone()),
         }
     }
+
+    /// Returns true if the expression may be `NULL`.
+    pub fn is_nullable(&self) -> bool {
+        self.return_type().map_or(true, |ty| ty.is_nullable())
+    }
+
+    /// Returns the direct sub-expressions.
+    ///
+    /// Expressions inside subqueries are not included.
+    pub fn children(&self) -> Vec<&BoundExpr> {
+        match self {
+            Self::BinaryOp(e) => vec![&e.left_expr, &e.right_expr],
+            Self::UnaryOp(e) => vec![&e.expr],
+            Self::TypeCast(e) => vec![&e.expr],
+            Self::IsNull(e) => vec![&e.expr],
+            Self::Case(e) => {
+                let branches = e.conditions.iter().chain(&e.results);
+                branches.chain(e.else_result.as_deref()).collect()
+            }
+            Self::InList(e) => std::iter::once(&*e.expr).chain(&e.list).collect(),
+            Self::Between(e) => vec![&e.expr, &e.low, &e.high],
+            Self::Like(e) => vec![&e.expr, &e.pattern],
+            Self::Subquery(e) => match &e.kind {
+                SubqueryKind::In { expr, .. } => vec![expr],
+                _ => vec![],
+            },
+            Self::FunctionCall(e) => e.args.iter().collect(),
+            Self::AggCall(e) => e.args.iter().collect(),
+            Self::Window(e) => {
+                let order_by = e.order_by.iter().map(|o| &o.expr);
+                (e.args.iter().chain(&e.partition_by).chain(order_by)).collect()
+            }
+            Self::Constant(_) | Self::ColumnRef(_) => vec![],
+        }
+    }
+
+    /// Mutable version of [`BoundExpr::children`].
+    pub fn children_mut(&mut self) -> Vec<&mut BoundExpr> {
+        match self {
+            Self::BinaryOp(e) => vec![&mut e.left_expr, &mut e.right_expr],
+            Self::UnaryOp(e) => vec![&mut e.expr],
+            Self::TypeCast(e) => vec![&mut e.expr],
+            Self::IsNull(e) => vec![&mut e.expr],
+            Self::Case(e) => {
+                let branches = e.conditions.iter_mut().chain(&mut e.results);
+                branches.chain(e.else_result.as_deref_mut()).collect()
+            }
+            Self::InList(e) => std::iter::once(&mut *e.expr).chain(&mut e.list).collect(),
+            Self::Between(e) => vec![&mut e.expr, &mut e.low, &mut e.high],
+            Self::Like(e) => vec![&mut e.expr, &mut e.pattern],
+            Self::Subquery(e) => match &mut e.kind {
+                SubqueryKind::In { expr, .. } => vec![expr],
+                _ => vec![],
+            },
+            Self::FunctionCall(e) => e.args.iter_mut().collect(),
+            Self::AggCall(e) => e.args.iter_mut().collect(),
+            Self::Window(e) => {
+                let order_by = e.order_by.iter_mut().map(|o| &mut o.expr);
+                (e.args.iter_mut().chain(&mut e.partition_by).chain(order_by)).collect()
+            }
+            Self::Constant(_) | Self::ColumnRef(_) => vec![],
+        }
+    }
+
+    /// Call `f` on this expression and all its sub-expressions in post-order.
+    ///
+    /// Expressions inside subqueries are not visited.
+    pub fn visit(&self, f: &mut impl FnMut(&BoundExpr)) {
+        for child in self.children() {
+            child.visit(f);
+        }
+        f(self);
+    }
+
+    /// Mutable version of [`BoundExpr::visit`].
+    pub fn visit_mut(&mut self, f: &mut impl FnMut(&mut BoundExpr)) {
+        for child in self.children_mut() {
+            child.visit_mut(f);
+        }
+        f(self);
+    }
+
+    /// Call `f` on this expression and its sub-expressions in pre-order. The sub-expressions
+    /// of an expression are skipped if `f` returns true on it, e.g. when it is replaced.
+    pub fn rewrite(&mut self, f: &mut impl FnMut(&mut BoundExpr) -> bool) {
+        if f(self) {
+            return;
+        }
+        for child in self.children_mut() {
+            child.rewrite(f);
+        }
+    }
+
+    /// Returns true if the expression contains a subquery.
+    pub fn contains_subquery(&self) -> bool {
+        let mut found = false;
+        self.visit(&mut |e| found |= matches!(e, BoundExpr::Subquery(_)));
+        found
+    }
 }
 
 impl Binder {
     pub fn bind_expr(&mut self, expr: &Expr) -> Result<BoundExpr, BindError> {
         match expr {
+            // `X'01ff'` is the same as `'\x01ff'::BYTEA`
+            Expr::Value(Value::HexStringLiteral(hex)) => {
+                let value = Expr::Value(Value::SingleQuotedString(format!("\\x{}", hex)));
+                self.bind_type_cast(&value, DataTypeKind::Bytea)
+            }
             Expr::Value(v) => Ok(BoundExpr::Constant(v.into())),
             Expr::Identifier(ident) => self.bind_column_ref(std::slice::from_ref(ident)),
             Expr::CompoundIdentifier(idents) => self.bind_column_ref(idents),
+            Expr::BinaryOp { left, op, right } => self.bind_binary_op(left, op, right),
+            Expr::UnaryOp { op, expr } => self.bind_unary_op(op, expr),
+            Expr::Nested(expr) => self.bind_expr(expr),
+            Expr::IsNull(expr) => self.bind_is_null(expr, false),
+            Expr::IsNotNull(expr) => self.bind_is_null(expr, true),
+            Expr::Cast { expr, data_type } => self.bind_type_cast(expr, data_type.clone()),
+            Expr::Subquery(query) => self.bind_scalar_subquery(query),
+            Expr::Exists { subquery, negated } => self.bind_exists(subquery, *negated),
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => self.bind_in_subquery(expr, subquery, *negated),
+            Expr::Function(func) if func.over.is_some() => {
+                self.bind_window_function(func, func.over.as_ref().unwrap())
+            }
+            Expr::Function(func) => self.bind_function(func),
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => self.bind_case(
+                operand.as_deref(),
+                conditions,
+                results,
+                else_result.as_deref(),
+            ),
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => self.bind_in_list(expr, list, *negated),
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => self.bind_between(expr, low, high, *negated),
+            Expr::Like {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+            } => self.bind_like(expr, pattern, *negated, false, *escape_char),
+            Expr::ILike {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+            } => self.bind_like(expr, pattern, *negated, true, *escape_char),
+            Expr::Ceil { .. }
+            | Expr::Floor { .. }
+            | Expr::Substring { .. }
+            | Expr::Trim { .. }
+            | Expr::Extract { .. } => self.bind_special_function(expr),
+            Expr::TypedString { data_type, value } => {
+                let value = Expr::Value(Value::SingleQuotedString(value.clone()));
+                self.bind_type_cast(&value, data_type.clone())
+            }
+            Expr::Interval {
+                value,
+                leading_field,
+                ..
+            } => self.bind_interval(value, leading_field.as_ref()),
             _ => todo!("bind expression: {:?}", expr),
         }
     }
@@ -38,6 +251,10 @@ impl From<&Value> for DataValue {
             Value::Number(n, _) => {
                 if let Ok(int) = n.parse::<i32>() {
                     Self::Int32(int)
+                } else if let Ok(int) = n.parse::<i64>() {
+                    Self::Int64(int)
+                } else if let Ok(decimal) = n.parse::<Decimal>() {
+                    Self::Decimal(decimal)
                 } else if let Ok(float) = n.parse::<f64>() {
                     Self::Float64(float)
                 } else {
@@ -0,0 +1,116 @@
+use super::*;
+use crate::parser::Query;
+use crate::types::DataTypeExt;
+
+#[derive(PartialEq, Clone)]
+pub struct BoundSubquery {
+    pub kind: SubqueryKind,
+    pub query: Box<BoundSelect>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SubqueryKind {
+    /// `(SELECT ..)` returning at most one value.
+    Scalar,
+    /// `[NOT] EXISTS (SELECT ..)`
+    Exists { negated: bool },
+    /// `expr [NOT] IN (SELECT ..)`
+    In { expr: Box<BoundExpr>, negated: bool },
+}
+
+impl std::fmt::Debug for BoundSubquery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            SubqueryKind::Scalar => write!(f, "(subquery)"),
+            SubqueryKind::Exists { negated } => {
+                write!(f, "({}EXISTS subquery)", if *negated { "NOT " } else { "" })
+            }
+            SubqueryKind::In { expr, negated } => {
+                let not = if *negated { "NOT " } else { "" };
+                write!(f, "({:?} {}IN subquery)", expr, not)
+            }
+        }
+    }
+}
+
+impl BoundSubquery {
+    pub fn return_type(&self) -> DataType {
+        match self.kind {
+            SubqueryKind::Scalar => {
+                let ty = self.query.select_list[0].return_type();
+                ty.map_or(DataTypeKind::Int(None), |ty| ty.kind())
+                    .nullable()
+            }
+            SubqueryKind::Exists { .. } => DataTypeKind::Boolean.not_null(),
+            SubqueryKind::In { .. } => DataTypeKind::Boolean.nullable(),
+        }
+    }
+
+    /// Returns true if the subquery references columns of the query it belongs to.
+    pub fn is_correlated(&self) -> bool {
+        self.query.max_outer_depth() > 0
+    }
+}
+
+impl Binder {
+    pub fn bind_scalar_subquery(&mut self, query: &Query) -> Result<BoundExpr, BindError> {
+        let mut query = self.bind_subquery_with_one_column(query)?;
+        query.cast_null_columns(&DataTypeKind::Int(None));
+        Ok(BoundExpr::Subquery(BoundSubquery {
+            kind: SubqueryKind::Scalar,
+            query: query.into(),
+        }))
+    }
+
+    pub fn bind_exists(&mut self, query: &Query, negated: bool) -> Result<BoundExpr, BindError> {
+        let mut query = self.bind_select(query)?;
+        query.cast_null_columns(&DataTypeKind::Int(None));
+        Ok(BoundExpr::Subquery(BoundSubquery {
+            kind: SubqueryKind::Exists { negated },
+            query: query.into(),
+        }))
+    }
+
+    pub fn bind_in_subquery(
+        &mut self,
+        expr: &Expr,
+        query: &Query,
+        negated: bool,
+    ) -> Result<BoundExpr, BindError> {
+        let expr = self.bind_expr(expr)?;
+        let mut query = self.bind_subquery_with_one_column(query)?;
+        let expr = match (expr.return_type(), query.select_list[0].return_type()) {
+            (Some(l), Some(r)) => {
+                let kind = unify_types(&l.kind(), &r.kind()).ok_or_else(|| {
+                    BindError::BinaryOpTypeMismatch(l.kind(), BinaryOperator::Eq, r.kind())
+                })?;
+                let column = query.select_list.pop().unwrap();
+                query.select_list.push(column.cast_to(&kind));
+                expr.cast_to(&kind)
+            }
+            (None, Some(r)) => expr.cast_if_null(r.kind()),
+            (l, None) => {
+                let kind = l.map_or(DataTypeKind::Int(None), |ty| ty.kind());
+                query.cast_null_columns(&kind);
+                expr.cast_if_null(kind)
+            }
+        };
+        Ok(BoundExpr::Subquery(BoundSubquery {
+            kind: SubqueryKind::In {
+                expr: expr.into(),
+                negated,
+            },
+            query: query.into(),
+        }))
+    }
+
+    fn bind_subquery_with_one_column(&mut self, query: &Query) -> Result<BoundSelect, BindError> {
+        let query = self.bind_select(query)?;
+        if query.select_list.len() != 1 {
+            return Err(BindError::SubqueryMustReturnOneColumn(
+                query.select_list.len(),
+            ));
+        }
+        Ok(query)
+    }
+}
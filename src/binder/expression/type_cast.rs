@@ -0,0 +1,142 @@
+use super::*;
+use crate::parser::{DateTimeField, Value};
+use crate::types::{DataTypeKindExt, DataValue, ExactNumberInfo};
+
+#[derive(PartialEq, Clone)]
+pub struct BoundTypeCast {
+    pub expr: Box<BoundExpr>,
+    pub ty: DataType,
+}
+
+impl std::fmt::Debug for BoundTypeCast {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}::{}", self.expr, self.ty.kind())
+    }
+}
+
+impl Binder {
+    pub fn bind_type_cast(
+        &mut self,
+        expr: &Expr,
+        ty: DataTypeKind,
+    ) -> Result<BoundExpr, BindError> {
+        check_type(&ty)?;
+        let expr = self.bind_expr(expr)?;
+        if let Some(from) = expr.return_type() {
+            if !can_cast(&from.kind(), &ty) {
+                return Err(BindError::CastTypeMismatch(from.kind(), ty));
+            }
+        }
+        let nullable = expr.return_type().map_or(true, |ty| ty.is_nullable());
+        Ok(BoundExpr::TypeCast(BoundTypeCast {
+            expr: expr.into(),
+            ty: DataType::new(ty, nullable),
+        }))
+    }
+
+    /// Bind `INTERVAL 'value' [unit]`. A number followed by a unit, e.g. `INTERVAL '1' DAY`,
+    /// is a quantity of the unit. Other units are ignored.
+    pub fn bind_interval(
+        &mut self,
+        value: &Expr,
+        leading_field: Option<&DateTimeField>,
+    ) -> Result<BoundExpr, BindError> {
+        let value = match value {
+            Expr::Value(Value::SingleQuotedString(s) | Value::Number(s, _)) => s,
+            // The parser takes the rest of the expression as the value, e.g.
+            // `INTERVAL '1 day' * 2` is parsed as `INTERVAL ('1 day' * 2)`. Bind it as
+            // `(INTERVAL '1 day') * 2` instead.
+            Expr::BinaryOp { .. }
+            | Expr::IsNull(_)
+            | Expr::IsNotNull(_)
+            | Expr::Between { .. }
+            | Expr::InList { .. } => {
+                let mut expr = value.clone();
+                let operand = leftmost_operand(&mut expr);
+                *operand = Expr::Interval {
+                    value: operand.clone().into(),
+                    leading_field: leading_field.cloned(),
+                    leading_precision: None,
+                    last_field: None,
+                    fractional_seconds_precision: None,
+                };
+                return self.bind_expr(&expr);
+            }
+            _ if leading_field.is_none() => {
+                return self.bind_type_cast(value, DataTypeKind::Interval)
+            }
+            _ => {
+                let field = leading_field.unwrap();
+                let expr = format!("INTERVAL {} {}", value, field);
+                return Err(BindError::InvalidExpression(expr));
+            }
+        };
+        let value = match leading_field {
+            Some(field) if value.trim().parse::<f64>().is_ok() => format!("{} {}", value, field),
+            _ => value.clone(),
+        };
+        let value = Expr::Value(Value::SingleQuotedString(value));
+        self.bind_type_cast(&value, DataTypeKind::Interval)
+    }
+}
+
+/// Returns the leftmost operand of an expression made of operators.
+fn leftmost_operand(expr: &mut Expr) -> &mut Expr {
+    match expr {
+        Expr::BinaryOp { left: expr, .. }
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::Between { expr, .. }
+        | Expr::InList { expr, .. } => leftmost_operand(expr),
+        expr => expr,
+    }
+}
+
+/// Returns true if values of type `from` can be explicitly casted to `to`.
+pub fn can_cast(from: &DataTypeKind, to: &DataTypeKind) -> bool {
+    let scalar = |kind: &DataTypeKind| {
+        kind.is_number() || kind.is_string() || kind == &DataTypeKind::Boolean
+    };
+    let datetime = |kind: &DataTypeKind| kind == &DataTypeKind::Date || kind.is_timestamp();
+    (scalar(from) && scalar(to))
+        || (from.is_string() && to.is_temporal())
+        || (from.is_temporal() && to.is_string())
+        || (from.is_string() && to.is_binary())
+        || (from.is_binary() && (to.is_binary() || to.is_string()))
+        || (datetime(from) && datetime(to))
+        || (from == &DataTypeKind::Interval && to == &DataTypeKind::Interval)
+}
+
+impl BoundExpr {
+    /// Cast the expression to `kind` unless it already has a compatible type.
+    pub fn cast_to(self, kind: &DataTypeKind) -> BoundExpr {
+        let ty = match self.return_type() {
+            Some(ty) => ty,
+            None => return self.cast_if_null(kind.clone()),
+        };
+        let from = ty.kind();
+        // any decimal can be used where a decimal of unlimited precision is expected
+        let any_decimal = DataTypeKind::Decimal(ExactNumberInfo::None);
+        if from == *kind
+            || from.canonical() == kind.canonical()
+            || (from.is_decimal() && kind.canonical() == any_decimal)
+        {
+            return self;
+        }
+        BoundExpr::TypeCast(BoundTypeCast {
+            expr: self.into(),
+            ty: DataType::new(kind.clone(), ty.is_nullable()),
+        })
+    }
+
+    /// Give an untyped `NULL` constant the type `kind`.
+    pub fn cast_if_null(self, kind: DataTypeKind) -> BoundExpr {
+        match self {
+            BoundExpr::Constant(DataValue::Null) => BoundExpr::TypeCast(BoundTypeCast {
+                expr: self.into(),
+                ty: DataType::new(kind, true),
+            }),
+            expr => expr,
+        }
+    }
+}
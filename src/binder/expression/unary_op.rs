@@ -0,0 +1,65 @@
+use super::*;
+use crate::types::DataTypeKindExt;
+
+#[derive(PartialEq, Clone)]
+pub struct BoundUnaryOp {
+    pub op: UnaryOperator,
+    pub expr: Box<BoundExpr>,
+    pub return_type: Option<DataType>,
+}
+
+impl std::fmt::Debug for BoundUnaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({} {:?})", self.op, self.expr)
+    }
+}
+
+#[derive(PartialEq, Clone)]
+pub struct BoundIsNull {
+    pub expr: Box<BoundExpr>,
+    pub negated: bool,
+}
+
+impl std::fmt::Debug for BoundIsNull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let not = if self.negated { "NOT " } else { "" };
+        write!(f, "({:?} IS {}NULL)", self.expr, not)
+    }
+}
+
+impl Binder {
+    pub fn bind_unary_op(
+        &mut self,
+        op: &UnaryOperator,
+        expr: &Expr,
+    ) -> Result<BoundExpr, BindError> {
+        let expr = self.bind_expr(expr)?;
+        let return_type = expr.return_type();
+        let expr = match (op, return_type.as_ref().map(|ty| ty.kind())) {
+            (UnaryOperator::Not, None) => expr.cast_if_null(DataTypeKind::Boolean),
+            (UnaryOperator::Not, Some(DataTypeKind::Boolean)) => expr,
+            (UnaryOperator::Plus | UnaryOperator::Minus, None) => {
+                expr.cast_if_null(DataTypeKind::Int(None))
+            }
+            (UnaryOperator::Plus | UnaryOperator::Minus, Some(kind)) if kind.is_number() => expr,
+            (UnaryOperator::Plus | UnaryOperator::Minus, Some(DataTypeKind::Interval)) => expr,
+            (_, Some(kind)) => return Err(BindError::UnaryOpTypeMismatch(op.clone(), kind)),
+            (_, None) => return Err(BindError::NotSupported(format!("unary operator {}", op))),
+        };
+        Ok(BoundExpr::UnaryOp(BoundUnaryOp {
+            op: op.clone(),
+            return_type: expr.return_type(),
+            expr: expr.into(),
+        }))
+    }
+
+    pub fn bind_is_null(&mut self, expr: &Expr, negated: bool) -> Result<BoundExpr, BindError> {
+        Ok(BoundExpr::IsNull(BoundIsNull {
+            expr: self
+                .bind_expr(expr)?
+                .cast_if_null(DataTypeKind::Boolean)
+                .into(),
+            negated,
+        }))
+    }
+}
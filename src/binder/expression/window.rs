@@ -0,0 +1,290 @@
+use super::*;
+use crate::parser::{Function, OrderByExpr, WindowFrameBound, WindowFrameUnits, WindowSpec};
+use crate::types::DataTypeExt;
+
+/// A window function call: `func(args) OVER (PARTITION BY .. ORDER BY .. frame)`.
+#[derive(PartialEq, Clone)]
+pub struct BoundWindow {
+    pub func: WindowFunc,
+    pub args: Vec<BoundExpr>,
+    pub partition_by: Vec<BoundExpr>,
+    pub order_by: Vec<BoundOrderBy>,
+    pub frame: BoundWindowFrame,
+    pub return_type: DataType,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum WindowFunc {
+    RowNumber,
+    Rank,
+    DenseRank,
+    /// The value `offset` rows before the current row.
+    Lag {
+        offset: usize,
+    },
+    /// The value `offset` rows after the current row.
+    Lead {
+        offset: usize,
+    },
+    FirstValue,
+    LastValue,
+    Agg(AggKind),
+}
+
+#[derive(PartialEq, Clone)]
+pub struct BoundOrderBy {
+    pub expr: BoundExpr,
+    pub desc: bool,
+    pub nulls_first: bool,
+}
+
+/// The rows of the partition that a window function is computed over.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundWindowFrame {
+    /// `ROWS` if true, `RANGE` otherwise. In `RANGE` mode, a frame bound of `CURRENT ROW`
+    /// includes all peers of the current row, i.e. the rows with equal ORDER BY values.
+    pub rows: bool,
+    pub start: FrameBound,
+    pub end: FrameBound,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(usize),
+    CurrentRow,
+    Following(usize),
+    UnboundedFollowing,
+}
+
+impl std::fmt::Debug for BoundWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?}({:?}) over (partition: {:?}, order: {:?}, frame: {:?})",
+            self.func, self.args, self.partition_by, self.order_by, self.frame
+        )
+    }
+}
+
+impl std::fmt::Debug for BoundOrderBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} {} nulls {}",
+            self.expr,
+            if self.desc { "desc" } else { "asc" },
+            if self.nulls_first { "first" } else { "last" }
+        )
+    }
+}
+
+impl Binder {
+    pub fn bind_window_function(
+        &mut self,
+        func: &Function,
+        spec: &WindowSpec,
+    ) -> Result<BoundExpr, BindError> {
+        let name = func.name.to_string().to_lowercase();
+        if func.distinct {
+            return Err(BindError::InvalidFunctionArguments(name));
+        }
+        let mut args = self.bind_function_args(&name, &func.args)?;
+        if args.iter().any(|arg| arg.contains_window()) {
+            return Err(BindError::WindowFunctionNotAllowed(
+                "window function arguments",
+            ));
+        }
+        let invalid_args = || BindError::InvalidFunctionArguments(name.clone());
+        let arg_kind = args.first().map(|arg| {
+            arg.return_type()
+                .map_or(DataTypeKind::Int(None), |ty| ty.kind())
+        });
+        let (func, return_type) = match name.as_str() {
+            "row_number" | "rank" | "dense_rank" => {
+                if !args.is_empty() {
+                    return Err(invalid_args());
+                }
+                let func = match name.as_str() {
+                    "row_number" => WindowFunc::RowNumber,
+                    "rank" => WindowFunc::Rank,
+                    _ => WindowFunc::DenseRank,
+                };
+                (func, DataTypeKind::Int(None).not_null())
+            }
+            "lag" | "lead" => {
+                if args.is_empty() || args.len() > 3 {
+                    return Err(invalid_args());
+                }
+                let kind = arg_kind.unwrap();
+                let offset = match args.get(1) {
+                    None => 1,
+                    Some(BoundExpr::Constant(DataValue::Int32(n))) if *n >= 0 => *n as usize,
+                    Some(_) => return Err(invalid_args()),
+                };
+                // Keep the value and the default.
+                let default = if args.len() == 3 { args.pop() } else { None };
+                args.truncate(1);
+                if let Some(default) = default {
+                    args.push(default.cast_to(&kind));
+                }
+                args[0] = std::mem::replace(&mut args[0], BoundExpr::Constant(DataValue::Null))
+                    .cast_if_null(kind.clone());
+                let func = match name.as_str() {
+                    "lag" => WindowFunc::Lag { offset },
+                    _ => WindowFunc::Lead { offset },
+                };
+                (func, kind.nullable())
+            }
+            "first_value" | "last_value" => {
+                if args.len() != 1 {
+                    return Err(invalid_args());
+                }
+                let kind = arg_kind.unwrap();
+                args[0] = std::mem::replace(&mut args[0], BoundExpr::Constant(DataValue::Null))
+                    .cast_if_null(kind.clone());
+                let func = match name.as_str() {
+                    "first_value" => WindowFunc::FirstValue,
+                    _ => WindowFunc::LastValue,
+                };
+                (func, kind.nullable())
+            }
+            _ if AggKind::from_name(&name).is_none() => {
+                let call = (self.bind_user_agg(&name, args)?)
+                    .ok_or_else(|| BindError::InvalidWindowFunction(name.clone()))?;
+                args = call.args;
+                (WindowFunc::Agg(call.kind), call.return_type)
+            }
+            _ => {
+                let agg = AggKind::from_name(&name)
+                    .ok_or_else(|| BindError::InvalidWindowFunction(name.clone()))?;
+                let expected_args = if agg == AggKind::Count { 0..=1 } else { 1..=1 };
+                if !expected_args.contains(&args.len()) {
+                    return Err(invalid_args());
+                }
+                let return_type = agg
+                    .return_type(arg_kind.as_ref())
+                    .ok_or_else(invalid_args)?;
+                if let Some(arg) = args.first_mut() {
+                    let kind = arg_kind.unwrap();
+                    *arg = std::mem::replace(arg, BoundExpr::Constant(DataValue::Null))
+                        .cast_if_null(kind);
+                }
+                (WindowFunc::Agg(agg), return_type)
+            }
+        };
+
+        let mut partition_by = vec![];
+        for expr in spec.partition_by.iter() {
+            partition_by.push(self.bind_expr(expr)?);
+        }
+        let mut order_by = vec![];
+        for expr in spec.order_by.iter() {
+            order_by.push(self.bind_order_by(expr)?);
+        }
+        let window_exprs = partition_by.iter().chain(order_by.iter().map(|o| &o.expr));
+        if window_exprs.clone().any(|e| e.contains_window()) {
+            return Err(BindError::WindowFunctionNotAllowed("window definition"));
+        }
+        // Constants are untyped if they are NULL.
+        let partition_by = partition_by
+            .into_iter()
+            .map(|e| e.cast_if_null(DataTypeKind::Int(None)))
+            .collect();
+        for o in order_by.iter_mut() {
+            o.expr = std::mem::replace(&mut o.expr, BoundExpr::Constant(DataValue::Null))
+                .cast_if_null(DataTypeKind::Int(None));
+        }
+
+        let frame = match &spec.window_frame {
+            None => BoundWindowFrame {
+                rows: false,
+                start: FrameBound::UnboundedPreceding,
+                end: FrameBound::CurrentRow,
+            },
+            Some(frame) => {
+                let invalid_frame = || {
+                    BindError::InvalidWindowFrame(match &frame.end_bound {
+                        Some(end) => {
+                            format!("{} BETWEEN {} AND {}", frame.units, frame.start_bound, end)
+                        }
+                        None => format!("{} {}", frame.units, frame.start_bound),
+                    })
+                };
+                let rows = match frame.units {
+                    WindowFrameUnits::Rows => true,
+                    WindowFrameUnits::Range => false,
+                    WindowFrameUnits::Groups => return Err(invalid_frame()),
+                };
+                let start = self.bind_frame_bound(&frame.start_bound)?;
+                let end = match &frame.end_bound {
+                    Some(bound) => self.bind_frame_bound(bound)?,
+                    None => FrameBound::CurrentRow,
+                };
+                let has_offset =
+                    |b| matches!(b, FrameBound::Preceding(_) | FrameBound::Following(_));
+                let invalid = start == FrameBound::UnboundedFollowing
+                    || end == FrameBound::UnboundedPreceding
+                    || kind_order(start) > kind_order(end)
+                    || (!rows && (has_offset(start) || has_offset(end)));
+                if invalid {
+                    return Err(invalid_frame());
+                }
+                BoundWindowFrame { rows, start, end }
+            }
+        };
+
+        Ok(BoundExpr::Window(BoundWindow {
+            func,
+            args,
+            partition_by,
+            order_by,
+            frame,
+            return_type,
+        }))
+    }
+
+    pub fn bind_order_by(&mut self, expr: &OrderByExpr) -> Result<BoundOrderBy, BindError> {
+        let desc = expr.asc == Some(false);
+        Ok(BoundOrderBy {
+            expr: self.bind_expr(&expr.expr)?,
+            desc,
+            // NULLs are larger than any other values by default.
+            nulls_first: expr.nulls_first.unwrap_or(desc),
+        })
+    }
+
+    fn bind_frame_bound(&mut self, bound: &WindowFrameBound) -> Result<FrameBound, BindError> {
+        let offset = |binder: &mut Self, expr: &Expr| match binder.bind_expr(expr)? {
+            BoundExpr::Constant(DataValue::Int32(n)) if n >= 0 => Ok(n as usize),
+            _ => Err(BindError::InvalidWindowFrame(expr.to_string())),
+        };
+        Ok(match bound {
+            WindowFrameBound::CurrentRow => FrameBound::CurrentRow,
+            WindowFrameBound::Preceding(None) => FrameBound::UnboundedPreceding,
+            WindowFrameBound::Following(None) => FrameBound::UnboundedFollowing,
+            WindowFrameBound::Preceding(Some(expr)) => FrameBound::Preceding(offset(self, expr)?),
+            WindowFrameBound::Following(Some(expr)) => FrameBound::Following(offset(self, expr)?),
+        })
+    }
+}
+
+/// The order of the kinds of frame bounds, ignoring offsets.
+fn kind_order(bound: FrameBound) -> u8 {
+    match bound {
+        FrameBound::UnboundedPreceding => 0,
+        FrameBound::Preceding(_) => 1,
+        FrameBound::CurrentRow => 2,
+        FrameBound::Following(_) => 3,
+        FrameBound::UnboundedFollowing => 4,
+    }
+}
+
+impl BoundExpr {
+    /// Returns true if the expression contains a window function.
+    pub fn contains_window(&self) -> bool {
+        let mut found = false;
+        self.visit(&mut |e| found |= matches!(e, BoundExpr::Window(_)));
+        found
+    }
+}
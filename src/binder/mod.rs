@@ -1,8 +1,14 @@
-use std::collections::HashMap;
+use std::sync::Arc;
 use std::vec::Vec;
 
 use crate::catalog::*;
-use crate::parser::{Ident, ObjectName, Statement};
+use crate::parser::{
+    refreshed_materialized_view, BinaryOperator, Ident, ObjectName, ObjectType, SetOperator,
+    Statement, UnaryOperator,
+};
+use crate::types::{
+    DataType, DataTypeKind, DataTypeKindExt, Timestamp, TimezoneInfo, MAX_DECIMAL_PRECISION,
+};
 
 mod expression;
 mod statement;
@@ -17,6 +23,20 @@ pub enum BoundStatement {
     CreateTable(BoundCreateTable),
     Select(BoundSelect),
     Insert(BoundInsert),
+    DropTable(BoundDropTable),
+    AlterTable(BoundAlterTable),
+    CreateSchema(BoundCreateSchema),
+    DropSchema(BoundDropSchema),
+    CreateIndex(BoundCreateIndex),
+    DropIndex(BoundDropIndex),
+    CreateView(BoundCreateView),
+    DropView(BoundDropView),
+    Truncate(BoundTruncate),
+    CreateMaterializedView(BoundCreateMaterializedView),
+    RefreshMaterializedView(BoundRefreshMaterializedView),
+    /// Set the schemas that unqualified table names are looked up in.
+    SetSearchPath(Vec<String>),
+    ShowSearchPath,
     Explain(Box<BoundStatement>),
 }
 
@@ -30,10 +50,51 @@ pub enum BindError {
     TableNotFound(String),
     #[error("column not found: {0}")]
     ColumnNotFound(String),
+    #[error("index not found: {0}")]
+    IndexNotFound(String),
+    #[error("schema already exists: {0}")]
+    DuplicatedSchema(String),
+    #[error("schema {0} is not empty")]
+    SchemaNotEmpty(String),
+    #[error("no schema has been selected to create in")]
+    NoSchemaSelected,
+    #[error("invalid schema name: {0}")]
+    InvalidSchemaName(String),
+    #[error("unrecognized configuration parameter: {0}")]
+    UnknownVariable(String),
+    #[error("invalid value for parameter {0}")]
+    InvalidVariableValue(String),
     #[error("duplicated table: {0}")]
     DuplicatedTable(String),
     #[error("duplicated column: {0}")]
     DuplicatedColumn(String),
+    #[error("duplicated index: {0}")]
+    DuplicatedIndex(String),
+    #[error("duplicated view: {0}")]
+    DuplicatedView(String),
+    #[error("view not found: {0}")]
+    ViewNotFound(String),
+    #[error("cannot change the columns of view {0}")]
+    ViewColumnsChanged(String),
+    #[error("infinite recursion detected in view {0}")]
+    RecursiveView(String),
+    #[error("the query of view {0} no longer matches its columns")]
+    InvalidView(String),
+    /// The view is described as `view v` or `materialized view v`.
+    #[error("cannot {0} because {1} depends on it")]
+    DependentView(String, String),
+    #[error("{0} is not a materialized view")]
+    NotMaterializedView(String),
+    #[error("cannot change materialized view {0}")]
+    ChangeMaterializedView(String),
+    #[error("duplicated constraint: {0}")]
+    DuplicatedConstraint(String),
+    #[error("multiple primary keys for table {0} are not allowed")]
+    MultiplePrimaryKeys(String),
+    #[error("cannot drop the only column: {0}")]
+    DropOnlyColumn(String),
+    #[error("column {0} is not nullable and must have a default value")]
+    NoDefaultValue(String),
     #[error("invalid table name: {0:?}")]
     InvalidTableName(Vec<Ident>),
     #[error("duplicated alias: {0}")]
@@ -46,42 +107,304 @@ pub enum BindError {
     TupleLengthMismatch { expected: usize, actual: usize },
     #[error("value should not be null in column: {0}")]
     NullValueInColumn(String),
+    #[error("type mismatch: {0:?} {1} {2:?}")]
+    BinaryOpTypeMismatch(DataTypeKind, BinaryOperator, DataTypeKind),
+    #[error("type mismatch: {0} {1:?}")]
+    UnaryOpTypeMismatch(UnaryOperator, DataTypeKind),
+    #[error("can not cast {0:?} to {1:?}")]
+    CastTypeMismatch(DataTypeKind, DataTypeKind),
+    #[error("argument of {0} must be boolean, not {1:?}")]
+    NotBoolean(&'static str, DataTypeKind),
+    #[error("argument of {0} must be a string, not {1:?}")]
+    NotString(&'static str, DataTypeKind),
+    #[error("{0} types {1:?} and {2:?} cannot be matched")]
+    TypeMismatch(&'static str, DataTypeKind, DataTypeKind),
+    #[error("subquery must return only one column, got {0}")]
+    SubqueryMustReturnOneColumn(usize),
+    #[error("function not found: {0}")]
+    FunctionNotFound(String),
+    #[error("invalid arguments for function {0}")]
+    InvalidFunctionArguments(String),
+    #[error("{0} is not a window function")]
+    InvalidWindowFunction(String),
+    #[error("window functions are not allowed in {0}")]
+    WindowFunctionNotAllowed(&'static str),
+    #[error("invalid window frame: {0}")]
+    InvalidWindowFrame(String),
+    #[error("subqueries are not allowed in {0}")]
+    SubqueryNotAllowed(&'static str),
+    #[error("aggregate functions are not allowed in {0}")]
+    AggregateNotAllowed(&'static str),
+    #[error("column {0} must appear in the GROUP BY clause or be used in an aggregate function")]
+    ColumnNotInGroupBy(String),
+    #[error("GROUP BY position {0} is not in select list")]
+    GroupByPositionOutOfRange(String),
+    #[error("non-integer constant in GROUP BY")]
+    NonIntegerGroupByConstant,
+    #[error("{0} types {1:?} and {2:?} cannot be matched")]
+    SetOperationTypeMismatch(SetOperator, DataTypeKind, DataTypeKind),
+    #[error("invalid type {0}: {1}")]
+    InvalidType(DataTypeKind, &'static str),
+    #[error("invalid expression: {0}")]
+    InvalidExpression(String),
+    #[error("not supported: {0}")]
+    NotSupported(String),
 }
 
-type TableName = String;
+/// A relation in the FROM clause that column references can be resolved against.
+#[derive(Debug, Clone)]
+struct Relation {
+    name: String,
+    /// The position of the first column in the input row of the query.
+    offset: usize,
+    columns: Vec<(String, DataType)>,
+}
+
+/// A common table expression defined in a WITH clause.
+#[derive(Debug, Clone)]
+struct CteBinding {
+    name: String,
+    id: usize,
+    columns: Vec<(String, DataType)>,
+    /// Whether the CTE has been referenced since it was defined.
+    referenced: bool,
+}
+
+/// The names visible to a query block.
+#[derive(Debug, Default)]
+struct Context {
+    relations: Vec<Relation>,
+    width: usize,
+    ctes: Vec<CteBinding>,
+}
 
 pub struct Binder {
     catalog: CatalogRef,
-    tables: HashMap<TableName, TableRefId>,
+    /// The contexts of the query blocks being bound. The last one is the innermost.
+    contexts: Vec<Context>,
+    next_cte_id: usize,
+    /// The time when the statement is bound, which is returned by `NOW()` so that it does not
+    /// change within the statement.
+    now: Timestamp,
+    /// The schemas that unqualified table names are looked up in, in order.
+    search_path: Vec<String>,
+    /// The tables and views referenced by the statement, not including those referenced by
+    /// the views.
+    dependencies: Vec<TableRefId>,
 }
 
 impl Binder {
     pub fn new(catalog: CatalogRef) -> Self {
         Binder {
             catalog,
-            tables: HashMap::default(),
+            contexts: vec![],
+            next_cte_id: 0,
+            now: Timestamp::now(),
+            search_path: vec![DEFAULT_SCHEMA_NAME.into()],
+            dependencies: vec![],
         }
     }
 
+    /// Look up unqualified table names in the given schemas instead of the default one.
+    pub fn with_search_path(mut self, search_path: Vec<String>) -> Self {
+        self.search_path = search_path;
+        self
+    }
+
+    fn context(&mut self) -> &mut Context {
+        self.contexts.last_mut().expect("no query context")
+    }
+
     pub fn bind(&mut self, stmt: &Statement) -> Result<BoundStatement, BindError> {
         match stmt {
             Statement::CreateTable { .. } => {
                 Ok(BoundStatement::CreateTable(self.bind_create_table(stmt)?))
             }
             Statement::Insert { .. } => Ok(BoundStatement::Insert(self.bind_insert(stmt)?)),
-            Statement::Explain { statement, .. } => {
-                Ok(BoundStatement::Explain(self.bind(&*statement)?.into()))
+            Statement::Drop {
+                object_type: ObjectType::Table,
+                ..
+            } => Ok(BoundStatement::DropTable(self.bind_drop_table(stmt)?)),
+            Statement::Truncate { .. } => Ok(BoundStatement::Truncate(self.bind_truncate(stmt)?)),
+            Statement::AlterTable { .. } => {
+                Ok(BoundStatement::AlterTable(self.bind_alter_table(stmt)?))
+            }
+            Statement::Explain { statement, .. } => match self.bind(statement)? {
+                BoundStatement::SetSearchPath(_) | BoundStatement::ShowSearchPath => Err(
+                    BindError::NotSupported(format!("explain statement: {}", statement)),
+                ),
+                stmt => Ok(BoundStatement::Explain(stmt.into())),
+            },
+            Statement::Query(query) => {
+                let mut select = self.bind_select(query)?;
+                select.cast_null_columns(&DataTypeKind::Varchar(None));
+                Ok(BoundStatement::Select(select))
             }
-            Statement::Query(query) => Ok(BoundStatement::Select(self.bind_select(&*query)?)),
+            Statement::CreateSchema { .. } => {
+                Ok(BoundStatement::CreateSchema(self.bind_create_schema(stmt)?))
+            }
+            Statement::Drop {
+                object_type: ObjectType::Schema,
+                ..
+            } => Ok(BoundStatement::DropSchema(self.bind_drop_schema(stmt)?)),
+            Statement::CreateIndex { .. } => {
+                Ok(BoundStatement::CreateIndex(self.bind_create_index(stmt)?))
+            }
+            Statement::Drop {
+                object_type: ObjectType::Index,
+                ..
+            } => Ok(BoundStatement::DropIndex(self.bind_drop_index(stmt)?)),
+            Statement::CreateView {
+                materialized: true, ..
+            } => Ok(BoundStatement::CreateMaterializedView(
+                self.bind_create_materialized_view(stmt)?,
+            )),
+            Statement::CreateView { .. } => {
+                Ok(BoundStatement::CreateView(self.bind_create_view(stmt)?))
+            }
+            Statement::Cache { .. } if refreshed_materialized_view(stmt).is_some() => Ok(
+                BoundStatement::RefreshMaterializedView(self.bind_refresh_materialized_view(stmt)?),
+            ),
+            Statement::Drop {
+                object_type: ObjectType::View,
+                ..
+            } => Ok(BoundStatement::DropView(self.bind_drop_view(stmt)?)),
+            Statement::SetVariable {
+                variable, value, ..
+            } => self.bind_set_variable(variable, value),
+            Statement::ShowVariable { variable } => self.bind_show_variable(variable),
             _ => todo!("bind statement: {:#?}", stmt),
         }
     }
 }
 
-fn split_name(name: &ObjectName) -> Result<(&str, &str), BindError> {
+/// Check the parameters of a type given in `CREATE TABLE` or `CAST`.
+fn check_type(kind: &DataTypeKind) -> Result<(), BindError> {
+    if let Some((Some(precision), scale)) = kind.decimal_precision_scale() {
+        if precision == 0 || precision > MAX_DECIMAL_PRECISION {
+            return Err(BindError::InvalidType(
+                kind.clone(),
+                "precision must be between 1 and 28",
+            ));
+        }
+        if scale.unwrap_or(0) > precision {
+            return Err(BindError::InvalidType(
+                kind.clone(),
+                "scale must not be greater than precision",
+            ));
+        }
+    }
+    if let DataTypeKind::Timestamp(_, TimezoneInfo::WithTimeZone | TimezoneInfo::Tz) = kind {
+        return Err(BindError::InvalidType(
+            kind.clone(),
+            "time zones are not supported",
+        ));
+    }
+    Ok(())
+}
+
+/// Split a table name into the schema name, if it is qualified, and the table name.
+fn split_name(name: &ObjectName) -> Result<(Option<&str>, &str), BindError> {
     Ok(match name.0.as_slice() {
-        [table] => (DEFAULT_SCHEMA_NAME, &table.value),
-        [schema, table] => (&schema.value, &table.value),
+        [table] => (None, &table.value),
+        [schema, table] => (Some(&schema.value), &table.value),
         _ => return Err(BindError::InvalidTableName(name.0.clone())),
     })
 }
+
+impl Binder {
+    /// Returns the schema with the name, or an error if it does not exist.
+    fn get_schema(&self, name: &str) -> Result<Arc<SchemaCatalog>, BindError> {
+        (self.catalog.get_schema_by_name(name))
+            .ok_or_else(|| BindError::SchemaNotFound(name.into()))
+    }
+
+    /// Returns the table of the name and its schema. An unqualified name is looked up in the
+    /// schemas of the search path in order.
+    fn resolve_table(
+        &self,
+        name: &ObjectName,
+    ) -> Result<(Arc<SchemaCatalog>, Arc<TableCatalog>), BindError> {
+        let (schema_name, table_name) = split_name(name)?;
+        let schemas = match schema_name {
+            Some(schema_name) => vec![self.get_schema(schema_name)?],
+            None => (self.search_path.iter())
+                .filter_map(|name| self.catalog.get_schema_by_name(name))
+                .collect(),
+        };
+        for schema in schemas {
+            if let Some(table) = schema.get_table_by_name(table_name) {
+                return Ok((schema, table));
+            }
+            // a view of the name hides the tables in the schemas after it
+            if schema.get_view_by_name(table_name).is_some() {
+                break;
+            }
+        }
+        Err(BindError::TableNotFound(table_name.into()))
+    }
+
+    /// Returns the view of the name and the id of its schema, if the name refers to a view. An
+    /// unqualified name refers to the first table or view of the name in the search path.
+    fn resolve_view(
+        &self,
+        name: &ObjectName,
+    ) -> Result<Option<(SchemaId, Arc<ViewCatalog>)>, BindError> {
+        let (schema_name, view_name) = split_name(name)?;
+        let schemas = match schema_name {
+            Some(schema_name) => vec![self.get_schema(schema_name)?],
+            None => (self.search_path.iter())
+                .filter_map(|name| self.catalog.get_schema_by_name(name))
+                .collect(),
+        };
+        for schema in schemas {
+            if schema.get_table_by_name(view_name).is_some() {
+                return Ok(None);
+            }
+            if let Some(view) = schema.get_view_by_name(view_name) {
+                return Ok(Some((schema.id(), view)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns an error if a view depends on the table or view, unless it is in `dropped`.
+    /// `action` describes what is done to the table or view in the error.
+    fn check_dependent_views(
+        &self,
+        table_ref_id: TableRefId,
+        dropped: &[TableRefId],
+        action: impl FnOnce() -> String,
+    ) -> Result<(), BindError> {
+        let dependent = (self.catalog.dependent_views(table_ref_id).into_iter())
+            .find(|(id, _)| !dropped.contains(id));
+        match dependent {
+            Some((id, view_name)) => {
+                // materialized views are stored as tables
+                let kind = match self.catalog.get_table(id) {
+                    Some(_) => "materialized view",
+                    None => "view",
+                };
+                let view = format!("{} {}", kind, view_name);
+                Err(BindError::DependentView(action(), view))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the schema that a table of the name is created in and the table name. Tables
+    /// of unqualified names are created in the first existing schema of the search path.
+    fn resolve_new_table<'a>(
+        &self,
+        name: &'a ObjectName,
+    ) -> Result<(Arc<SchemaCatalog>, &'a str), BindError> {
+        let (schema_name, table_name) = split_name(name)?;
+        let schema = match schema_name {
+            Some(schema_name) => self.get_schema(schema_name)?,
+            None => (self.search_path.iter())
+                .find_map(|name| self.catalog.get_schema_by_name(name))
+                .ok_or(BindError::NoSchemaSelected)?,
+        };
+        Ok((schema, table_name))
+    }
+}
@@ -0,0 +1,130 @@
+use super::*;
+use crate::catalog::ColumnDesc;
+use crate::parser::{AlterTableOperation, ColumnOption, Statement};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundAlterTable {
+    pub table_ref_id: TableRefId,
+    pub action: AlterTableAction,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum AlterTableAction {
+    /// Add a column. The existing rows get its default value, or null without it.
+    AddColumn {
+        name: String,
+        desc: ColumnDesc,
+    },
+    /// Drop a column. It is `None` if the column does not exist and `IF EXISTS` is given.
+    DropColumn {
+        column_id: Option<ColumnId>,
+    },
+    RenameColumn {
+        column_id: ColumnId,
+        new_name: String,
+    },
+    RenameTable {
+        new_name: String,
+    },
+}
+
+impl Binder {
+    pub fn bind_alter_table(&mut self, stmt: &Statement) -> Result<BoundAlterTable, BindError> {
+        let (name, operation) = match stmt {
+            Statement::AlterTable { name, operation } => (name, operation),
+            _ => panic!("mismatched statement type"),
+        };
+        let (schema, table) = self.resolve_table(name)?;
+        let table_ref_id = TableRefId::new(schema.id(), table.id());
+        if table.view_definition().is_some() {
+            return Err(BindError::ChangeMaterializedView(table.name()));
+        }
+        // the query of a view is bound again when it is referenced, so the tables it depends
+        // on must not change
+        self.check_dependent_views(table_ref_id, &[], || {
+            format!("alter table {}", table.name())
+        })?;
+        let action = match operation {
+            AlterTableOperation::AddColumn { column_def } => {
+                let name = column_def.name.value.clone();
+                if table.contains_column(&name) {
+                    return Err(BindError::DuplicatedColumn(name));
+                }
+                check_type(&column_def.data_type)?;
+                let mut desc = ColumnDesc::from(column_def);
+                for opt in column_def.options.iter() {
+                    match &opt.option {
+                        ColumnOption::Default(expr) => {
+                            let default = self.bind_default(expr, &name, desc.datatype())?;
+                            desc = desc.with_default(default);
+                        }
+                        ColumnOption::Unique { .. } | ColumnOption::Check(_) => {
+                            let option = format!("add column with constraint {}", opt.option);
+                            return Err(BindError::NotSupported(option));
+                        }
+                        _ => {}
+                    }
+                }
+                if !desc.is_nullable() && desc.default().is_none() {
+                    return Err(BindError::NoDefaultValue(name));
+                }
+                AlterTableAction::AddColumn { name, desc }
+            }
+            AlterTableOperation::DropColumn {
+                column_name,
+                if_exists,
+                ..
+            } => {
+                let column_id = match table.get_column_by_name(&column_name.value) {
+                    Some(column) => Some(column.id()),
+                    None if *if_exists => None,
+                    None => return Err(BindError::ColumnNotFound(column_name.value.clone())),
+                };
+                if column_id.is_some() && table.all_columns().len() == 1 {
+                    return Err(BindError::DropOnlyColumn(column_name.value.clone()));
+                }
+                AlterTableAction::DropColumn { column_id }
+            }
+            AlterTableOperation::RenameColumn {
+                old_column_name,
+                new_column_name,
+            } => {
+                let column = table
+                    .get_column_by_name(&old_column_name.value)
+                    .ok_or_else(|| BindError::ColumnNotFound(old_column_name.value.clone()))?;
+                if table.contains_column(&new_column_name.value) {
+                    return Err(BindError::DuplicatedColumn(new_column_name.value.clone()));
+                }
+                AlterTableAction::RenameColumn {
+                    column_id: column.id(),
+                    new_name: new_column_name.value.clone(),
+                }
+            }
+            AlterTableOperation::RenameTable { table_name } => {
+                // the table can not be moved to another schema
+                let new_name = match split_name(table_name)? {
+                    (Some(schema_name), _) if schema_name != schema.name() => {
+                        return Err(BindError::InvalidTableName(table_name.0.clone()));
+                    }
+                    (_, new_name) => new_name,
+                };
+                if schema.get_table_by_name(new_name).is_some()
+                    || schema.get_view_by_name(new_name).is_some()
+                {
+                    return Err(BindError::DuplicatedTable(new_name.into()));
+                }
+                AlterTableAction::RenameTable {
+                    new_name: new_name.into(),
+                }
+            }
+            op => {
+                let op = format!("alter table operation: {}", op);
+                return Err(BindError::NotSupported(op));
+            }
+        };
+        Ok(BoundAlterTable {
+            table_ref_id,
+            action,
+        })
+    }
+}
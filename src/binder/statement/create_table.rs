@@ -1,8 +1,10 @@
 use std::collections::HashSet;
 
+use itertools::Itertools;
+
 use super::*;
-use crate::catalog::ColumnDesc;
-use crate::parser::{ColumnDef, ColumnOption, Statement};
+use crate::catalog::{ColumnDesc, UniqueConstraint};
+use crate::parser::{ColumnDef, ColumnOption, Expr, Statement, TableConstraint};
 use crate::types::DataType;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -10,23 +12,31 @@ pub struct BoundCreateTable {
     pub schema_id: SchemaId,
     pub table_name: String,
     pub columns: Vec<(String, ColumnDesc)>,
+    /// The unique constraints, in which the id of a column is its position in `columns`.
+    pub uniques: Vec<UniqueConstraint>,
+    /// The check constraints, in which the index of a column reference is the position of
+    /// the column in `columns`.
+    pub checks: Vec<(String, BoundExpr)>,
 }
 
 impl Binder {
     pub fn bind_create_table(&mut self, stmt: &Statement) -> Result<BoundCreateTable, BindError> {
         match stmt {
-            Statement::CreateTable { name, columns, .. } => {
+            Statement::CreateTable {
+                name,
+                columns,
+                constraints,
+                ..
+            } => {
                 // check empty columns
                 if columns.is_empty() {
                     return Err(BindError::EmptyColumns);
                 }
-                let (schema_name, table_name) = split_name(name)?;
-                let schema = self
-                    .catalog
-                    .get_schema_by_name(schema_name)
-                    .ok_or_else(|| BindError::SchemaNotFound(schema_name.into()))?;
+                let (schema, table_name) = self.resolve_new_table(name)?;
                 // check duplicated table name
-                if schema.get_table_by_name(table_name).is_some() {
+                if schema.get_table_by_name(table_name).is_some()
+                    || schema.get_view_by_name(table_name).is_some()
+                {
                     return Err(BindError::DuplicatedTable(table_name.into()));
                 }
                 // check duplicated column names
@@ -35,20 +45,189 @@ impl Binder {
                     if !set.insert(col.name.value.clone()) {
                         return Err(BindError::DuplicatedColumn(col.name.value.clone()));
                     }
+                    check_type(&col.data_type)?;
+                }
+                let mut descs = vec![];
+                // the constraints, each with its name if given and the default name
+                let mut uniques = vec![];
+                let mut checks = vec![];
+                for (idx, col) in columns.iter().enumerate() {
+                    let name = &col.name.value;
+                    let mut desc = ColumnDesc::from(col);
+                    for opt in col.options.iter() {
+                        let constraint_name = opt.name.as_ref().map(|name| name.value.clone());
+                        match &opt.option {
+                            ColumnOption::Default(expr) => {
+                                let default = self.bind_default(expr, name, desc.datatype())?;
+                                desc = desc.with_default(default);
+                            }
+                            ColumnOption::Unique { is_primary } => uniques.push((
+                                constraint_name,
+                                unique_name(table_name, &[name], *is_primary),
+                                vec![idx],
+                                *is_primary,
+                            )),
+                            ColumnOption::Check(expr) => checks.push((
+                                constraint_name,
+                                format!("{}_{}_check", table_name, name),
+                                expr,
+                            )),
+                            _ => {}
+                        }
+                    }
+                    descs.push((name.clone(), desc));
                 }
-                let columns = columns
-                    .iter()
-                    .map(|col| (col.name.value.clone(), ColumnDesc::from(col)))
-                    .collect();
+                for constraint in constraints.iter() {
+                    match constraint {
+                        TableConstraint::Unique {
+                            name,
+                            columns: key_columns,
+                            is_primary,
+                        } => {
+                            let mut positions = vec![];
+                            for ident in key_columns {
+                                let idx = (columns.iter())
+                                    .position(|col| col.name.value == ident.value)
+                                    .ok_or_else(|| {
+                                        BindError::ColumnNotFound(ident.value.clone())
+                                    })?;
+                                if positions.contains(&idx) {
+                                    return Err(BindError::DuplicatedColumn(ident.value.clone()));
+                                }
+                                positions.push(idx);
+                            }
+                            let names = key_columns.iter().map(|c| &c.value).collect::<Vec<_>>();
+                            uniques.push((
+                                name.as_ref().map(|name| name.value.clone()),
+                                unique_name(table_name, &names, *is_primary),
+                                positions,
+                                *is_primary,
+                            ));
+                        }
+                        TableConstraint::Check { name, expr } => checks.push((
+                            name.as_ref().map(|name| name.value.clone()),
+                            format!("{}_check", table_name),
+                            expr,
+                        )),
+                        _ => {}
+                    }
+                }
+                let mut primary_keys = uniques.iter().filter(|(.., is_primary)| *is_primary);
+                if let Some((.., positions, _)) = primary_keys.next() {
+                    if primary_keys.next().is_some() {
+                        return Err(BindError::MultiplePrimaryKeys(table_name.into()));
+                    }
+                    for &idx in positions {
+                        descs[idx].1 = descs[idx].1.clone().with_primary();
+                    }
+                }
+                self.contexts.push(Context::default());
+                let bound_checks = self.bind_checks(table_name, &descs, &checks);
+                self.contexts.pop();
+
+                let mut names = HashSet::new();
+                let mut name_constraint = |name: &Option<String>, default_name: &String| {
+                    let name = match name {
+                        Some(name) => name.clone(),
+                        // a number is appended to the default name to make it unique
+                        None => (0..)
+                            .map(|i| match i {
+                                0 => default_name.clone(),
+                                i => format!("{}{}", default_name, i),
+                            })
+                            .find(|name| !names.contains(name))
+                            .unwrap(),
+                    };
+                    match names.insert(name.clone()) {
+                        true => Ok(name),
+                        false => Err(BindError::DuplicatedConstraint(name)),
+                    }
+                };
+                let uniques = (uniques.iter())
+                    .map(|(name, default_name, positions, is_primary)| {
+                        Ok(UniqueConstraint {
+                            name: name_constraint(name, default_name)?,
+                            column_ids: positions.iter().map(|&idx| idx as ColumnId).collect(),
+                            is_primary: *is_primary,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let checks = (checks.iter().zip(bound_checks?))
+                    .map(|((name, default_name, _), expr)| {
+                        Ok((name_constraint(name, default_name)?, expr))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
                 Ok(BoundCreateTable {
                     schema_id: schema.id(),
                     table_name: table_name.into(),
-                    columns,
+                    columns: descs,
+                    uniques,
+                    checks,
                 })
             }
             _ => panic!("mismatched statement type"),
         }
     }
+
+    /// Bind the default value of a column, which is casted to the type of the column.
+    pub(super) fn bind_default(
+        &mut self,
+        expr: &Expr,
+        column_name: &str,
+        ty: &DataType,
+    ) -> Result<BoundExpr, BindError> {
+        let expr = self.bind_expr(expr)?;
+        if expr.contains_subquery() {
+            return Err(BindError::SubqueryNotAllowed("DEFAULT"));
+        }
+        if expr.contains_agg() {
+            return Err(BindError::AggregateNotAllowed("DEFAULT"));
+        }
+        if expr.contains_window() {
+            return Err(BindError::WindowFunctionNotAllowed("DEFAULT"));
+        }
+        match expr.return_type() {
+            Some(from) if !is_assignable(&expr, &ty.kind()) => {
+                return Err(BindError::CastTypeMismatch(from.kind(), ty.kind()));
+            }
+            None if !ty.is_nullable() => {
+                return Err(BindError::NullValueInColumn(column_name.into()));
+            }
+            _ => {}
+        }
+        Ok(expr.cast_to(&ty.kind()))
+    }
+
+    /// Bind the check constraints of a new table with the given columns.
+    fn bind_checks(
+        &mut self,
+        table_name: &str,
+        columns: &[(String, ColumnDesc)],
+        checks: &[(Option<String>, String, &Expr)],
+    ) -> Result<Vec<BoundExpr>, BindError> {
+        let names = columns.iter().map(|(name, _)| name.clone()).collect();
+        let types = (columns.iter())
+            .map(|(_, desc)| desc.datatype().clone())
+            .collect();
+        self.add_relation(table_name.into(), names, types, None)?;
+        let mut bound_checks = vec![];
+        for (.., expr) in checks {
+            let expr = self.bind_condition(expr, "CHECK")?;
+            if expr.contains_subquery() {
+                return Err(BindError::SubqueryNotAllowed("CHECK"));
+            }
+            bound_checks.push(expr);
+        }
+        Ok(bound_checks)
+    }
+}
+
+/// Returns the default name of a `UNIQUE` or `PRIMARY KEY` constraint.
+fn unique_name(table_name: &str, columns: &[&String], is_primary: bool) -> String {
+    match is_primary {
+        true => format!("{}_pkey", table_name),
+        false => format!("{}_{}_key", table_name, columns.iter().join("_")),
+    }
 }
 
 impl From<&ColumnDef> for ColumnDesc {
@@ -59,14 +238,17 @@ impl From<&ColumnDef> for ColumnDesc {
             match opt.option {
                 ColumnOption::Null => is_nullable = true,
                 ColumnOption::NotNull => is_nullable = false,
-                ColumnOption::Unique { is_primary: v } => is_primary = v,
+                ColumnOption::Unique { is_primary: v } => is_primary |= v,
+                // bound by the binder
+                ColumnOption::Default(_) | ColumnOption::Check(_) => {}
                 _ => todo!("column options"),
             }
         }
-        ColumnDesc::new(
-            DataType::new(cdef.data_type.clone(), is_nullable),
-            is_primary,
-        )
+        let desc = ColumnDesc::new(DataType::new(cdef.data_type.clone(), is_nullable), false);
+        match is_primary {
+            true => desc.with_primary(),
+            false => desc,
+        }
     }
 }
 
@@ -98,6 +280,8 @@ mod tests {
                     ("v1".into(), DataTypeKind::Int(None).not_null().to_column()),
                     ("v2".into(), DataTypeKind::Int(None).nullable().to_column()),
                 ],
+                uniques: vec![],
+                checks: vec![],
             }
         );
 
@@ -0,0 +1,49 @@
+use super::*;
+use crate::parser::{ObjectType, Statement};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundDropTable {
+    /// The tables to drop, which are distinct.
+    pub table_ref_ids: Vec<TableRefId>,
+}
+
+impl Binder {
+    pub fn bind_drop_table(&mut self, stmt: &Statement) -> Result<BoundDropTable, BindError> {
+        let (if_exists, names) = match stmt {
+            Statement::Drop {
+                object_type: ObjectType::Table,
+                if_exists,
+                names,
+                ..
+            } => (*if_exists, names),
+            _ => panic!("mismatched statement type"),
+        };
+        let mut tables = vec![];
+        for name in names {
+            let (table_ref_id, description) = match self.resolve_table(name) {
+                Ok((schema, table)) => {
+                    let kind = match table.view_definition() {
+                        Some(_) => "materialized view",
+                        None => "table",
+                    };
+                    let description = format!("{} {}", kind, table.name());
+                    (TableRefId::new(schema.id(), table.id()), description)
+                }
+                // tables that do not exist are skipped with IF EXISTS
+                Err(_) if if_exists => continue,
+                Err(e) => return Err(e),
+            };
+            if !tables.iter().any(|(id, _)| *id == table_ref_id) {
+                tables.push((table_ref_id, description));
+            }
+        }
+        // materialized views are dropped as tables, so they may depend on the other tables
+        let table_ref_ids = tables.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        for (table_ref_id, description) in &tables {
+            self.check_dependent_views(*table_ref_id, &table_ref_ids, || {
+                format!("drop {}", description)
+            })?;
+        }
+        Ok(BoundDropTable { table_ref_ids })
+    }
+}
@@ -0,0 +1,101 @@
+use super::*;
+use crate::parser::{Expr, ObjectType, Statement};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundCreateIndex {
+    pub table_ref_id: TableRefId,
+    pub index_name: String,
+    pub column_id: ColumnId,
+    /// Whether the statement does nothing if the index exists.
+    pub if_not_exists: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundDropIndex {
+    /// The indexes to drop and the tables they are on, which are distinct.
+    pub indexes: Vec<(TableRefId, String)>,
+}
+
+impl Binder {
+    pub fn bind_create_index(&mut self, stmt: &Statement) -> Result<BoundCreateIndex, BindError> {
+        let (name, table_name, columns, unique, if_not_exists) = match stmt {
+            Statement::CreateIndex {
+                name,
+                table_name,
+                columns,
+                unique,
+                if_not_exists,
+            } => (name, table_name, columns, *unique, *if_not_exists),
+            _ => panic!("mismatched statement type"),
+        };
+        if unique {
+            return Err(BindError::NotSupported("unique index".into()));
+        }
+        let (schema, table) = self.resolve_table(table_name)?;
+        // the index is created in the schema of the table
+        let index_name = match split_name(name)? {
+            (Some(schema_name), _) if schema_name != schema.name() => {
+                return Err(BindError::InvalidTableName(name.0.clone()));
+            }
+            (_, index_name) => index_name,
+        };
+        if !if_not_exists && schema.get_index_by_name(index_name).is_some() {
+            return Err(BindError::DuplicatedIndex(index_name.into()));
+        }
+        let column_name = match columns.as_slice() {
+            [column] => match &column.expr {
+                Expr::Identifier(ident) => &ident.value,
+                expr => {
+                    let expr = format!("index on expression {}", expr);
+                    return Err(BindError::NotSupported(expr));
+                }
+            },
+            _ => return Err(BindError::NotSupported("index on multiple columns".into())),
+        };
+        let column = (table.get_column_by_name(column_name))
+            .ok_or_else(|| BindError::ColumnNotFound(column_name.clone()))?;
+        Ok(BoundCreateIndex {
+            table_ref_id: TableRefId::new(schema.id(), table.id()),
+            index_name: index_name.into(),
+            column_id: column.id(),
+            if_not_exists,
+        })
+    }
+
+    pub fn bind_drop_index(&mut self, stmt: &Statement) -> Result<BoundDropIndex, BindError> {
+        let (if_exists, names) = match stmt {
+            Statement::Drop {
+                object_type: ObjectType::Index,
+                if_exists,
+                names,
+                ..
+            } => (*if_exists, names),
+            _ => panic!("mismatched statement type"),
+        };
+        let mut indexes = vec![];
+        for name in names {
+            // an unqualified name is looked up in the schemas of the search path in order
+            let (schema_name, index_name) = split_name(name)?;
+            let schemas = match schema_name {
+                Some(schema_name) => vec![self.get_schema(schema_name)?],
+                None => (self.search_path.iter())
+                    .filter_map(|name| self.catalog.get_schema_by_name(name))
+                    .collect(),
+            };
+            let found = schemas.iter().find_map(|schema| {
+                let (table, _) = schema.get_index_by_name(index_name)?;
+                Some(TableRefId::new(schema.id(), table.id()))
+            });
+            let index = match found {
+                Some(table_ref_id) => (table_ref_id, index_name.to_string()),
+                // indexes that do not exist are skipped with IF EXISTS
+                None if if_exists => continue,
+                None => return Err(BindError::IndexNotFound(index_name.into())),
+            };
+            if !indexes.contains(&index) {
+                indexes.push(index);
+            }
+        }
+        Ok(BoundDropIndex { indexes })
+    }
+}
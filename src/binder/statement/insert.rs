@@ -5,15 +5,23 @@ use itertools::Itertools;
 
 use super::*;
 use crate::catalog::{ColumnCatalog, ColumnId, TableCatalog};
-use crate::parser::{SetExpr, Statement};
-use crate::types::{DataType, DataTypeKind};
+use crate::parser::{Expr, Query, SetExpr, Statement};
+use crate::types::{DataType, DataTypeKind, DataTypeKindExt, DataValue};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct BoundInsert {
     pub table_ref_id: TableRefId,
     pub column_ids: Vec<ColumnId>,
     pub column_types: Vec<DataType>,
-    pub values: Vec<Vec<BoundExpr>>,
+    pub source: BoundInsertSource,
+}
+
+/// The rows to insert.
+#[derive(Debug, PartialEq, Clone)]
+pub enum BoundInsertSource {
+    Values(Vec<Vec<BoundExpr>>),
+    /// A query whose select list has been casted to the types of the columns.
+    Query(BoundSelect),
 }
 
 impl Binder {
@@ -28,26 +36,33 @@ impl Binder {
             _ => panic!("mismatched statement type"),
         };
         let (table_ref_id, table, columns) = self.bind_table_columns(table_name, columns)?;
+        if table.view_definition().is_some() {
+            return Err(BindError::ChangeMaterializedView(table.name()));
+        }
         let column_ids = columns.iter().map(|col| col.id()).collect_vec();
         let column_types = columns.iter().map(|col| col.datatype()).collect_vec();
-
-        // Check columns after transforming.
-        let col_set: HashSet<ColumnId> = column_ids.iter().cloned().collect();
-        for (id, col) in table.all_columns() {
-            if !col_set.contains(&id) && !col.is_nullable() {
-                return Err(BindError::NotNullableColumn(col.name().into()));
-            }
-        }
+        check_omitted_columns(&table, &column_ids)?;
 
         let values = match source.body.as_ref() {
-            SetExpr::Select(_) => todo!("handle 'insert into .. select .. from ..' case."),
             SetExpr::Values(values) => &values.0,
-            _ => todo!("handle insert ???"),
+            // Handle 'insert into .. select .. from ..' case.
+            _ => {
+                let query = self.bind_insert_query(source, &columns)?;
+                // The columns after the select list are filled with their default values.
+                let len = query.select_list.len();
+                check_omitted_columns(&table, &column_ids[..len])?;
+                return Ok(BoundInsert {
+                    table_ref_id,
+                    column_ids: column_ids[..len].to_vec(),
+                    column_types: column_types[..len].to_vec(),
+                    source: BoundInsertSource::Query(query),
+                });
+            }
         };
 
         // Handle 'insert into .. values ..' case.
 
-        // Check inserted values, we only support inserting values now.
+        // Check inserted values.
         let mut bound_values = Vec::with_capacity(values.len());
         for row in values.iter() {
             if row.len() > column_ids.len() {
@@ -58,23 +73,22 @@ impl Binder {
             }
             let mut bound_row = Vec::with_capacity(row.len());
             for (idx, expr) in row.iter().enumerate() {
-                // Bind expression
-                let expr = self.bind_expr(expr)?;
+                // `DEFAULT` is the default value of the column, or null if it has none.
+                let expr = match expr {
+                    Expr::Identifier(ident)
+                        if ident.quote_style.is_none()
+                            && ident.value.eq_ignore_ascii_case("default") =>
+                    {
+                        let default = columns[idx].desc().default().cloned();
+                        default.unwrap_or(BoundExpr::Constant(DataValue::Null))
+                    }
+                    expr => self.bind_expr(expr)?,
+                };
 
+                let column_kind = column_types[idx].kind();
                 if let Some(data_type) = &expr.return_type() {
-                    // TODO: support valid type cast
-                    // For example:
-                    //   CREATE TABLE t (a FLOAT, b FLOAT);
-                    //   INSERT INTO VALUES (1, 1);
-                    // 1 should be casted to float.
-                    let left_kind = data_type.kind();
-                    let right_kind = column_types[idx].kind();
-                    match (&left_kind, &right_kind) {
-                        _ if left_kind == right_kind => {}
-                        // For char types, no need to cast
-                        (DataTypeKind::Char(_), DataTypeKind::Varchar(_)) => {}
-                        (DataTypeKind::Varchar(_), DataTypeKind::Char(_)) => {}
-                        _ => todo!("type cast: {} -> {}", left_kind, right_kind),
+                    if !is_assignable(&expr, &column_kind) {
+                        return Err(BindError::CastTypeMismatch(data_type.kind(), column_kind));
                     }
                 } else {
                     // If the data value is null, the column must be nullable.
@@ -82,7 +96,7 @@ impl Binder {
                         return Err(BindError::NullValueInColumn(columns[idx].name().into()));
                     }
                 }
-                bound_row.push(expr);
+                bound_row.push(expr.cast_to(&column_kind));
             }
             bound_values.push(bound_row);
         }
@@ -91,23 +105,46 @@ impl Binder {
             table_ref_id,
             column_ids,
             column_types,
-            values: bound_values,
+            source: BoundInsertSource::Values(bound_values),
         })
     }
 
+    /// Bind the query of an insert, and cast its select list to the types of `columns`.
+    fn bind_insert_query(
+        &mut self,
+        query: &Query,
+        columns: &[ColumnCatalog],
+    ) -> Result<BoundSelect, BindError> {
+        let mut select = self.bind_select(query)?;
+        if select.select_list.len() > columns.len() {
+            return Err(BindError::TupleLengthMismatch {
+                expected: columns.len(),
+                actual: select.select_list.len(),
+            });
+        }
+        let select_list = std::mem::take(&mut select.select_list);
+        for (expr, column) in select_list.into_iter().zip(columns) {
+            let column_kind = column.datatype().kind();
+            match expr.return_type() {
+                Some(data_type) if !is_assignable(&expr, &column_kind) => {
+                    return Err(BindError::CastTypeMismatch(data_type.kind(), column_kind));
+                }
+                None if !column.is_nullable() => {
+                    return Err(BindError::NullValueInColumn(column.name().into()));
+                }
+                _ => {}
+            }
+            select.select_list.push(expr.cast_to(&column_kind));
+        }
+        Ok(select)
+    }
+
     pub fn bind_table_columns(
         &mut self,
         table_name: &ObjectName,
         columns: &[Ident],
     ) -> Result<(TableRefId, Arc<TableCatalog>, Vec<ColumnCatalog>), BindError> {
-        let (schema_name, table_name) = split_name(table_name)?;
-        let schema = self
-            .catalog
-            .get_schema_by_name(schema_name)
-            .ok_or_else(|| BindError::SchemaNotFound(schema_name.into()))?;
-        let table = schema
-            .get_table_by_name(table_name)
-            .ok_or_else(|| BindError::TableNotFound(table_name.into()))?;
+        let (schema, table) = self.resolve_table(table_name)?;
         let table_ref_id = TableRefId::new(schema.id(), table.id());
 
         let columns = if columns.is_empty() {
@@ -127,3 +164,31 @@ impl Binder {
         Ok((table_ref_id, table, columns))
     }
 }
+
+/// Check that the columns of the table that are not in `column_ids` can be filled with null
+/// or their default values.
+fn check_omitted_columns(table: &TableCatalog, column_ids: &[ColumnId]) -> Result<(), BindError> {
+    let col_set: HashSet<ColumnId> = column_ids.iter().cloned().collect();
+    for (id, col) in table.all_columns() {
+        if !col_set.contains(&id) && !col.is_nullable() && col.desc().default().is_none() {
+            return Err(BindError::NotNullableColumn(col.name().into()));
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if the value of `expr` can be assigned to a column of `column_kind`.
+///
+/// Numbers can be assigned to a column of any numeric type, dates and timestamps to each
+/// other, and any value to a string column. Nulls and string literals can be assigned to a
+/// column of any type, and the literals are checked when they are casted.
+pub(super) fn is_assignable(expr: &BoundExpr, column_kind: &DataTypeKind) -> bool {
+    let Some(kind) = expr.return_type().map(|ty| ty.kind()) else {
+        return true;
+    };
+    &kind == column_kind
+        || (kind.is_number() && column_kind.is_number())
+        || (kind.is_temporal() && can_cast(&kind, column_kind))
+        || column_kind.is_string()
+        || (kind.is_string() && matches!(expr, BoundExpr::Constant(_)))
+}
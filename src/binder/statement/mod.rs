@@ -1,9 +1,22 @@
 use super::*;
 
+mod alter_table;
 mod create_table;
-mod select;
+mod drop_table;
+mod index;
 mod insert;
+mod schema;
+mod select;
+mod set;
+mod truncate;
+mod view;
 
+pub use self::alter_table::*;
 pub use self::create_table::*;
-pub use self::select::*;
+pub use self::drop_table::*;
+pub use self::index::*;
 pub use self::insert::*;
+pub use self::schema::*;
+pub use self::select::*;
+pub use self::truncate::*;
+pub use self::view::*;
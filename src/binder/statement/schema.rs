@@ -0,0 +1,90 @@
+use super::*;
+use crate::parser::{ObjectType, SchemaName, Statement};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundCreateSchema {
+    pub schema_name: String,
+    /// Whether the statement does nothing if the schema exists.
+    pub if_not_exists: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundDropSchema {
+    /// The schemas to drop, which are distinct.
+    pub schema_ids: Vec<SchemaId>,
+    /// Whether the tables in the schemas are dropped. Otherwise the schemas must be empty.
+    pub cascade: bool,
+}
+
+impl Binder {
+    pub fn bind_create_schema(&mut self, stmt: &Statement) -> Result<BoundCreateSchema, BindError> {
+        let (schema_name, if_not_exists) = match stmt {
+            Statement::CreateSchema {
+                schema_name,
+                if_not_exists,
+            } => (schema_name, *if_not_exists),
+            _ => panic!("mismatched statement type"),
+        };
+        let schema_name = match schema_name {
+            SchemaName::Simple(ObjectName(name)) if name.len() == 1 => name[0].value.clone(),
+            name => return Err(BindError::InvalidSchemaName(name.to_string())),
+        };
+        if !if_not_exists && self.catalog.get_schema_by_name(&schema_name).is_some() {
+            return Err(BindError::DuplicatedSchema(schema_name));
+        }
+        Ok(BoundCreateSchema {
+            schema_name,
+            if_not_exists,
+        })
+    }
+
+    pub fn bind_drop_schema(&mut self, stmt: &Statement) -> Result<BoundDropSchema, BindError> {
+        let (if_exists, names, cascade) = match stmt {
+            Statement::Drop {
+                object_type: ObjectType::Schema,
+                if_exists,
+                names,
+                cascade,
+                ..
+            } => (*if_exists, names, *cascade),
+            _ => panic!("mismatched statement type"),
+        };
+        let mut schema_ids = vec![];
+        for name in names {
+            let schema = match name.0.as_slice() {
+                [name] => self.catalog.get_schema_by_name(&name.value),
+                _ => return Err(BindError::InvalidSchemaName(name.to_string())),
+            };
+            let schema = match schema {
+                Some(schema) => schema,
+                // schemas that do not exist are skipped with IF EXISTS
+                None if if_exists => continue,
+                None => return Err(BindError::SchemaNotFound(name.to_string())),
+            };
+            let is_empty = schema.all_tables().is_empty() && schema.all_views().is_empty();
+            if !cascade && !is_empty {
+                return Err(BindError::SchemaNotEmpty(schema.name()));
+            }
+            if !schema_ids.contains(&schema.id()) {
+                schema_ids.push(schema.id());
+            }
+        }
+        // the views in other schemas must not depend on the tables and views in the schemas
+        let mut dropped = vec![];
+        for &schema_id in &schema_ids {
+            let schema = self.catalog.get_schema(schema_id).unwrap();
+            let ids = (schema.all_tables().into_keys()).chain(schema.all_views().into_keys());
+            dropped.extend(ids.map(|id| TableRefId::new(schema_id, id)));
+        }
+        for &table_ref_id in &dropped {
+            self.check_dependent_views(table_ref_id, &dropped, || {
+                let schema = self.catalog.get_schema(table_ref_id.schema_id).unwrap();
+                format!("drop schema {}", schema.name())
+            })?;
+        }
+        Ok(BoundDropSchema {
+            schema_ids,
+            cascade,
+        })
+    }
+}
@@ -1,60 +1,336 @@
 use super::*;
+use crate::binder::BoundCte;
 use crate::binder::BoundTableRef;
-use crate::parser::{Query, SelectItem, SetExpr};
+use crate::parser::{Expr, Query, Select, SelectItem, SetExpr, Value};
+use crate::types::DataValue;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct BoundSelect {
+    /// The CTEs defined in the WITH clause.
+    pub ctes: Vec<BoundCte>,
     pub select_list: Vec<BoundExpr>,
-    pub from_list: Vec<BoundTableRef>,
+    /// The names of the output columns.
+    pub aliases: Vec<String>,
+    pub from_table: Option<BoundTableRef>,
+    pub where_clause: Option<BoundExpr>,
+    pub group_by: Vec<BoundExpr>,
+    pub having: Option<BoundExpr>,
 }
 
 impl Binder {
     pub fn bind_select(&mut self, query: &Query) -> Result<BoundSelect, BindError> {
-        let select = match query.body.as_ref() {
-            SetExpr::Select(select) => &**select,
-            _ => todo!("not select"),
-        };
+        self.contexts.push(Context::default());
+        let result = self.bind_select_in_context(query);
+        self.contexts.pop();
+        result
+    }
 
-        let mut from_list = vec![];
-        assert!(select.from.len() <= 1, "multiple tables are not supported");
-        for table_with_join in select.from.iter() {
-            let table_ref = self.bind_table_with_joins(table_with_join)?;
-            from_list.push(table_ref);
-        }
+    /// Bind the body of a query in a new query block.
+    pub fn bind_set_expr(&mut self, body: &SetExpr) -> Result<BoundSelect, BindError> {
+        self.contexts.push(Context::default());
+        let result = self.bind_set_expr_in_context(body);
+        self.contexts.pop();
+        result
+    }
 
-        assert!(select.selection.is_none(), "WHERE clause is not supported");
+    fn bind_select_in_context(&mut self, query: &Query) -> Result<BoundSelect, BindError> {
+        let ctes = match &query.with {
+            Some(with) => self.bind_with(with)?,
+            None => vec![],
+        };
         assert!(
             query.order_by.is_empty(),
             "ORDER BY clause is not supported"
         );
         assert!(query.limit.is_none(), "LIMIT clause is not supported");
         assert!(query.offset.is_none(), "OFFSET clause is not supported");
-        assert!(
-            select.group_by.is_empty(),
-            "GROUP BY clause is not supported"
-        );
+        let mut select = self.bind_set_expr_in_context(&query.body)?;
+        select.ctes = ctes;
+        Ok(select)
+    }
+
+    fn bind_set_expr_in_context(&mut self, body: &SetExpr) -> Result<BoundSelect, BindError> {
+        match body {
+            SetExpr::Select(select) => self.bind_select_body(select),
+            SetExpr::Query(query) => self.bind_select_in_context(query),
+            SetExpr::SetOperation {
+                op,
+                set_quantifier,
+                left,
+                right,
+            } => self.bind_set_operation(op, set_quantifier, left, right),
+            SetExpr::Values(_) => Err(BindError::NotSupported("VALUES in a query".into())),
+            body => Err(BindError::NotSupported(format!("query body: {}", body))),
+        }
+    }
+
+    fn bind_select_body(&mut self, select: &Select) -> Result<BoundSelect, BindError> {
+        // Multiple tables in the FROM clause are cross joined.
+        let mut from_table: Option<BoundTableRef> = None;
+        for table_with_join in select.from.iter() {
+            let table_ref = self.bind_table_with_joins(table_with_join)?;
+            from_table = Some(match from_table {
+                Some(left) => BoundTableRef::cross_join(left, table_ref),
+                None => table_ref,
+            });
+        }
+
+        let where_clause = match &select.selection {
+            Some(expr) => Some(self.bind_condition(expr, "WHERE")?),
+            None => None,
+        };
         assert!(!select.distinct, "DISTINCT is not supported");
 
         // Bind the select list.
         let mut select_list = vec![];
+        let mut aliases = vec![];
         for item in select.projection.iter() {
             match item {
                 SelectItem::UnnamedExpr(expr) => {
                     select_list.push(self.bind_expr(expr)?);
+                    aliases.push(match expr {
+                        Expr::Identifier(ident) => ident.value.clone(),
+                        Expr::CompoundIdentifier(idents) => idents.last().unwrap().value.clone(),
+                        _ => "?column?".into(),
+                    });
                 }
-                SelectItem::ExprWithAlias { expr, .. } => {
+                SelectItem::ExprWithAlias { expr, alias } => {
                     select_list.push(self.bind_expr(expr)?);
+                    aliases.push(alias.value.clone());
                 }
                 SelectItem::Wildcard => {
-                    select_list.extend(self.bind_all_column_refs()?);
+                    for (name, expr) in self.bind_all_column_refs()? {
+                        aliases.push(name);
+                        select_list.push(expr);
+                    }
+                }
+                SelectItem::QualifiedWildcard(name) => {
+                    let table_name = &name.0.last().unwrap().value;
+                    for (name, expr) in self.bind_qualified_column_refs(table_name)? {
+                        aliases.push(name);
+                        select_list.push(expr);
+                    }
                 }
-                _ => todo!("not supported select item: {:?}", item),
+            }
+        }
+
+        let group_by = self.bind_group_by(&select.group_by, &select_list, &aliases)?;
+        let having = match &select.having {
+            Some(expr) => {
+                let expr = self.bind_expr(expr)?;
+                Some(self.check_condition(expr, "HAVING")?)
+            }
+            None => None,
+        };
+        // Outside of aggregate functions, an aggregated query can only reference the columns
+        // in GROUP BY.
+        let aggregated = !group_by.is_empty()
+            || having.is_some()
+            || select_list.iter().any(|e| e.contains_agg());
+        if aggregated {
+            for expr in select_list.iter().chain(&having) {
+                self.check_grouped(expr, &group_by)?;
             }
         }
 
         Ok(BoundSelect {
+            ctes: vec![],
             select_list,
-            from_list,
+            aliases,
+            from_table,
+            where_clause,
+            group_by,
+            having,
         })
     }
+
+    /// Bind the GROUP BY clause.
+    ///
+    /// As in PostgreSQL, an integer constant refers to an item of the select list by its
+    /// position, and a name that is not an input column refers to an item by its alias.
+    fn bind_group_by(
+        &mut self,
+        exprs: &[Expr],
+        select_list: &[BoundExpr],
+        aliases: &[String],
+    ) -> Result<Vec<BoundExpr>, BindError> {
+        let mut group_by = vec![];
+        for expr in exprs {
+            let bound = match expr {
+                Expr::Value(Value::Number(n, _)) => {
+                    let pos = n
+                        .parse::<i64>()
+                        .map_err(|_| BindError::NonIntegerGroupByConstant)?;
+                    if pos < 1 || pos as usize > select_list.len() {
+                        return Err(BindError::GroupByPositionOutOfRange(n.clone()));
+                    }
+                    select_list[pos as usize - 1].clone()
+                }
+                Expr::Value(_) => return Err(BindError::NonIntegerGroupByConstant),
+                Expr::Identifier(ident) => match self.bind_expr(expr) {
+                    Err(BindError::ColumnNotFound(name)) => {
+                        let mut items = (aliases.iter().zip(select_list))
+                            .filter(|(alias, _)| **alias == ident.value)
+                            .map(|(_, item)| item);
+                        let Some(item) = items.next() else {
+                            return Err(BindError::ColumnNotFound(name));
+                        };
+                        if items.any(|other| other != item) {
+                            return Err(BindError::AmbiguousColumnName(name));
+                        }
+                        item.clone()
+                    }
+                    result => result?,
+                },
+                _ => self.bind_expr(expr)?,
+            };
+            if bound.contains_agg() {
+                return Err(BindError::AggregateNotAllowed("GROUP BY"));
+            }
+            if bound.contains_window() {
+                return Err(BindError::WindowFunctionNotAllowed("GROUP BY"));
+            }
+            group_by.push(bound.cast_if_null(DataTypeKind::Int(None)));
+        }
+        Ok(group_by)
+    }
+
+    /// Check that the columns of the query block are only referenced in aggregate functions
+    /// or in expressions of GROUP BY.
+    fn check_grouped(&mut self, expr: &BoundExpr, group_by: &[BoundExpr]) -> Result<(), BindError> {
+        if group_by.contains(expr) {
+            return Ok(());
+        }
+        match expr {
+            BoundExpr::AggCall(_) => Ok(()),
+            BoundExpr::ColumnRef(c) if c.depth == 0 => {
+                Err(BindError::ColumnNotInGroupBy(self.column_name(c.index)))
+            }
+            _ => {
+                for child in expr.children() {
+                    self.check_grouped(child, group_by)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Bind a boolean expression used as a filter condition.
+    pub fn bind_condition(
+        &mut self,
+        expr: &Expr,
+        clause: &'static str,
+    ) -> Result<BoundExpr, BindError> {
+        let expr = self.bind_expr(expr)?;
+        if expr.contains_agg() {
+            return Err(BindError::AggregateNotAllowed(clause));
+        }
+        self.check_condition(expr, clause)
+    }
+
+    fn check_condition(
+        &mut self,
+        expr: BoundExpr,
+        clause: &'static str,
+    ) -> Result<BoundExpr, BindError> {
+        if expr.contains_window() {
+            return Err(BindError::WindowFunctionNotAllowed(clause));
+        }
+        match expr.return_type().map(|ty| ty.kind()) {
+            None => Ok(expr.cast_if_null(DataTypeKind::Boolean)),
+            Some(DataTypeKind::Boolean) => Ok(expr),
+            Some(kind) => Err(BindError::NotBoolean(clause, kind)),
+        }
+    }
+}
+
+impl BoundSelect {
+    /// Gives untyped `NULL`s in the select list the type `kind`.
+    pub fn cast_null_columns(&mut self, kind: &DataTypeKind) {
+        for expr in &mut self.select_list {
+            *expr = std::mem::replace(expr, BoundExpr::Constant(DataValue::Null))
+                .cast_if_null(kind.clone());
+        }
+    }
+
+    /// Returns the types of the output columns. Untyped `NULL`s are typed as `VARCHAR`.
+    pub fn column_types(&mut self) -> Vec<DataType> {
+        self.cast_null_columns(&DataTypeKind::Varchar(None));
+        (self.select_list.iter())
+            .map(|expr| expr.return_type().unwrap())
+            .collect()
+    }
+
+    /// Returns the maximum depth of references to columns outside this query block,
+    /// or 0 if the query is not correlated.
+    pub fn max_outer_depth(&self) -> usize {
+        self.outer_depth(0)
+    }
+
+    /// Returns true if the query computes aggregations, i.e. it has a GROUP BY or HAVING
+    /// clause, or aggregate functions in the select list.
+    pub fn is_aggregated(&self) -> bool {
+        !self.group_by.is_empty()
+            || self.having.is_some()
+            || self.select_list.iter().any(|e| e.contains_agg())
+    }
+
+    fn outer_depth(&self, level: usize) -> usize {
+        let mut depth = self.from_table.as_ref().map_or(0, |t| t.outer_depth(level));
+        for cte in self.ctes.iter() {
+            depth = depth.max(cte.query.outer_depth(level + 1));
+            if let Some(query) = &cte.recursive {
+                depth = depth.max(query.outer_depth(level + 1));
+            }
+        }
+        let exprs = (self.select_list.iter())
+            .chain(&self.where_clause)
+            .chain(&self.group_by)
+            .chain(&self.having);
+        for expr in exprs {
+            depth = depth.max(expr.outer_depth(level));
+        }
+        depth
+    }
+}
+
+impl BoundTableRef {
+    /// Returns the maximum depth of references to columns outside the current query block.
+    pub fn max_outer_depth(&self) -> usize {
+        self.outer_depth(0)
+    }
+
+    fn outer_depth(&self, level: usize) -> usize {
+        match self {
+            BoundTableRef::BaseTable { .. } | BoundTableRef::Cte { .. } => 0,
+            BoundTableRef::Subquery(query) => query.outer_depth(level + 1),
+            BoundTableRef::Join(join) => join
+                .left
+                .outer_depth(level)
+                .max(join.right.outer_depth(level))
+                .max(join.condition.outer_depth(level)),
+            BoundTableRef::SetOperation(op) => op
+                .left
+                .outer_depth(level + 1)
+                .max(op.right.outer_depth(level + 1)),
+        }
+    }
+}
+
+impl BoundExpr {
+    /// Returns the maximum depth of references to columns outside the current query block,
+    /// including the ones in subqueries.
+    pub fn max_outer_depth(&self) -> usize {
+        self.outer_depth(0)
+    }
+
+    fn outer_depth(&self, level: usize) -> usize {
+        let mut depth = 0;
+        self.visit(&mut |e| match e {
+            BoundExpr::ColumnRef(c) if c.depth > level => depth = depth.max(c.depth - level),
+            BoundExpr::Subquery(s) => depth = depth.max(s.query.outer_depth(level + 1)),
+            _ => {}
+        });
+        depth
+    }
 }
@@ -0,0 +1,39 @@
+use itertools::Itertools;
+
+use super::*;
+use crate::parser::{Expr, Value};
+
+impl Binder {
+    /// Bind `SET variable = value`. Only `search_path` is supported.
+    pub fn bind_set_variable(
+        &mut self,
+        variable: &ObjectName,
+        value: &[Expr],
+    ) -> Result<BoundStatement, BindError> {
+        let name = variable.to_string();
+        if !name.eq_ignore_ascii_case("search_path") {
+            return Err(BindError::UnknownVariable(name));
+        }
+        let mut schemas = vec![];
+        for expr in value {
+            schemas.push(match expr {
+                Expr::Identifier(ident) => ident.value.clone(),
+                Expr::Value(Value::SingleQuotedString(s)) => s.clone(),
+                _ => return Err(BindError::InvalidVariableValue(name)),
+            });
+        }
+        Ok(BoundStatement::SetSearchPath(schemas))
+    }
+
+    /// Bind `SHOW variable`. Only `search_path` is supported.
+    pub fn bind_show_variable(&mut self, variable: &[Ident]) -> Result<BoundStatement, BindError> {
+        match variable {
+            [name] if name.value.eq_ignore_ascii_case("search_path") => {
+                Ok(BoundStatement::ShowSearchPath)
+            }
+            _ => Err(BindError::UnknownVariable(
+                variable.iter().map(|ident| ident.value.as_str()).join(" "),
+            )),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use super::*;
+use crate::parser::{truncated_tables, Statement};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundTruncate {
+    /// The tables to empty, which are distinct.
+    pub table_ref_ids: Vec<TableRefId>,
+}
+
+impl Binder {
+    pub fn bind_truncate(&mut self, stmt: &Statement) -> Result<BoundTruncate, BindError> {
+        let names = truncated_tables(stmt).expect("mismatched statement type");
+        let mut table_ref_ids = vec![];
+        for name in &names {
+            let (schema, table) = self.resolve_table(name)?;
+            if table.view_definition().is_some() {
+                return Err(BindError::ChangeMaterializedView(table.name()));
+            }
+            let table_ref_id = TableRefId::new(schema.id(), table.id());
+            if !table_ref_ids.contains(&table_ref_id) {
+                table_ref_ids.push(table_ref_id);
+            }
+        }
+        Ok(BoundTruncate { table_ref_ids })
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashSet;
+
+use super::*;
+use crate::parser::{refreshed_materialized_view, ObjectType, Query, Statement};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundCreateView {
+    pub schema_id: SchemaId,
+    pub view_name: String,
+    pub definition: ViewDefinition,
+    /// Whether the view replaces the existing view of the name.
+    pub or_replace: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundCreateMaterializedView {
+    pub schema_id: SchemaId,
+    pub view_name: String,
+    pub definition: ViewDefinition,
+    /// The query whose result is stored in the view.
+    pub query: BoundSelect,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundRefreshMaterializedView {
+    pub table_ref_id: TableRefId,
+    /// The query whose result replaces the rows of the view.
+    pub query: BoundSelect,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundDropView {
+    /// The views to drop, which are distinct.
+    pub view_ref_ids: Vec<TableRefId>,
+}
+
+impl Binder {
+    pub fn bind_create_view(&mut self, stmt: &Statement) -> Result<BoundCreateView, BindError> {
+        let (or_replace, name, columns, query) = match stmt {
+            Statement::CreateView {
+                materialized: false,
+                or_replace,
+                name,
+                columns,
+                query,
+                ..
+            } => (*or_replace, name, columns, query),
+            _ => panic!("mismatched statement type"),
+        };
+        let (schema, view_name) = self.resolve_new_table(name)?;
+        if schema.get_table_by_name(view_name).is_some() {
+            return Err(BindError::DuplicatedTable(view_name.into()));
+        }
+        let old_view = schema.get_view_by_name(view_name);
+        if old_view.is_some() && !or_replace {
+            return Err(BindError::DuplicatedView(view_name.into()));
+        }
+        let search_path = self.search_path.clone();
+        let (select, dependencies) = self.bind_view_query(query, search_path.clone())?;
+        let columns = view_columns(&select, columns)?;
+
+        if let Some(old_view) = old_view {
+            // the views that depend on the view must still be valid, so columns can only be
+            // added to it
+            let old_columns = &old_view.definition().columns;
+            let unchanged = old_columns.len() <= columns.len()
+                && (old_columns.iter().zip(&columns))
+                    .all(|((n1, t1), (n2, t2))| n1 == n2 && t1.kind() == t2.kind());
+            if !unchanged {
+                return Err(BindError::ViewColumnsChanged(view_name.into()));
+            }
+            let view_ref_id = TableRefId::new(schema.id(), old_view.id());
+            if self.depends_on(&dependencies, view_ref_id) {
+                return Err(BindError::RecursiveView(view_name.into()));
+            }
+        }
+        Ok(BoundCreateView {
+            schema_id: schema.id(),
+            view_name: view_name.into(),
+            definition: ViewDefinition {
+                columns,
+                query: (**query).clone(),
+                search_path,
+                dependencies,
+            },
+            or_replace,
+        })
+    }
+
+    pub fn bind_create_materialized_view(
+        &mut self,
+        stmt: &Statement,
+    ) -> Result<BoundCreateMaterializedView, BindError> {
+        let (name, columns, query) = match stmt {
+            Statement::CreateView {
+                materialized: true,
+                or_replace: true,
+                ..
+            } => {
+                let feature = "create or replace materialized view";
+                return Err(BindError::NotSupported(feature.into()));
+            }
+            Statement::CreateView {
+                materialized: true,
+                name,
+                columns,
+                query,
+                ..
+            } => (name, columns, query),
+            _ => panic!("mismatched statement type"),
+        };
+        let (schema, view_name) = self.resolve_new_table(name)?;
+        if schema.get_table_by_name(view_name).is_some() {
+            return Err(BindError::DuplicatedTable(view_name.into()));
+        }
+        if schema.get_view_by_name(view_name).is_some() {
+            return Err(BindError::DuplicatedView(view_name.into()));
+        }
+        let search_path = self.search_path.clone();
+        let (select, dependencies) = self.bind_view_query(query, search_path.clone())?;
+        let columns = view_columns(&select, columns)?;
+        Ok(BoundCreateMaterializedView {
+            schema_id: schema.id(),
+            view_name: view_name.into(),
+            definition: ViewDefinition {
+                columns,
+                query: (**query).clone(),
+                search_path,
+                dependencies,
+            },
+            query: select,
+        })
+    }
+
+    pub fn bind_refresh_materialized_view(
+        &mut self,
+        stmt: &Statement,
+    ) -> Result<BoundRefreshMaterializedView, BindError> {
+        let name = refreshed_materialized_view(stmt).expect("mismatched statement type");
+        let (schema, table) = self.resolve_table(name)?;
+        let Some(definition) = table.view_definition() else {
+            return Err(BindError::NotMaterializedView(table.name()));
+        };
+        let query = self.bind_view_definition(&table.name(), &definition)?;
+        Ok(BoundRefreshMaterializedView {
+            table_ref_id: TableRefId::new(schema.id(), table.id()),
+            query,
+        })
+    }
+
+    pub fn bind_drop_view(&mut self, stmt: &Statement) -> Result<BoundDropView, BindError> {
+        let (if_exists, names) = match stmt {
+            Statement::Drop {
+                object_type: ObjectType::View,
+                if_exists,
+                names,
+                cascade: false,
+                ..
+            } => (*if_exists, names),
+            Statement::Drop { cascade: true, .. } => {
+                return Err(BindError::NotSupported("drop view cascade".into()))
+            }
+            _ => panic!("mismatched statement type"),
+        };
+        let mut views = vec![];
+        for name in names {
+            let (schema_id, view) = match self.resolve_view(name)? {
+                Some(view) => view,
+                // views that do not exist are skipped with IF EXISTS
+                None if if_exists => continue,
+                None => return Err(BindError::ViewNotFound(name.to_string())),
+            };
+            let view_ref_id = TableRefId::new(schema_id, view.id());
+            if !views.iter().any(|(id, _)| *id == view_ref_id) {
+                views.push((view_ref_id, view.name().to_string()));
+            }
+        }
+        let view_ref_ids = views.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        for (view_ref_id, name) in &views {
+            self.check_dependent_views(*view_ref_id, &view_ref_ids, || {
+                format!("drop view {}", name)
+            })?;
+        }
+        Ok(BoundDropView { view_ref_ids })
+    }
+
+    /// Bind the query of a view in a scope of its own, in which unqualified table names are
+    /// looked up in `search_path`. Untyped `NULL`s in the select list are typed as `VARCHAR`.
+    /// Returns the bound query and the tables and views that it references directly.
+    pub fn bind_view_query(
+        &mut self,
+        query: &Query,
+        search_path: Vec<String>,
+    ) -> Result<(BoundSelect, Vec<TableRefId>), BindError> {
+        let contexts = std::mem::take(&mut self.contexts);
+        let search_path = std::mem::replace(&mut self.search_path, search_path);
+        let dependencies = std::mem::take(&mut self.dependencies);
+        let result = self.bind_select(query);
+        self.contexts = contexts;
+        self.search_path = search_path;
+        let mut query_dependencies = std::mem::replace(&mut self.dependencies, dependencies);
+        let mut set = HashSet::new();
+        query_dependencies.retain(|id| set.insert(*id));
+        let mut query = result?;
+        query.cast_null_columns(&DataTypeKind::Varchar(None));
+        Ok((query, query_dependencies))
+    }
+
+    /// Bind the query of a view or a materialized view again, and check that it still returns
+    /// the columns of the view.
+    pub fn bind_view_definition(
+        &mut self,
+        view_name: &str,
+        definition: &ViewDefinition,
+    ) -> Result<BoundSelect, BindError> {
+        let search_path = definition.search_path.clone();
+        let (query, _) = self.bind_view_query(&definition.query, search_path)?;
+        let matched = query.select_list.len() == definition.columns.len()
+            && (query.select_list.iter().zip(&definition.columns))
+                .all(|(expr, (_, ty))| expr.return_type().map(|t| t.kind()) == Some(ty.kind()));
+        if !matched {
+            return Err(BindError::InvalidView(view_name.into()));
+        }
+        Ok(query)
+    }
+
+    /// Returns true if any of the tables and views, or the views they depend on, is `target`.
+    fn depends_on(&self, dependencies: &[TableRefId], target: TableRefId) -> bool {
+        dependencies.iter().any(|&id| {
+            id == target
+                || (self.catalog.get_view(id)).map_or(false, |view| {
+                    self.depends_on(&view.definition().dependencies, target)
+                })
+        })
+    }
+}
+
+/// Returns the names and types of the columns of a view. The columns are named by the column
+/// list, or by the select list after it.
+fn view_columns(
+    select: &BoundSelect,
+    columns: &[Ident],
+) -> Result<Vec<(String, DataType)>, BindError> {
+    let mut names = select.aliases.clone();
+    if columns.len() > names.len() {
+        return Err(BindError::TupleLengthMismatch {
+            expected: names.len(),
+            actual: columns.len(),
+        });
+    }
+    for (name, ident) in names.iter_mut().zip(columns) {
+        *name = ident.value.clone();
+    }
+    let mut set = HashSet::new();
+    for name in &names {
+        if !set.insert(name) {
+            return Err(BindError::DuplicatedColumn(name.clone()));
+        }
+    }
+    let types = select.select_list.iter().map(|e| e.return_type().unwrap());
+    Ok(names.into_iter().zip(types).collect())
+}
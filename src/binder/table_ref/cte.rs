@@ -0,0 +1,174 @@
+use super::*;
+use crate::parser::{Cte, SetExpr, SetOperator, SetQuantifier, With};
+
+/// A common table expression defined in a WITH clause.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundCte {
+    pub id: usize,
+    pub query: Box<BoundSelect>,
+    /// The recursive term of a `WITH RECURSIVE` query. It is evaluated repeatedly on the
+    /// rows produced by the previous iteration until no new rows are produced.
+    pub recursive: Option<Box<BoundSelect>>,
+    /// Whether to keep duplicate rows produced by the recursive term (`UNION ALL`).
+    pub union_all: bool,
+    pub column_types: Vec<DataType>,
+}
+
+impl Binder {
+    /// Bind the CTEs in the WITH clause and add them to the current context.
+    pub fn bind_with(&mut self, with: &With) -> Result<Vec<BoundCte>, BindError> {
+        let mut ctes = vec![];
+        for cte in with.cte_tables.iter() {
+            let name = &cte.alias.name.value;
+            if self.context().ctes.iter().any(|c| &c.name == name) {
+                return Err(BindError::DuplicatedAlias(name.clone()));
+            }
+            let bound = match cte.query.body.as_ref() {
+                SetExpr::SetOperation {
+                    op: SetOperator::Union,
+                    set_quantifier,
+                    left,
+                    right,
+                } if with.recursive => {
+                    let union_all = matches!(set_quantifier, SetQuantifier::All);
+                    self.bind_recursive_cte(cte, left, right, union_all)?
+                }
+                _ => {
+                    let query = self.bind_select(&cte.query)?;
+                    self.bind_plain_cte(cte, query)?
+                }
+            };
+            ctes.push(bound);
+        }
+        Ok(ctes)
+    }
+
+    /// Add a non-recursive CTE whose output is `query` to the current context.
+    fn bind_plain_cte(&mut self, cte: &Cte, mut query: BoundSelect) -> Result<BoundCte, BindError> {
+        let binding = self.add_cte(cte, &mut query)?;
+        Ok(BoundCte {
+            id: binding.id,
+            column_types: binding.columns.into_iter().map(|(_, ty)| ty).collect(),
+            query: query.into(),
+            recursive: None,
+            union_all: false,
+        })
+    }
+
+    /// Bind `left UNION [ALL] right` where `right` may reference the CTE itself.
+    fn bind_recursive_cte(
+        &mut self,
+        cte: &Cte,
+        left: &SetExpr,
+        right: &SetExpr,
+        union_all: bool,
+    ) -> Result<BoundCte, BindError> {
+        let query = self.bind_set_expr(left)?;
+        // `NULL`s are typed when the CTE is added, but the untyped query is kept in case the
+        // CTE turns out to be a plain UNION, where they are typed by the other side.
+        let mut typed_query = query.clone();
+        self.add_cte(cte, &mut typed_query)?;
+        let mut recursive = self.bind_set_expr(right)?;
+        let binding = self.context().ctes.pop().unwrap();
+        if !binding.referenced {
+            // Not actually recursive, so it is a plain UNION.
+            let query = build_set_operation(SetOperator::Union, union_all, query, recursive)?;
+            return self.bind_plain_cte(cte, query);
+        }
+        check_recursive_term(&recursive)?;
+        if recursive.select_list.len() != binding.columns.len() {
+            return Err(BindError::TupleLengthMismatch {
+                expected: binding.columns.len(),
+                actual: recursive.select_list.len(),
+            });
+        }
+        // The output types are determined by the non-recursive term.
+        let mut column_types = vec![];
+        for (expr, (_, ty)) in recursive.select_list.iter_mut().zip(&binding.columns) {
+            let kind = ty.kind();
+            let expr_ty = expr.return_type();
+            if let Some(expr_ty) = &expr_ty {
+                if unify_types(&kind, &expr_ty.kind()).is_none() {
+                    return Err(BindError::CastTypeMismatch(expr_ty.kind(), kind));
+                }
+            }
+            let nullable = ty.is_nullable() || expr_ty.map_or(true, |ty| ty.is_nullable());
+            column_types.push(DataType::new(kind.clone(), nullable));
+            *expr = std::mem::replace(expr, BoundExpr::Constant(DataValue::Null)).cast_to(&kind);
+        }
+        // Register the CTE again with the final types for the rest of the query.
+        let columns = binding.columns.iter().map(|(name, _)| name.clone());
+        let columns = columns.zip(column_types.iter().cloned()).collect();
+        self.context().ctes.push(CteBinding { columns, ..binding });
+        Ok(BoundCte {
+            id: binding.id,
+            query: typed_query.into(),
+            recursive: Some(recursive.into()),
+            union_all,
+            column_types,
+        })
+    }
+
+    /// Add a CTE whose output is `query` to the current context.
+    fn add_cte(&mut self, cte: &Cte, query: &mut BoundSelect) -> Result<CteBinding, BindError> {
+        let mut names = query.aliases.clone();
+        if cte.alias.columns.len() > names.len() {
+            return Err(BindError::TupleLengthMismatch {
+                expected: names.len(),
+                actual: cte.alias.columns.len(),
+            });
+        }
+        for (name, ident) in names.iter_mut().zip(&cte.alias.columns) {
+            *name = ident.value.clone();
+        }
+        let types = query.column_types();
+        let binding = CteBinding {
+            name: cte.alias.name.value.clone(),
+            id: self.next_cte_id,
+            columns: names.into_iter().zip(types).collect(),
+            referenced: false,
+        };
+        self.next_cte_id += 1;
+        self.context().ctes.push(binding.clone());
+        Ok(binding)
+    }
+
+    /// Find the CTE `name` in the current and outer query blocks.
+    pub(super) fn find_cte(&mut self, name: &str) -> Option<CteBinding> {
+        let cte = self
+            .contexts
+            .iter_mut()
+            .rev()
+            .find_map(|context| context.ctes.iter_mut().rev().find(|c| c.name == name))?;
+        cte.referenced = true;
+        Some(cte.clone())
+    }
+}
+
+/// Check that the recursive term of a `WITH RECURSIVE` query, including the subqueries in its
+/// FROM clause, does not compute aggregations or window functions over the rows of each
+/// iteration, which would never stop producing new rows.
+fn check_recursive_term(query: &BoundSelect) -> Result<(), BindError> {
+    const CLAUSE: &str = "a recursive query's recursive term";
+    if (query.select_list.iter().chain(&query.having)).any(|e| e.contains_agg()) {
+        return Err(BindError::AggregateNotAllowed(CLAUSE));
+    }
+    if query.select_list.iter().any(|e| e.contains_window()) {
+        return Err(BindError::WindowFunctionNotAllowed(CLAUSE));
+    }
+    fn check_table(table: &BoundTableRef) -> Result<(), BindError> {
+        match table {
+            BoundTableRef::BaseTable { .. } | BoundTableRef::Cte { .. } => Ok(()),
+            BoundTableRef::Subquery(query) => check_recursive_term(query),
+            BoundTableRef::Join(join) => {
+                check_table(&join.left)?;
+                check_table(&join.right)
+            }
+            BoundTableRef::SetOperation(op) => {
+                check_recursive_term(&op.left)?;
+                check_recursive_term(&op.right)
+            }
+        }
+    }
+    query.from_table.as_ref().map_or(Ok(()), check_table)
+}
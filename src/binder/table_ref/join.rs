@@ -0,0 +1,30 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundJoin {
+    pub left: Box<BoundTableRef>,
+    pub right: Box<BoundTableRef>,
+    pub join_type: JoinType,
+    pub condition: BoundExpr,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JoinType {
+    Inner,
+    LeftOuter,
+    RightOuter,
+    FullOuter,
+    /// Output rows on the left that match at least one row on the right.
+    ///
+    /// Only produced by the planner when planning subqueries.
+    Semi,
+    /// Output rows on the left that match no row on the right.
+    ///
+    /// Only produced by the planner when planning subqueries.
+    Anti,
+    /// Output rows of a left outer join, where each row on the left must match at most one row
+    /// on the right.
+    ///
+    /// Only produced by the planner when planning scalar subqueries.
+    LeftSingle,
+}
@@ -1,10 +1,42 @@
+use itertools::Itertools;
+
 use super::*;
-use crate::parser::{TableFactor, TableWithJoins};
+use crate::parser::{JoinConstraint, JoinOperator, TableAlias, TableFactor, TableWithJoins};
+use crate::types::DataValue;
+
+mod cte;
+mod join;
+mod set_operation;
+
+pub use self::cte::*;
+pub use self::join::*;
+pub use self::set_operation::*;
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct BoundTableRef {
-    pub table_ref_id: TableRefId,
-    pub column_ids: Vec<ColumnId>,
+pub enum BoundTableRef {
+    BaseTable {
+        table_ref_id: TableRefId,
+        column_ids: Vec<ColumnId>,
+        column_types: Vec<DataType>,
+    },
+    Subquery(Box<BoundSelect>),
+    Join(BoundJoin),
+    SetOperation(BoundSetOperation),
+    Cte {
+        cte_id: usize,
+        column_types: Vec<DataType>,
+    },
+}
+
+impl BoundTableRef {
+    pub fn cross_join(left: BoundTableRef, right: BoundTableRef) -> Self {
+        BoundTableRef::Join(BoundJoin {
+            left: left.into(),
+            right: right.into(),
+            join_type: JoinType::Inner,
+            condition: BoundExpr::Constant(DataValue::Bool(true)),
+        })
+    }
 }
 
 impl Binder {
@@ -12,24 +44,156 @@ impl Binder {
         &mut self,
         table: &TableWithJoins,
     ) -> Result<BoundTableRef, BindError> {
-        assert!(table.joins.is_empty(), "JOIN is not supported");
+        let start = self.context().relations.len();
+        let mut table_ref = self.bind_table_factor(&table.relation)?;
+        for join in table.joins.iter() {
+            let mid = self.context().relations.len();
+            let right = self.bind_table_factor(&join.relation)?;
+            let (join_type, constraint) = match &join.join_operator {
+                JoinOperator::Inner(constraint) => (JoinType::Inner, constraint),
+                JoinOperator::LeftOuter(constraint) => (JoinType::LeftOuter, constraint),
+                JoinOperator::RightOuter(constraint) => (JoinType::RightOuter, constraint),
+                JoinOperator::FullOuter(constraint) => (JoinType::FullOuter, constraint),
+                JoinOperator::CrossJoin => (JoinType::Inner, &JoinConstraint::None),
+                JoinOperator::CrossApply => {
+                    return Err(BindError::NotSupported("CROSS APPLY".into()))
+                }
+                JoinOperator::OuterApply => {
+                    return Err(BindError::NotSupported("OUTER APPLY".into()))
+                }
+            };
+            // Columns on the outer side of a join may be filled with nulls.
+            let relations = &mut self.context().relations;
+            let nullable_range = match join_type {
+                JoinType::LeftOuter => mid..relations.len(),
+                JoinType::RightOuter => start..mid,
+                JoinType::FullOuter => start..relations.len(),
+                _ => 0..0,
+            };
+            for relation in &mut relations[nullable_range] {
+                for (_, ty) in &mut relation.columns {
+                    *ty = DataType::new(ty.kind(), true);
+                }
+            }
+            let condition = match constraint {
+                JoinConstraint::On(expr) => self.bind_condition(expr, "JOIN/ON")?,
+                JoinConstraint::None => BoundExpr::Constant(DataValue::Bool(true)),
+                JoinConstraint::Using(_) => {
+                    return Err(BindError::NotSupported("JOIN/USING".into()))
+                }
+                JoinConstraint::Natural => {
+                    return Err(BindError::NotSupported("NATURAL JOIN".into()))
+                }
+            };
+            table_ref = BoundTableRef::Join(BoundJoin {
+                left: table_ref.into(),
+                right: right.into(),
+                join_type,
+                condition,
+            });
+        }
+        Ok(table_ref)
+    }
 
-        let (name, alias) = match &table.relation {
-            TableFactor::Table { name, alias, .. } => (name, alias),
-            r => panic!("not supported table factor: {:?}", r),
-        };
-        let (table_ref_id, _, columns) = self.bind_table_columns(name, &[])?;
-        let alias = match alias {
-            Some(alias) => &alias.name.value,
-            None => split_name(name).unwrap().1,
+    fn bind_table_factor(&mut self, table: &TableFactor) -> Result<BoundTableRef, BindError> {
+        match table {
+            TableFactor::Table { name, alias, .. } => {
+                if let [ident] = name.0.as_slice() {
+                    if let Some(cte) = self.find_cte(&ident.value) {
+                        let (names, types): (Vec<_>, Vec<_>) = cte.columns.into_iter().unzip();
+                        self.add_relation(cte.name, names, types.clone(), alias.as_ref())?;
+                        return Ok(BoundTableRef::Cte {
+                            cte_id: cte.id,
+                            column_types: types,
+                        });
+                    }
+                }
+                if let Some((schema_id, view)) = self.resolve_view(name)? {
+                    return self.bind_view(schema_id, &view, alias.as_ref());
+                }
+                let (table_ref_id, _, columns) = self.bind_table_columns(name, &[])?;
+                self.dependencies.push(table_ref_id);
+                let table_name = split_name(name)?.1.to_string();
+                let names = columns.iter().map(|col| col.name().to_string()).collect();
+                let types = columns.iter().map(|col| col.datatype()).collect_vec();
+                self.add_relation(table_name, names, types.clone(), alias.as_ref())?;
+                Ok(BoundTableRef::BaseTable {
+                    table_ref_id,
+                    column_ids: columns.iter().map(|col| col.id()).collect(),
+                    column_types: types,
+                })
+            }
+            TableFactor::Derived {
+                lateral: false,
+                subquery,
+                alias,
+            } => {
+                let mut query = self.bind_select(subquery)?;
+                let types = query.column_types();
+                self.add_relation(String::new(), query.aliases.clone(), types, alias.as_ref())?;
+                Ok(BoundTableRef::Subquery(query.into()))
+            }
+            TableFactor::NestedJoin {
+                table_with_joins,
+                alias: None,
+            } => self.bind_table_with_joins(table_with_joins),
+            TableFactor::Derived { lateral: true, .. } => {
+                Err(BindError::NotSupported("LATERAL subquery".into()))
+            }
+            r => Err(BindError::NotSupported(format!("table factor: {}", r))),
+        }
+    }
+
+    /// Bind a reference to a view as a subquery, which is bound from the query of the view.
+    fn bind_view(
+        &mut self,
+        schema_id: SchemaId,
+        view: &ViewCatalog,
+        alias: Option<&TableAlias>,
+    ) -> Result<BoundTableRef, BindError> {
+        let definition = view.definition();
+        let query = self.bind_view_definition(view.name(), definition)?;
+        self.dependencies
+            .push(TableRefId::new(schema_id, view.id()));
+        let (names, types) = definition.columns.iter().cloned().unzip();
+        self.add_relation(view.name().into(), names, types, alias)?;
+        Ok(BoundTableRef::Subquery(query.into()))
+    }
+
+    /// Add a relation to the current context so that its columns can be referenced.
+    pub(super) fn add_relation(
+        &mut self,
+        name: String,
+        mut column_names: Vec<String>,
+        column_types: Vec<DataType>,
+        alias: Option<&TableAlias>,
+    ) -> Result<(), BindError> {
+        let name = match alias {
+            Some(alias) => {
+                if alias.columns.len() > column_names.len() {
+                    return Err(BindError::TupleLengthMismatch {
+                        expected: column_names.len(),
+                        actual: alias.columns.len(),
+                    });
+                }
+                for (name, ident) in column_names.iter_mut().zip(&alias.columns) {
+                    *name = ident.value.clone();
+                }
+                alias.name.value.clone()
+            }
+            None => name,
         };
-        if self.tables.contains_key(alias) {
-            return Err(BindError::DuplicatedAlias(alias.into()));
+        let context = self.context();
+        if !name.is_empty() && context.relations.iter().any(|r| r.name == name) {
+            return Err(BindError::DuplicatedAlias(name));
         }
-        self.tables.insert(alias.into(), table_ref_id);
-        Ok(BoundTableRef {
-            table_ref_id,
-            column_ids: columns.iter().map(|col| col.id()).collect(),
-        })
+        let width = column_names.len();
+        context.relations.push(Relation {
+            name,
+            offset: context.width,
+            columns: column_names.into_iter().zip(column_types).collect(),
+        });
+        context.width += width;
+        Ok(())
     }
 }
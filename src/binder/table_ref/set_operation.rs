@@ -0,0 +1,81 @@
+use super::*;
+use crate::parser::{SetExpr, SetOperator, SetQuantifier};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundSetOperation {
+    pub op: SetOperator,
+    /// Whether to keep duplicate rows (`ALL`).
+    pub all: bool,
+    pub left: Box<BoundSelect>,
+    pub right: Box<BoundSelect>,
+}
+
+impl Binder {
+    /// Bind `left op right` as a query block whose input is the result of the set operation.
+    pub fn bind_set_operation(
+        &mut self,
+        op: &SetOperator,
+        set_quantifier: &SetQuantifier,
+        left: &SetExpr,
+        right: &SetExpr,
+    ) -> Result<BoundSelect, BindError> {
+        let left = self.bind_set_expr(left)?;
+        let right = self.bind_set_expr(right)?;
+        let all = matches!(set_quantifier, SetQuantifier::All);
+        build_set_operation(op.clone(), all, left, right)
+    }
+}
+
+/// Build a set operation, casting the columns of both sides to common types.
+pub fn build_set_operation(
+    op: SetOperator,
+    all: bool,
+    mut left: BoundSelect,
+    mut right: BoundSelect,
+) -> Result<BoundSelect, BindError> {
+    if left.select_list.len() != right.select_list.len() {
+        return Err(BindError::TupleLengthMismatch {
+            expected: left.select_list.len(),
+            actual: right.select_list.len(),
+        });
+    }
+    let mut select_list = vec![];
+    let columns = left
+        .select_list
+        .iter_mut()
+        .zip(right.select_list.iter_mut());
+    for (index, (l, r)) in columns.enumerate() {
+        let (lty, rty) = (l.return_type(), r.return_type());
+        let kind = match (&lty, &rty) {
+            (Some(lty), Some(rty)) => unify_types(&lty.kind(), &rty.kind()).ok_or_else(|| {
+                BindError::SetOperationTypeMismatch(op.clone(), lty.kind(), rty.kind())
+            })?,
+            (Some(ty), None) | (None, Some(ty)) => ty.kind(),
+            (None, None) => DataTypeKind::Varchar(None),
+        };
+        let nullable = [lty, rty]
+            .iter()
+            .any(|ty| ty.as_ref().map_or(true, |ty| ty.is_nullable()));
+        *l = std::mem::replace(l, BoundExpr::Constant(DataValue::Null)).cast_to(&kind);
+        *r = std::mem::replace(r, BoundExpr::Constant(DataValue::Null)).cast_to(&kind);
+        select_list.push(BoundExpr::ColumnRef(BoundColumnRef {
+            index,
+            depth: 0,
+            return_type: DataType::new(kind, nullable),
+        }));
+    }
+    Ok(BoundSelect {
+        ctes: vec![],
+        select_list,
+        aliases: left.aliases.clone(),
+        from_table: Some(BoundTableRef::SetOperation(BoundSetOperation {
+            op,
+            all,
+            left: left.into(),
+            right: right.into(),
+        })),
+        where_clause: None,
+        group_by: vec![],
+        having: None,
+    })
+}
@@ -1,10 +1,13 @@
 use super::*;
+use crate::binder::BoundExpr;
 use crate::types::DataType;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ColumnDesc {
     datatype: DataType,
     is_primary: bool,
+    /// The value of the column when it is omitted in `INSERT`, which is null without it.
+    default: Option<BoundExpr>,
 }
 
 impl ColumnDesc {
@@ -12,9 +15,30 @@ impl ColumnDesc {
         ColumnDesc {
             datatype,
             is_primary,
+            default: None,
         }
     }
 
+    pub fn with_default(self, default: BoundExpr) -> Self {
+        ColumnDesc {
+            default: Some(default),
+            ..self
+        }
+    }
+
+    /// Make the column part of the primary key, which is not nullable.
+    pub fn with_primary(self) -> Self {
+        ColumnDesc {
+            datatype: DataType::new(self.datatype.kind(), false),
+            is_primary: true,
+            ..self
+        }
+    }
+
+    pub fn default(&self) -> Option<&BoundExpr> {
+        self.default.as_ref()
+    }
+
     pub fn is_primary(&self) -> bool {
         self.is_primary
     }
@@ -38,7 +62,7 @@ impl DataType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ColumnCatalog {
     id: ColumnId,
     name: String,
@@ -58,6 +82,10 @@ impl ColumnCatalog {
         &self.name
     }
 
+    pub(super) fn set_name(&mut self, name: String) -> String {
+        std::mem::replace(&mut self.name, name)
+    }
+
     pub fn desc(&self) -> &ColumnDesc {
         &self.desc
     }
@@ -2,9 +2,11 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use super::*;
+use crate::function::FunctionRegistry;
 
 pub struct DatabaseCatalog {
     inner: Mutex<Inner>,
+    functions: FunctionRegistry,
 }
 
 #[derive(Default)]
@@ -24,6 +26,7 @@ impl DatabaseCatalog {
     pub fn new() -> DatabaseCatalog {
         let db_catalog = DatabaseCatalog {
             inner: Mutex::new(Inner::default()),
+            functions: FunctionRegistry::new(),
         };
         db_catalog.add_schema(DEFAULT_SCHEMA_NAME).unwrap();
         db_catalog
@@ -71,8 +74,40 @@ impl DatabaseCatalog {
             .cloned()
     }
 
+    pub fn functions(&self) -> &FunctionRegistry {
+        &self.functions
+    }
+
     pub fn get_table(&self, table_ref_id: TableRefId) -> Option<Arc<TableCatalog>> {
         let schema = self.get_schema(table_ref_id.schema_id)?;
         schema.get_table(table_ref_id.table_id)
     }
+
+    pub fn get_view(&self, table_ref_id: TableRefId) -> Option<Arc<ViewCatalog>> {
+        let schema = self.get_schema(table_ref_id.schema_id)?;
+        schema.get_view(table_ref_id.table_id)
+    }
+
+    /// Returns the views and materialized views in all schemas that reference the table or
+    /// view directly.
+    pub fn dependent_views(&self, table_ref_id: TableRefId) -> Vec<(TableRefId, String)> {
+        let mut views = vec![];
+        for (schema_id, schema) in self.all_schemas() {
+            for (view_id, view) in schema.all_views() {
+                if view.definition().dependencies.contains(&table_ref_id) {
+                    views.push((TableRefId::new(schema_id, view_id), view.name().into()));
+                }
+            }
+            for (table_id, table) in schema.all_tables() {
+                let Some(definition) = table.view_definition() else {
+                    continue;
+                };
+                if definition.dependencies.contains(&table_ref_id) {
+                    views.push((TableRefId::new(schema_id, table_id), table.name()));
+                }
+            }
+        }
+        views.sort_by_key(|(id, _)| (id.schema_id, id.table_id));
+        views
+    }
 }
@@ -4,11 +4,13 @@ mod column;
 mod database;
 mod schema;
 mod table;
+mod view;
 
 pub use self::column::*;
 pub use self::database::*;
 pub use self::schema::*;
 pub use self::table::*;
+pub use self::view::*;
 
 pub type CatalogRef = Arc<DatabaseCatalog>;
 
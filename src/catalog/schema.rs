@@ -12,6 +12,10 @@ struct Inner {
     name: String,
     table_idxs: HashMap<String, TableId>,
     tables: HashMap<TableId, Arc<TableCatalog>>,
+    view_idxs: HashMap<String, TableId>,
+    views: HashMap<TableId, Arc<ViewCatalog>>,
+    /// The next id of a table or view. Views share ids with tables so that a `TableRefId`
+    /// refers to either of them.
     next_table_id: TableId,
 }
 
@@ -23,6 +27,8 @@ impl SchemaCatalog {
                 name,
                 table_idxs: HashMap::new(),
                 tables: HashMap::new(),
+                view_idxs: HashMap::new(),
+                views: HashMap::new(),
                 next_table_id: 0,
             }),
         }
@@ -39,7 +45,7 @@ impl SchemaCatalog {
 
     pub fn add_table(&self, name: &str) -> Result<TableId, CatalogError> {
         let mut inner = self.inner.lock().unwrap();
-        if inner.table_idxs.contains_key(name) {
+        if inner.table_idxs.contains_key(name) || inner.view_idxs.contains_key(name) {
             return Err(CatalogError::Duplicated("table", name.into()));
         }
 
@@ -61,10 +67,24 @@ impl SchemaCatalog {
         Ok(())
     }
 
-    pub fn del_table(&self, id: TableId) {
+    pub fn del_table(&self, id: TableId) -> Result<(), CatalogError> {
         let mut inner = self.inner.lock().unwrap();
-        let catalog = inner.tables.remove(&id).unwrap();
-        inner.table_idxs.remove(&catalog.name()).unwrap();
+        let catalog = (inner.tables.remove(&id))
+            .ok_or_else(|| CatalogError::NotFound("table", id.to_string()))?;
+        inner.table_idxs.remove(&catalog.name());
+        Ok(())
+    }
+
+    pub fn rename_table(&self, id: TableId, new_name: &str) -> Result<(), CatalogError> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.table_idxs.contains_key(new_name) || inner.view_idxs.contains_key(new_name) {
+            return Err(CatalogError::Duplicated("table", new_name.into()));
+        }
+        let catalog = inner.tables[&id].clone();
+        inner.table_idxs.remove(&catalog.name());
+        inner.table_idxs.insert(new_name.into(), id);
+        catalog.rename(new_name);
+        Ok(())
     }
 
     pub fn all_tables(&self) -> HashMap<TableId, Arc<TableCatalog>> {
@@ -77,6 +97,65 @@ impl SchemaCatalog {
         inner.tables.get(&table_id).cloned()
     }
 
+    pub fn add_view(
+        &self,
+        name: &str,
+        definition: ViewDefinition,
+    ) -> Result<TableId, CatalogError> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.table_idxs.contains_key(name) || inner.view_idxs.contains_key(name) {
+            return Err(CatalogError::Duplicated("view", name.into()));
+        }
+        let id = inner.next_table_id;
+        inner.next_table_id += 1;
+        let view_catalog = Arc::new(ViewCatalog::new(id, name.into(), definition));
+        inner.view_idxs.insert(name.into(), id);
+        inner.views.insert(id, view_catalog);
+        Ok(id)
+    }
+
+    /// Replace the definition of a view.
+    pub fn replace_view(&self, id: TableId, definition: ViewDefinition) {
+        let mut inner = self.inner.lock().unwrap();
+        let name = inner.views[&id].name().to_string();
+        let view_catalog = Arc::new(ViewCatalog::new(id, name, definition));
+        inner.views.insert(id, view_catalog);
+    }
+
+    pub fn del_view(&self, id: TableId) {
+        let mut inner = self.inner.lock().unwrap();
+        let catalog = inner.views.remove(&id).unwrap();
+        inner.view_idxs.remove(catalog.name()).unwrap();
+    }
+
+    pub fn all_views(&self) -> HashMap<TableId, Arc<ViewCatalog>> {
+        let inner = self.inner.lock().unwrap();
+        inner.views.clone()
+    }
+
+    pub fn get_view(&self, view_id: TableId) -> Option<Arc<ViewCatalog>> {
+        let inner = self.inner.lock().unwrap();
+        inner.views.get(&view_id).cloned()
+    }
+
+    pub fn get_view_by_name(&self, name: &str) -> Option<Arc<ViewCatalog>> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .view_idxs
+            .get(name)
+            .and_then(|id| inner.views.get(id))
+            .cloned()
+    }
+
+    /// Returns the index of the name and the table it is on.
+    pub fn get_index_by_name(&self, name: &str) -> Option<(Arc<TableCatalog>, IndexCatalog)> {
+        let inner = self.inner.lock().unwrap();
+        inner.tables.values().find_map(|table| {
+            let index = table.all_indexes().into_iter().find(|i| i.name == name)?;
+            Some((table.clone(), index))
+        })
+    }
+
     pub fn get_table_by_name(&self, name: &str) -> Option<Arc<TableCatalog>> {
         let inner = self.inner.lock().unwrap();
         inner
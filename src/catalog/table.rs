@@ -2,6 +2,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::sync::Mutex;
 
 use super::*;
+use crate::binder::BoundExpr;
 
 pub struct TableCatalog {
     id: TableId,
@@ -13,6 +14,47 @@ struct Inner {
     column_idxs: HashMap<String, ColumnId>,
     columns: BTreeMap<ColumnId, ColumnCatalog>,
     next_column_id: ColumnId,
+    checks: Vec<CheckConstraint>,
+    uniques: Vec<UniqueConstraint>,
+    indexes: Vec<IndexCatalog>,
+    /// The query whose result the table stores, if it is a materialized view.
+    view_definition: Option<ViewDefinition>,
+}
+
+/// A secondary index on a column, used to scan the rows whose values in the column are in a
+/// range. Index names are unique in the schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexCatalog {
+    pub name: String,
+    pub column_id: ColumnId,
+}
+
+/// A `UNIQUE` or `PRIMARY KEY` constraint. No two rows may have the same values in the
+/// columns, unless one of them is null.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniqueConstraint {
+    pub name: String,
+    pub column_ids: Vec<ColumnId>,
+    pub is_primary: bool,
+}
+
+/// A `CHECK` constraint. A row violates it if the expression is false.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckConstraint {
+    pub name: String,
+    /// The expression, in which the index of a column reference is the id of the column.
+    pub expr: BoundExpr,
+}
+
+impl CheckConstraint {
+    /// Returns true if the constraint references the column.
+    pub fn references(&self, column_id: ColumnId) -> bool {
+        let mut found = false;
+        self.expr.visit(&mut |e| {
+            found |= matches!(e, BoundExpr::ColumnRef(c) if c.index == column_id as usize)
+        });
+        found
+    }
 }
 
 impl TableCatalog {
@@ -24,6 +66,10 @@ impl TableCatalog {
                 column_idxs: HashMap::new(),
                 columns: BTreeMap::new(),
                 next_column_id: 0,
+                checks: vec![],
+                uniques: vec![],
+                indexes: vec![],
+                view_definition: None,
             }),
         }
     }
@@ -51,6 +97,101 @@ impl TableCatalog {
         Ok(id)
     }
 
+    /// Remove a column and the constraints and indexes that reference it.
+    pub fn del_column(&self, id: ColumnId) {
+        let mut inner = self.inner.lock().unwrap();
+        let column = inner.columns.remove(&id).unwrap();
+        inner.column_idxs.remove(column.name()).unwrap();
+        inner.checks.retain(|check| !check.references(id));
+        inner
+            .uniques
+            .retain(|unique| !unique.column_ids.contains(&id));
+        inner.indexes.retain(|index| index.column_id != id);
+    }
+
+    pub fn add_check(&self, check: CheckConstraint) -> Result<(), CatalogError> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.checks.iter().any(|c| c.name == check.name)
+            || inner.uniques.iter().any(|u| u.name == check.name)
+        {
+            return Err(CatalogError::Duplicated("constraint", check.name));
+        }
+        inner.checks.push(check);
+        Ok(())
+    }
+
+    pub fn all_checks(&self) -> Vec<CheckConstraint> {
+        let inner = self.inner.lock().unwrap();
+        inner.checks.clone()
+    }
+
+    pub fn add_unique(&self, unique: UniqueConstraint) -> Result<(), CatalogError> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.uniques.iter().any(|u| u.name == unique.name)
+            || inner.checks.iter().any(|c| c.name == unique.name)
+        {
+            return Err(CatalogError::Duplicated("constraint", unique.name));
+        }
+        inner.uniques.push(unique);
+        Ok(())
+    }
+
+    pub fn all_uniques(&self) -> Vec<UniqueConstraint> {
+        let inner = self.inner.lock().unwrap();
+        inner.uniques.clone()
+    }
+
+    pub fn add_index(&self, index: IndexCatalog) -> Result<(), CatalogError> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.indexes.iter().any(|i| i.name == index.name) {
+            return Err(CatalogError::Duplicated("index", index.name));
+        }
+        inner.indexes.push(index);
+        Ok(())
+    }
+
+    pub fn del_index(&self, name: &str) -> Result<IndexCatalog, CatalogError> {
+        let mut inner = self.inner.lock().unwrap();
+        let pos = (inner.indexes.iter())
+            .position(|i| i.name == name)
+            .ok_or_else(|| CatalogError::NotFound("index", name.into()))?;
+        Ok(inner.indexes.remove(pos))
+    }
+
+    pub fn all_indexes(&self) -> Vec<IndexCatalog> {
+        let inner = self.inner.lock().unwrap();
+        inner.indexes.clone()
+    }
+
+    /// Make the table a materialized view that stores the result of the query.
+    pub fn set_view_definition(&self, definition: ViewDefinition) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.view_definition = Some(definition);
+    }
+
+    /// Returns the definition of the materialized view, or `None` if the table is not one.
+    pub fn view_definition(&self) -> Option<ViewDefinition> {
+        let inner = self.inner.lock().unwrap();
+        inner.view_definition.clone()
+    }
+
+    pub fn rename_column(&self, id: ColumnId, new_name: &str) -> Result<(), CatalogError> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.column_idxs.contains_key(new_name) {
+            return Err(CatalogError::Duplicated("column", new_name.into()));
+        }
+        let column = inner.columns.get_mut(&id).unwrap();
+        let old_name = column.set_name(new_name.into());
+        inner.column_idxs.remove(&old_name);
+        inner.column_idxs.insert(new_name.into(), id);
+        Ok(())
+    }
+
+    pub(super) fn rename(&self, new_name: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.name = new_name.into();
+    }
+
     pub fn contains_column(&self, name: &str) -> bool {
         let inner = self.inner.lock().unwrap();
         inner.column_idxs.contains_key(name)
@@ -105,5 +246,23 @@ mod tests {
         let col1_catalog = table_catalog.get_column(1).unwrap();
         assert_eq!(col1_catalog.name(), "b");
         assert_eq!(col1_catalog.datatype().kind(), DataTypeKind::Boolean);
+
+        table_catalog.rename_column(0, "c").unwrap();
+        assert!(table_catalog.rename_column(1, "c").is_err());
+        assert!(!table_catalog.contains_column("a"));
+        assert_eq!(table_catalog.get_column_by_name("c").unwrap().id(), 0);
+
+        let index = IndexCatalog {
+            name: "i".into(),
+            column_id: 0,
+        };
+        table_catalog.add_index(index.clone()).unwrap();
+        assert!(table_catalog.add_index(index).is_err());
+        assert_eq!(table_catalog.all_indexes().len(), 1);
+
+        table_catalog.del_column(0);
+        assert!(!table_catalog.contains_column("c"));
+        assert!(table_catalog.all_indexes().is_empty());
+        assert_eq!(table_catalog.all_columns().len(), 1);
     }
 }
@@ -0,0 +1,44 @@
+use super::*;
+use crate::parser::Query;
+use crate::types::DataType;
+
+/// A view, which is a query stored under a name. The query is bound again each time the view
+/// is referenced.
+pub struct ViewCatalog {
+    id: TableId,
+    name: String,
+    definition: ViewDefinition,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewDefinition {
+    /// The names and types of the output columns.
+    pub columns: Vec<(String, DataType)>,
+    pub query: Query,
+    /// The search path when the view was defined, in which the query is bound.
+    pub search_path: Vec<String>,
+    /// The tables and views that the query references directly.
+    pub dependencies: Vec<TableRefId>,
+}
+
+impl ViewCatalog {
+    pub(super) fn new(id: TableId, name: String, definition: ViewDefinition) -> ViewCatalog {
+        ViewCatalog {
+            id,
+            name,
+            definition,
+        }
+    }
+
+    pub fn id(&self) -> TableId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn definition(&self) -> &ViewDefinition {
+        &self.definition
+    }
+}
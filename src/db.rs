@@ -1,21 +1,28 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
 use futures::TryStreamExt;
 use tokio::runtime::Runtime;
 
-use crate::array::DataChunk;
-use crate::binder::{BindError, Binder};
-use crate::catalog::{CatalogRef, DatabaseCatalog};
+use crate::array::{to_arrow_field, ArrayImpl, DataChunk, Utf8Array};
+use crate::binder::{BindError, Binder, BoundStatement};
+use crate::catalog::{CatalogRef, DatabaseCatalog, DEFAULT_SCHEMA_NAME};
 use crate::executor::{ExecuteError, ExecutorBuilder};
-use crate::logical_planner::{LogicalPlanError, LogicalPlanner};
-use crate::parser::{parse, ParserError};
+use crate::function::{Accumulator, AggregateFunction, ScalarFunction};
+use crate::logical_planner::{LogicalPlanError, LogicalPlanner, PlanNode};
+use crate::parser::{parse, ParserError, Statement};
 use crate::physical_planner::{PhysicalPlanError, PhysicalPlanner};
 use crate::storage::{DiskStorage, StorageOptions};
+use crate::types::{ConvertError, DataType, DataTypeExt, DataTypeKind};
 
 pub struct Database {
     catalog: CatalogRef,
     executor_builder: ExecutorBuilder,
     runtime: Runtime,
+    /// The schemas that unqualified table names are looked up in, set by `SET search_path`.
+    search_path: Mutex<Vec<String>>,
 }
 
 impl Database {
@@ -36,35 +43,130 @@ impl Database {
             catalog: catalog.clone(),
             executor_builder: ExecutorBuilder::new(catalog, storage, handle),
             runtime,
+            search_path: Mutex::new(vec![DEFAULT_SCHEMA_NAME.into()]),
         }
     }
 
+    /// Register a scalar function that can be called by `name` in SQL.
+    ///
+    /// `f` takes an array for each argument, all of the same length, and returns an array of
+    /// that length. The arguments are cast to `arg_types` and may contain NULLs. The result
+    /// must be an array of `return_type`, or the query fails. Registering a function with the
+    /// same name and argument types as an existing one, including a built-in one, replaces it.
+    /// Returns an error if `arg_types` is empty.
+    pub fn register_scalar_function(
+        &self,
+        name: &str,
+        arg_types: &[DataType],
+        return_type: DataType,
+        f: impl Fn(&[ArrayImpl]) -> Result<ArrayImpl, ConvertError> + Send + Sync + 'static,
+    ) -> Result<(), Error> {
+        if arg_types.is_empty() {
+            return Err(Error::InvalidFunction(format!(
+                "{} must have arguments",
+                name
+            )));
+        }
+        let arg_types = arg_types.iter().map(|ty| ty.kind()).collect();
+        let func = ScalarFunction::new(name, arg_types, return_type.kind(), f);
+        self.catalog.functions().register_scalar(func);
+        Ok(())
+    }
+
+    /// Register an aggregate function that can be called by `name` in SQL, also as a window
+    /// function.
+    ///
+    /// `create` returns the initial state for each group of rows. The arguments are cast to
+    /// `arg_types`, and rows where any argument is NULL are skipped. Registering a function
+    /// with the same name and argument types as an existing one replaces it. Built-in
+    /// aggregate functions can not be replaced.
+    pub fn register_aggregate_function<A: Accumulator + 'static>(
+        &self,
+        name: &str,
+        arg_types: &[DataType],
+        return_type: DataType,
+        create: impl Fn() -> A + Send + Sync + 'static,
+    ) {
+        let arg_types = arg_types.iter().map(|ty| ty.kind()).collect();
+        let func = AggregateFunction::new(name, arg_types, return_type.kind(), move || {
+            Box::new(create())
+        });
+        self.catalog.functions().register_aggregate(func);
+    }
+
     pub fn run_sql(&self, sql: &str) -> Result<Vec<DataChunk>, Error> {
-        let stmts = parse(sql)?;
+        let mut outputs = vec![];
+        for stmt in parse(sql)? {
+            outputs.extend(self.run_stmt(&stmt)?.1);
+        }
+        Ok(outputs)
+    }
 
+    /// Run SQL and returns the outputs as Arrow record batches.
+    ///
+    /// Each statement with outputs returns at least one batch, so that the schema of a query
+    /// that returns no rows is known. The columns of a query are named as in its select list,
+    /// and those of other statements are named `?column?`.
+    pub fn run_sql_arrow(&self, sql: &str) -> Result<Vec<RecordBatch>, Error> {
         let mut outputs = vec![];
-        for stmt in stmts {
-            let mut binder = Binder::new(self.catalog.clone());
-            let logical_planner = LogicalPlanner::default();
-            let physical_planner = PhysicalPlanner::default();
+        for stmt in parse(sql)? {
+            let (schema, chunks) = self.run_stmt(&stmt)?;
+            if chunks.is_empty() && !schema.fields().is_empty() {
+                outputs.push(RecordBatch::new_empty(schema.clone()));
+            }
+            for chunk in chunks {
+                outputs.push(chunk.to_record_batch(schema.clone())?);
+            }
+        }
+        Ok(outputs)
+    }
 
-            let bound_stmt = binder.bind(&stmt)?;
-            debug!("{:#?}", bound_stmt);
-            let logical_plan = logical_planner.plan(bound_stmt)?;
-            debug!("{:#?}", logical_plan);
-            let physical_plan = physical_planner.plan(&logical_plan)?;
-            debug!("{:#?}", physical_plan);
-            let mut executor = self.executor_builder.build(physical_plan);
+    /// Run a statement and returns the Arrow schema of its outputs and the outputs.
+    fn run_stmt(&self, stmt: &Statement) -> Result<(SchemaRef, Vec<DataChunk>), Error> {
+        let search_path = self.search_path.lock().unwrap().clone();
+        let mut binder = Binder::new(self.catalog.clone()).with_search_path(search_path);
+        let logical_planner = LogicalPlanner::default();
+        let physical_planner = PhysicalPlanner::new(self.catalog.clone());
 
-            self.runtime.block_on(async {
-                while let Some(chunk) = executor.try_next().await? {
-                    outputs.push(chunk);
-                }
-                Ok(()) as Result<(), Error>
-            })?;
+        let bound_stmt = binder.bind(stmt)?;
+        debug!("{:#?}", bound_stmt);
+        match bound_stmt {
+            BoundStatement::SetSearchPath(search_path) => {
+                *self.search_path.lock().unwrap() = search_path;
+                return Ok((Arc::new(Schema::empty()), vec![]));
+            }
+            BoundStatement::ShowSearchPath => {
+                let ty = DataTypeKind::Varchar(None).not_null();
+                let field = to_arrow_field("search_path", &ty);
+                let search_path = self.search_path.lock().unwrap().join(", ");
+                let array = [Some(search_path)].into_iter().collect::<Utf8Array>();
+                let chunk = [ArrayImpl::Utf8(array)].into_iter().collect();
+                return Ok((Arc::new(Schema::new(vec![field])), vec![chunk]));
+            }
+            _ => {}
         }
+        let names = match &bound_stmt {
+            BoundStatement::Select(select) => select.aliases.clone(),
+            _ => vec![],
+        };
+        let logical_plan = logical_planner.plan(bound_stmt)?;
+        debug!("{:#?}", logical_plan);
+        let fields = (logical_plan.out_types().iter().enumerate())
+            .map(|(i, ty)| to_arrow_field(names.get(i).map_or("?column?", |s| s), ty))
+            .collect();
+        let physical_plan = physical_planner.plan(&logical_plan)?;
+        debug!("{:#?}", physical_plan);
+        let mut executor = self.executor_builder.build(physical_plan);
 
-        Ok(outputs)
+        let mut outputs = vec![];
+        self.runtime.block_on(async {
+            while let Some(chunk) = executor.try_next().await? {
+                // callers see only the visible rows in the arrays
+                outputs.push(chunk.compact());
+            }
+            Ok(()) as Result<(), Error>
+        })?;
+        Ok((Arc::new(Schema::new(fields)), outputs))
     }
 }
 
@@ -80,4 +182,8 @@ pub enum Error {
     PhysicalPlan(#[from] PhysicalPlanError),
     #[error("execute error: {0}")]
     Execute(#[from] ExecuteError),
+    #[error("arrow error: {0}")]
+    Arrow(#[from] ArrowError),
+    #[error("invalid function: {0}")]
+    InvalidFunction(String),
 }
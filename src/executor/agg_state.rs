@@ -0,0 +1,99 @@
+use crate::binder::AggKind;
+use crate::function::Accumulator;
+use crate::types::{ConvertError, DataTypeKind, DataValue, Decimal};
+
+/// The intermediate state of an aggregate function.
+pub enum AggState {
+    Count(i64),
+    Sum(DataValue),
+    Avg { sum: DataValue, count: usize },
+    Min(DataValue),
+    Max(DataValue),
+    User(Box<dyn Accumulator>),
+}
+
+impl AggState {
+    pub fn new(kind: &AggKind) -> Self {
+        match kind {
+            AggKind::Count => Self::Count(0),
+            AggKind::Sum => Self::Sum(DataValue::Null),
+            AggKind::Avg => Self::Avg {
+                sum: DataValue::Null,
+                count: 0,
+            },
+            AggKind::Min => Self::Min(DataValue::Null),
+            AggKind::Max => Self::Max(DataValue::Null),
+            AggKind::User(f) => Self::User((f.create)()),
+        }
+    }
+
+    /// Add a row with a value for each argument to the aggregation. Rows with any NULL are
+    /// ignored. `COUNT(*)` has no arguments.
+    pub fn update(&mut self, args: &[DataValue]) -> Result<(), ConvertError> {
+        if args.iter().any(|v| v.is_null()) {
+            return Ok(());
+        }
+        // the argument of the built-in functions except `COUNT(*)`
+        let value = || &args[0];
+        match self {
+            Self::Count(count) => *count = count.checked_add(1).ok_or(ConvertError::Overflow)?,
+            Self::User(acc) => acc.update(args)?,
+            Self::Sum(sum) => *sum = add(sum, value())?,
+            Self::Avg { sum, count } => {
+                // decimals are averaged exactly and other numbers as floats
+                *sum = match value() {
+                    v @ DataValue::Decimal(_) => add(sum, v)?,
+                    v => add(sum, &v.cast(&DataTypeKind::Double)?)?,
+                };
+                *count += 1;
+            }
+            Self::Min(min) => {
+                if min.is_null() || value() < min {
+                    *min = value().clone();
+                }
+            }
+            Self::Max(max) => {
+                if max.is_null() || value() > max {
+                    *max = value().clone();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the result of the aggregation. It is NULL if no value is added, except `COUNT`.
+    pub fn output(&self) -> DataValue {
+        match self {
+            Self::Count(count) => DataValue::Int64(*count),
+            Self::Sum(v) | Self::Min(v) | Self::Max(v) => v.clone(),
+            Self::Avg { count: 0, .. } => DataValue::Null,
+            Self::Avg {
+                sum: DataValue::Decimal(sum),
+                count,
+            } => DataValue::Decimal(sum / Decimal::from(*count)),
+            Self::Avg {
+                sum: DataValue::Float64(sum),
+                count,
+            } => DataValue::Float64(sum / *count as f64),
+            Self::Avg { sum, .. } => panic!("can not average {:?}", sum),
+            Self::User(acc) => acc.output(),
+        }
+    }
+}
+
+/// Add a value to a sum, which is NULL if nothing is added. Integers of any width are summed up
+/// in 64 bits.
+fn add(sum: &DataValue, v: &DataValue) -> Result<DataValue, ConvertError> {
+    Ok(match (sum, v, v.as_i64()) {
+        (DataValue::Null, DataValue::Float64(_) | DataValue::Decimal(_), _) => v.clone(),
+        (DataValue::Float64(a), DataValue::Float64(b), _) => DataValue::Float64(a + b),
+        (DataValue::Decimal(a), DataValue::Decimal(b), _) => {
+            DataValue::Decimal(a.checked_add(*b).ok_or(ConvertError::Overflow)?)
+        }
+        (DataValue::Null, _, Some(b)) => DataValue::Int64(b),
+        (DataValue::Int64(a), _, Some(b)) => {
+            DataValue::Int64(a.checked_add(b).ok_or(ConvertError::Overflow)?)
+        }
+        (a, b, _) => panic!("can not sum {:?} and {:?}", a, b),
+    })
+}
@@ -0,0 +1,148 @@
+use std::collections::{HashMap, HashSet};
+
+use super::agg_state::AggState;
+use super::*;
+use crate::array::{Array, ArrayBuilderImpl, ArrayImpl, DataChunk};
+use crate::binder::{BoundAggCall, BoundExpr};
+use crate::types::{DataType, DataValue};
+
+pub struct SimpleAggExecutor {
+    pub aggs: Vec<BoundAggCall>,
+    pub child: BoxedExecutor,
+}
+
+impl SimpleAggExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let mut group = GroupState::new(&self.aggs);
+        #[for_await]
+        for chunk in self.child {
+            let chunk = chunk?;
+            let args = eval_args(&self.aggs, &chunk)?;
+            for row in chunk.visible_rows() {
+                group.update(&args, row)?;
+            }
+        }
+        let types = self.aggs.iter().map(|a| a.return_type.clone());
+        yield build_chunk(types, [group.output()]);
+    }
+}
+
+pub struct HashAggExecutor {
+    pub aggs: Vec<BoundAggCall>,
+    pub group_by: Vec<BoundExpr>,
+    pub child: BoxedExecutor,
+}
+
+impl HashAggExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        // The groups in the order of their first rows.
+        let mut groups: Vec<(Vec<DataValue>, GroupState)> = vec![];
+        let mut index = HashMap::new();
+        #[for_await]
+        for chunk in self.child {
+            let chunk = chunk?;
+            let keys = (self.group_by.iter())
+                .map(|e| e.eval_array(&chunk))
+                .collect::<Result<Vec<_>, _>>()?;
+            let args = eval_args(&self.aggs, &chunk)?;
+            // If all keys are dictionary-encoded, rows with the same codes are in the same
+            // group, so the strings of each combination of codes are looked up only once.
+            let codes = (keys.iter())
+                .map(|a| match a {
+                    ArrayImpl::Dictionary(a) => Some(a.codes()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            let mut groups_of_codes = HashMap::new();
+            for row in chunk.visible_rows() {
+                let mut group_of_row = || {
+                    let key: Vec<DataValue> = keys.iter().map(|a| a.get(row)).collect();
+                    *index.entry(key.clone()).or_insert_with(|| {
+                        groups.push((key, GroupState::new(&self.aggs)));
+                        groups.len() - 1
+                    })
+                };
+                let i = match &codes {
+                    Some(codes) => {
+                        let codes: Vec<Option<u32>> =
+                            codes.iter().map(|c| c.get(row).copied()).collect();
+                        *groups_of_codes.entry(codes).or_insert_with(group_of_row)
+                    }
+                    None => group_of_row(),
+                };
+                groups[i].1.update(&args, row)?;
+            }
+        }
+        if groups.is_empty() {
+            return Ok(());
+        }
+        let types = (self.group_by.iter())
+            .map(|e| e.return_type().unwrap())
+            .chain(self.aggs.iter().map(|a| a.return_type.clone()));
+        let rows = groups.into_iter().map(|(mut key, group)| {
+            key.extend(group.output());
+            key
+        });
+        yield build_chunk(types, rows);
+    }
+}
+
+/// The states of the aggregations of a group.
+struct GroupState {
+    states: Vec<AggState>,
+    /// The arguments that have been aggregated by each `DISTINCT` aggregation.
+    seen: Vec<Option<HashSet<Vec<DataValue>>>>,
+}
+
+impl GroupState {
+    fn new(aggs: &[BoundAggCall]) -> Self {
+        GroupState {
+            states: aggs.iter().map(|a| AggState::new(&a.kind)).collect(),
+            seen: aggs.iter().map(|a| a.distinct.then(HashSet::new)).collect(),
+        }
+    }
+
+    fn update(&mut self, args: &[Vec<ArrayImpl>], row: usize) -> Result<(), ExecuteError> {
+        for ((state, seen), args) in self.states.iter_mut().zip(&mut self.seen).zip(args) {
+            let values: Vec<DataValue> = args.iter().map(|a| a.get(row)).collect();
+            if let Some(seen) = seen {
+                if values.iter().any(|v| v.is_null()) || !seen.insert(values.clone()) {
+                    continue;
+                }
+            }
+            state.update(&values)?;
+        }
+        Ok(())
+    }
+
+    fn output(&self) -> Vec<DataValue> {
+        self.states.iter().map(|s| s.output()).collect()
+    }
+}
+
+/// Evaluate the arguments of each aggregation.
+fn eval_args(
+    aggs: &[BoundAggCall],
+    chunk: &DataChunk,
+) -> Result<Vec<Vec<ArrayImpl>>, ExecuteError> {
+    aggs.iter()
+        .map(|a| a.args.iter().map(|e| e.eval_array(chunk)).collect())
+        .collect()
+}
+
+fn build_chunk(
+    types: impl Iterator<Item = DataType>,
+    rows: impl IntoIterator<Item = Vec<DataValue>>,
+) -> DataChunk {
+    let mut builders = types
+        .map(|ty| ArrayBuilderImpl::with_capacity(0, &ty))
+        .collect::<Vec<_>>();
+    for row in rows {
+        for (builder, value) in builders.iter_mut().zip(&row) {
+            builder.push(value);
+        }
+    }
+    builders.into_iter().map(|b| b.finish()).collect()
+}
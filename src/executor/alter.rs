@@ -0,0 +1,51 @@
+use super::*;
+use crate::binder::AlterTableAction;
+use crate::physical_planner::PhysicalAlterTable;
+use crate::storage::StorageRef;
+use crate::types::DataValue;
+
+pub struct AlterTableExecutor {
+    pub plan: PhysicalAlterTable,
+    pub catalog: CatalogRef,
+    pub storage: StorageRef,
+}
+
+impl AlterTableExecutor {
+    /// Change the table in the catalog. Adding or dropping a column also changes the columns
+    /// of the table in the storage, without rewriting the data.
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let table_ref_id = self.plan.table_ref_id;
+        let schema = self.catalog.get_schema(table_ref_id.schema_id).unwrap();
+        let table = schema.get_table(table_ref_id.table_id).unwrap();
+        match self.plan.action {
+            AlterTableAction::AddColumn { name, desc } => {
+                let default = match desc.default() {
+                    Some(expr) => expr.eval_const()?,
+                    None => DataValue::Null,
+                };
+                let column_id = table.add_column(&name, desc).unwrap();
+                let column = table.get_column(column_id).unwrap();
+                self.storage
+                    .get_table(table_ref_id)?
+                    .add_column(column, default)?;
+            }
+            AlterTableAction::DropColumn { column_id } => {
+                if let Some(column_id) = column_id {
+                    table.del_column(column_id);
+                    self.storage
+                        .get_table(table_ref_id)?
+                        .drop_column(column_id)?;
+                }
+            }
+            AlterTableAction::RenameColumn {
+                column_id,
+                new_name,
+            } => table.rename_column(column_id, &new_name).unwrap(),
+            AlterTableAction::RenameTable { new_name } => schema
+                .rename_table(table_ref_id.table_id, &new_name)
+                .unwrap(),
+        }
+        yield DataChunk::single(1);
+    }
+}
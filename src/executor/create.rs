@@ -1,6 +1,9 @@
 use super::*;
-use crate::catalog::TableRefId;
-use crate::physical_planner::PhysicalCreateTable;
+use crate::binder::BoundExpr;
+use crate::catalog::{CheckConstraint, IndexCatalog, TableRefId, UniqueConstraint};
+use crate::physical_planner::{
+    PhysicalCreateIndex, PhysicalCreateSchema, PhysicalCreateTable, PhysicalCreateView,
+};
 use crate::storage::StorageRef;
 
 pub struct CreateTableExecutor {
@@ -15,15 +18,112 @@ impl CreateTableExecutor {
         let schema = self.catalog.get_schema(self.plan.schema_id).unwrap();
         let table_id = schema.add_table(&self.plan.table_name).unwrap();
         let table = schema.get_table(table_id).unwrap();
-        let mut column_descs = vec![];
+        let mut column_ids = vec![];
         for (name, desc) in &self.plan.columns {
-            table.add_column(name, desc.clone()).unwrap();
-            column_descs.push(desc.clone());
+            column_ids.push(table.add_column(name, desc.clone()).unwrap());
         }
+        let mut unique_keys = vec![];
+        for unique in self.plan.uniques {
+            let column_ids = (unique.column_ids.iter())
+                .map(|&idx| column_ids[idx as usize])
+                .collect::<Vec<_>>();
+            unique_keys.push(column_ids.clone());
+            table
+                .add_unique(UniqueConstraint {
+                    column_ids,
+                    ..unique
+                })
+                .unwrap();
+        }
+        for (name, mut expr) in self.plan.checks {
+            // refer to the columns by their ids
+            expr.visit_mut(&mut |e| {
+                if let BoundExpr::ColumnRef(c) = e {
+                    c.index = column_ids[c.index] as usize;
+                }
+            });
+            table.add_check(CheckConstraint { name, expr }).unwrap();
+        }
+        let columns = table.all_columns().into_values().collect::<Vec<_>>();
         self.storage.add_table(
             TableRefId::new(self.plan.schema_id, table_id),
-            &column_descs,
+            &columns,
+            &unique_keys,
         )?;
         yield DataChunk::single(1);
     }
 }
+
+pub struct CreateSchemaExecutor {
+    pub plan: PhysicalCreateSchema,
+    pub catalog: CatalogRef,
+}
+
+impl CreateSchemaExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let exists = self
+            .catalog
+            .get_schema_by_name(&self.plan.schema_name)
+            .is_some();
+        if !(exists && self.plan.if_not_exists) {
+            self.catalog.add_schema(&self.plan.schema_name).unwrap();
+        }
+        yield DataChunk::single(1);
+    }
+}
+
+pub struct CreateIndexExecutor {
+    pub plan: PhysicalCreateIndex,
+    pub catalog: CatalogRef,
+    pub storage: StorageRef,
+}
+
+impl CreateIndexExecutor {
+    /// Add the index to the catalog, and build it in the storage unless the column has been
+    /// indexed by another index.
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let table_ref_id = self.plan.table_ref_id;
+        let schema = self.catalog.get_schema(table_ref_id.schema_id).unwrap();
+        let exists = schema.get_index_by_name(&self.plan.index_name).is_some();
+        if !(exists && self.plan.if_not_exists) {
+            let table = schema.get_table(table_ref_id.table_id).unwrap();
+            let indexes = table.all_indexes();
+            if !indexes.iter().any(|i| i.column_id == self.plan.column_id) {
+                let storage_table = self.storage.get_table(table_ref_id)?;
+                storage_table.create_index(self.plan.column_id).await?;
+            }
+            table
+                .add_index(IndexCatalog {
+                    name: self.plan.index_name,
+                    column_id: self.plan.column_id,
+                })
+                .unwrap();
+        }
+        yield DataChunk::single(1);
+    }
+}
+
+pub struct CreateViewExecutor {
+    pub plan: PhysicalCreateView,
+    pub catalog: CatalogRef,
+}
+
+impl CreateViewExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let schema = self.catalog.get_schema(self.plan.schema_id).unwrap();
+        match schema.get_view_by_name(&self.plan.view_name) {
+            Some(view) if self.plan.or_replace => {
+                schema.replace_view(view.id(), self.plan.definition);
+            }
+            _ => {
+                schema
+                    .add_view(&self.plan.view_name, self.plan.definition)
+                    .unwrap();
+            }
+        }
+        yield DataChunk::single(1);
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use super::*;
+use crate::array::DataChunk;
+
+/// The materialized rows of the CTEs, indexed by CTE id.
+pub type CteTables = Arc<Mutex<HashMap<usize, Vec<DataChunk>>>>;
+
+/// Materializes the CTEs in order, then outputs the rows of `child`.
+///
+/// The CTEs and the child are only built after the CTEs they depend on are materialized.
+pub struct WithExecutor {
+    pub ctes: Vec<(usize, PhysicalPlan)>,
+    pub child: PhysicalPlan,
+    pub builder: ExecutorBuilder,
+}
+
+impl WithExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let ids = self.ctes.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        for (id, plan) in self.ctes {
+            let mut chunks = vec![];
+            #[for_await]
+            for chunk in self.builder.build(plan) {
+                chunks.push(chunk?);
+            }
+            self.builder.ctes.lock().unwrap().insert(id, chunks);
+        }
+        #[for_await]
+        for chunk in self.builder.build(self.child) {
+            yield chunk?;
+        }
+        let mut tables = self.builder.ctes.lock().unwrap();
+        for id in ids {
+            tables.remove(&id);
+        }
+    }
+}
+
+pub struct CteScanExecutor {
+    pub cte_id: usize,
+    pub tables: CteTables,
+}
+
+impl CteScanExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let chunks = self.tables.lock().unwrap()[&self.cte_id].clone();
+        for chunk in chunks {
+            yield chunk;
+        }
+    }
+}
+
+/// Evaluates the recursive term on the rows produced by the previous iteration until no new
+/// rows are produced.
+pub struct RecursiveCteExecutor {
+    pub cte_id: usize,
+    pub base: BoxedExecutor,
+    pub recursive: PhysicalPlan,
+    pub union_all: bool,
+    pub builder: ExecutorBuilder,
+}
+
+impl RecursiveCteExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let mut seen = HashSet::new();
+        let mut input = self.base;
+        loop {
+            // The rows produced by this iteration become the working table of the next one.
+            let mut working = vec![];
+            #[for_await]
+            for chunk in input {
+                let mut chunk = chunk?;
+                if !self.union_all {
+                    let visibility = (0..chunk.capacity())
+                        .map(|i| chunk.is_visible(i) && seen.insert(chunk.row(i)))
+                        .collect::<Vec<_>>();
+                    chunk = chunk.with_visibility(&visibility);
+                }
+                if chunk.cardinality() > 0 {
+                    working.push(chunk.clone());
+                    yield chunk;
+                }
+            }
+            if working.is_empty() {
+                break;
+            }
+            self.builder
+                .ctes
+                .lock()
+                .unwrap()
+                .insert(self.cte_id, working);
+            input = self.builder.build(self.recursive.clone());
+        }
+    }
+}
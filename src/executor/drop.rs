@@ -0,0 +1,91 @@
+use super::*;
+use crate::catalog::TableRefId;
+use crate::physical_planner::{
+    PhysicalDropIndex, PhysicalDropSchema, PhysicalDropTable, PhysicalDropView,
+};
+use crate::storage::StorageRef;
+
+pub struct DropTableExecutor {
+    pub plan: PhysicalDropTable,
+    pub catalog: CatalogRef,
+    pub storage: StorageRef,
+}
+
+impl DropTableExecutor {
+    /// Remove the tables from the storage and the catalog. The data of a table is deleted when
+    /// the transactions reading it have finished.
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        for table_ref_id in &self.plan.table_ref_ids {
+            let schema = (self.catalog.get_schema(table_ref_id.schema_id)).ok_or_else(|| {
+                CatalogError::NotFound("schema", table_ref_id.schema_id.to_string())
+            })?;
+            self.storage.drop_table(*table_ref_id)?;
+            schema.del_table(table_ref_id.table_id)?;
+        }
+        yield DataChunk::single(self.plan.table_ref_ids.len() as i32);
+    }
+}
+
+pub struct DropSchemaExecutor {
+    pub plan: PhysicalDropSchema,
+    pub catalog: CatalogRef,
+    pub storage: StorageRef,
+}
+
+impl DropSchemaExecutor {
+    /// Remove the schemas and their tables from the catalog and the storage.
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        for &schema_id in &self.plan.schema_ids {
+            let schema = self.catalog.get_schema(schema_id).unwrap();
+            for table_id in schema.all_tables().into_keys() {
+                self.storage
+                    .drop_table(TableRefId::new(schema_id, table_id))?;
+            }
+            self.catalog.del_schema(&schema.name()).unwrap();
+        }
+        yield DataChunk::single(self.plan.schema_ids.len() as i32);
+    }
+}
+
+pub struct DropIndexExecutor {
+    pub plan: PhysicalDropIndex,
+    pub catalog: CatalogRef,
+    pub storage: StorageRef,
+}
+
+impl DropIndexExecutor {
+    /// Remove the indexes from the catalog, and from the storage unless their columns are
+    /// still indexed by other indexes.
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        for (table_ref_id, name) in &self.plan.indexes {
+            let schema = self.catalog.get_schema(table_ref_id.schema_id).unwrap();
+            let table = schema.get_table(table_ref_id.table_id).unwrap();
+            let index = table.del_index(name).unwrap();
+            let indexes = table.all_indexes();
+            if !indexes.iter().any(|i| i.column_id == index.column_id) {
+                let storage_table = self.storage.get_table(*table_ref_id)?;
+                storage_table.drop_index(index.column_id).await?;
+            }
+        }
+        yield DataChunk::single(self.plan.indexes.len() as i32);
+    }
+}
+
+pub struct DropViewExecutor {
+    pub plan: PhysicalDropView,
+    pub catalog: CatalogRef,
+}
+
+impl DropViewExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        for view_ref_id in &self.plan.view_ref_ids {
+            let schema = self.catalog.get_schema(view_ref_id.schema_id).unwrap();
+            schema.del_view(view_ref_id.table_id);
+        }
+        yield DataChunk::single(self.plan.view_ref_ids.len() as i32);
+    }
+}
@@ -1,30 +1,224 @@
 use crate::array::*;
+use crate::binder::BoundCase;
 use crate::binder::BoundExpr;
 use crate::executor::ExecuteError;
-use crate::types::DataValue;
+use crate::parser::{BinaryOperator, UnaryOperator};
+use crate::types::{ConvertError, DataValue};
 
 impl BoundExpr {
+    /// Evaluate an expression that does not reference any column.
     pub fn eval_const(&self) -> Result<DataValue, ExecuteError> {
         match &self {
             Self::Constant(v) => Ok(v.clone()),
-            Self::ColumnRef(_) => panic!("can not evaluate on ColumnRef"),
+            _ => Ok(self.eval_array(&DataChunk::single(0))?.get(0)),
         }
     }
 
+    /// Evaluate the expression on each row of the chunk. The values of invisible rows are
+    /// unspecified, but they never raise errors.
     pub fn eval_array(&self, chunk: &DataChunk) -> Result<ArrayImpl, ExecuteError> {
+        match chunk.visibility() {
+            Some(visibility) if self.may_fail() => {
+                let array = self.eval_all_rows(&chunk.clone().compact())?;
+                Ok(array.expand(visibility))
+            }
+            _ => self.eval_all_rows(chunk),
+        }
+    }
+
+    /// Returns true if the expression may raise an error on some rows, e.g. on overflows.
+    pub fn may_fail(&self) -> bool {
+        use BinaryOperator::*;
+        match self {
+            Self::Constant(_) | Self::ColumnRef(_) => false,
+            Self::BinaryOp(e) => {
+                !matches!(e.op, Eq | NotEq | Gt | GtEq | Lt | LtEq | And | Or)
+                    || e.left_expr.may_fail()
+                    || e.right_expr.may_fail()
+            }
+            Self::UnaryOp(e) => !matches!(e.op, UnaryOperator::Not) || e.expr.may_fail(),
+            Self::IsNull(e) => e.expr.may_fail(),
+            Self::Case(e) => (e.conditions.iter().chain(&e.results))
+                .chain(e.else_result.as_deref())
+                .any(|e| e.may_fail()),
+            Self::InList(e) => e.expr.may_fail() || e.list.iter().any(|e| e.may_fail()),
+            Self::Between(e) => e.expr.may_fail() || e.low.may_fail() || e.high.may_fail(),
+            // a constant pattern is compiled before the rows are matched
+            Self::Like(e) => e.expr.may_fail() || !matches!(*e.pattern, Self::Constant(_)),
+            _ => true,
+        }
+    }
+
+    /// Evaluate the expression on all rows of the chunk, including the invisible ones.
+    fn eval_all_rows(&self, chunk: &DataChunk) -> Result<ArrayImpl, ExecuteError> {
         match &self {
-            Self::ColumnRef(v) => Ok(chunk.arrays()[v.column_ref_id.column_id as usize].clone()),
-            Self::Constant(v) => {
-                let mut builder = ArrayBuilderImpl::with_capacity(
-                    chunk.cardinality(),
-                    &self.return_type().unwrap(),
-                );
-                // TODO: optimize this
-                for _ in 0..chunk.cardinality() {
-                    builder.push(v);
+            Self::ColumnRef(v) => {
+                assert_eq!(v.depth, 0, "outer reference should have been decorrelated");
+                Ok(chunk.arrays()[v.index].clone())
+            }
+            Self::Constant(v) => Ok(ArrayImpl::repeat(
+                v,
+                &self.return_type().unwrap(),
+                chunk.capacity(),
+            )),
+            Self::BinaryOp(e) => {
+                let left = e.left_expr.eval_array(chunk)?;
+                let right = e.right_expr.eval_array(chunk)?;
+                Ok(left.binary_op(&e.op, &right)?)
+            }
+            Self::UnaryOp(e) => Ok(e.expr.eval_array(chunk)?.unary_op(&e.op)?),
+            Self::TypeCast(e) => match &*e.expr {
+                // NULL constants have no type before the cast
+                Self::Constant(v) => Ok(ArrayImpl::repeat(
+                    &v.cast(&e.ty.kind())?,
+                    &e.ty,
+                    chunk.capacity(),
+                )),
+                expr => Ok(expr.eval_array(chunk)?.cast(&e.ty)?),
+            },
+            Self::IsNull(e) => {
+                let array = e.expr.eval_array(chunk)?;
+                let is_null = (0..array.len()).map(|i| array.is_null(i) != e.negated);
+                Ok(ArrayImpl::Bool(is_null.collect()))
+            }
+            Self::Case(e) => e.eval_array(chunk),
+            Self::InList(e) => {
+                let expr = e.expr.eval_array(chunk)?;
+                let mut result: Option<ArrayImpl> = None;
+                for item in &e.list {
+                    let eq = expr.binary_op(&BinaryOperator::Eq, &item.eval_array(chunk)?)?;
+                    result = Some(match result {
+                        Some(result) => result.binary_op(&BinaryOperator::Or, &eq)?,
+                        None => eq,
+                    });
                 }
-                Ok(builder.finish())
+                let result = result.expect("IN list must not be empty");
+                Ok(negate_if(result, e.negated)?)
+            }
+            Self::Between(e) => {
+                let expr = e.expr.eval_array(chunk)?;
+                let low = expr.binary_op(&BinaryOperator::GtEq, &e.low.eval_array(chunk)?)?;
+                let high = expr.binary_op(&BinaryOperator::LtEq, &e.high.eval_array(chunk)?)?;
+                let result = low.binary_op(&BinaryOperator::And, &high)?;
+                Ok(negate_if(result, e.negated)?)
             }
+            Self::Like(e) => {
+                let array = e.expr.eval_array(chunk)?;
+                let result = match &*e.pattern {
+                    // compile the pattern only once if it is a constant
+                    Self::Constant(DataValue::String(pattern)) => {
+                        let pattern = LikePattern::new(pattern, e.escape_char, e.case_insensitive)?;
+                        match &array {
+                            // match each distinct string only once
+                            ArrayImpl::Dictionary(a) => {
+                                a.map::<BoolArray, _>(|s| Ok(pattern.matches(s)))?
+                            }
+                            ArrayImpl::Utf8(a) => a.like(&pattern),
+                            _ => panic!("LIKE on non-string"),
+                        }
+                    }
+                    pattern => {
+                        let array = array.decode();
+                        let array: &Utf8Array = (&array).try_into().expect("LIKE on non-string");
+                        let patterns = pattern.eval_array(chunk)?.decode();
+                        let patterns: &Utf8Array = (&patterns).try_into().unwrap();
+                        binary_op(array, patterns, |s, pattern| {
+                            let pattern =
+                                LikePattern::new(pattern, e.escape_char, e.case_insensitive)?;
+                            Ok(pattern.matches(s))
+                        })?
+                    }
+                };
+                Ok(negate_if(ArrayImpl::Bool(result), e.negated)?)
+            }
+            Self::FunctionCall(e) => {
+                // functions take plain string arrays
+                let args = (e.args.iter())
+                    .map(|arg| Ok(arg.eval_array(chunk)?.decode()))
+                    .collect::<Result<Vec<_>, ExecuteError>>()?;
+                let result = (e.func.eval)(&args)?;
+                // user-defined functions may return an array of any type and length
+                let len = args.first().map_or(chunk.capacity(), |a| a.len());
+                let expected = ArrayBuilderImpl::with_capacity(0, &e.return_type).finish();
+                let type_matched = match (&result, &expected) {
+                    (ArrayImpl::Dictionary(_), ArrayImpl::Utf8(_)) => true,
+                    (a, b) => std::mem::discriminant(a) == std::mem::discriminant(b),
+                };
+                if !type_matched || result.len() != len {
+                    return Err(ExecuteError::InvalidFunctionResult(e.func.name.clone()));
+                }
+                Ok(result)
+            }
+            Self::Subquery(_) => panic!("subquery should have been planned as a join"),
+            Self::AggCall(_) => {
+                panic!("aggregate function should have been planned as an aggregate")
+            }
+            Self::Window(_) => panic!("window function should have been planned as a window"),
         }
     }
+
+    /// Evaluate a boolean expression and returns the rows where it is true.
+    pub fn eval_visibility(&self, chunk: &DataChunk) -> Result<Vec<bool>, ExecuteError> {
+        match self.eval_array(chunk)? {
+            ArrayImpl::Bool(a) => Ok(a.iter().map(|v| v == Some(&true)).collect()),
+            _ => panic!("condition must be boolean"),
+        }
+    }
+}
+
+impl BoundCase {
+    /// Evaluate the conditions in order on the rows that no earlier condition holds for, then
+    /// evaluate each result only on the rows that take it, so that a branch that is not taken
+    /// can not raise an error.
+    fn eval_array(&self, chunk: &DataChunk) -> Result<ArrayImpl, ExecuteError> {
+        let len = chunk.capacity();
+        // the index of the branch taken by each row, where `conditions.len()` is ELSE
+        let else_branch = self.conditions.len();
+        let mut branches = vec![else_branch; len];
+        let mut undecided = vec![true; len];
+        for (i, cond) in self.conditions.iter().enumerate() {
+            if !undecided.contains(&true) {
+                break;
+            }
+            let mut visibility = cond.eval_visibility(&chunk.filter(&undecided))?.into_iter();
+            for (row, undecided) in undecided.iter_mut().enumerate() {
+                if *undecided && visibility.next().unwrap() {
+                    *undecided = false;
+                    branches[row] = i;
+                }
+            }
+        }
+
+        let results = self.results.iter().chain(self.else_result.as_deref());
+        let mut arrays = vec![];
+        for (i, result) in results.enumerate() {
+            let visibility = branches.iter().map(|b| *b == i).collect::<Vec<_>>();
+            arrays.push(match visibility.contains(&true) {
+                true => Some(result.eval_array(&chunk.filter(&visibility))?),
+                false => None,
+            });
+        }
+
+        let mut builder = ArrayBuilderImpl::with_capacity(len, &self.return_type);
+        let mut cursors = vec![0; arrays.len()];
+        for branch in branches {
+            match arrays.get(branch) {
+                Some(Some(array)) => {
+                    builder.push(&array.get(cursors[branch]));
+                    cursors[branch] += 1;
+                }
+                // no ELSE
+                _ => builder.push(&DataValue::Null),
+            }
+        }
+        Ok(builder.finish())
+    }
+}
+
+/// Apply `NOT` on a boolean array if `negated` is true.
+fn negate_if(array: ArrayImpl, negated: bool) -> Result<ArrayImpl, ConvertError> {
+    match negated {
+        true => array.unary_op(&UnaryOperator::Not),
+        false => Ok(array),
+    }
 }
@@ -0,0 +1,23 @@
+use super::*;
+use crate::array::DataChunk;
+use crate::binder::BoundExpr;
+
+pub struct FilterExecutor {
+    pub expr: BoundExpr,
+    pub child: BoxedExecutor,
+}
+
+impl FilterExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        #[for_await]
+        for batch in self.child {
+            let batch = batch?;
+            let visibility = self.expr.eval_visibility(&batch)?;
+            let chunk = batch.with_visibility(&visibility);
+            if chunk.cardinality() > 0 {
+                yield chunk;
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use super::*;
+use crate::array::DataChunk;
+use crate::catalog::{ColumnId, TableRefId};
+use crate::storage::IndexRange;
+
+pub struct IndexScanExecutor {
+    pub table_ref_id: TableRefId,
+    pub column_ids: Vec<ColumnId>,
+    pub index_column_id: ColumnId,
+    pub range: IndexRange,
+    pub storage: StorageRef,
+}
+
+impl IndexScanExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let table = self.storage.get_table(self.table_ref_id)?;
+        let txn = table.read().await?;
+
+        let chunks = txn
+            .index_chunks(&self.column_ids, self.index_column_id, &self.range)
+            .await?;
+        for chunk in chunks {
+            yield chunk;
+        }
+
+        txn.commit().await?;
+    }
+}
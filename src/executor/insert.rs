@@ -1,9 +1,10 @@
 use itertools::Itertools;
 
 use super::*;
-use crate::array::{ArrayBuilderImpl, DataChunk};
-use crate::catalog::{ColumnId, TableRefId};
-use crate::storage::StorageRef;
+use crate::array::{Array, ArrayBuilderImpl, ArrayImpl, DataChunk};
+use crate::binder::BoundExpr;
+use crate::catalog::{ColumnId, TableCatalog, TableRefId};
+use crate::storage::{StorageError, StorageRef};
 use crate::types::{DataType, DataValue};
 
 pub struct InsertExecutor {
@@ -24,38 +25,106 @@ impl InsertExecutor {
         //    columns = [0: Int, 1: Bool, 3: Float, 4: String]
         //    column_ids = [4, 1]
         // => output_columns = [Null(Int), Pick(1), Null(Float), Pick(0)]
-        let output_columns = catalog
-            .all_columns()
-            .values()
-            .map(
-                |col| match self.column_ids.iter().position(|&id| id == col.id()) {
-                    Some(index) => Column::Pick { index },
+        let columns = catalog.all_columns();
+        let mut output_columns = vec![];
+        for col in columns.values() {
+            let column = match self.column_ids.iter().position(|&id| id == col.id()) {
+                Some(index) => Column::Pick { index },
+                None => match col.desc().default() {
+                    Some(expr) => Column::Default {
+                        value: expr.eval_const()?,
+                        type_: col.datatype(),
+                    },
                     None => Column::Null {
                         type_: col.datatype(),
                     },
                 },
-            )
+            };
+            output_columns.push(column);
+        }
+        // The check constraints on the output chunks, whose column references are the ids of
+        // the columns, are changed to refer to the positions of the columns.
+        let column_ids = columns.keys().copied().collect_vec();
+        let checks = catalog
+            .all_checks()
+            .into_iter()
+            .map(|mut check| {
+                check.expr.visit_mut(&mut |e| {
+                    if let BoundExpr::ColumnRef(c) = e {
+                        c.index = column_ids.binary_search(&(c.index as ColumnId)).unwrap();
+                    }
+                });
+                check
+            })
             .collect_vec();
         let mut count = 0;
         let mut txn = table.write().await?;
 
         #[for_await]
         for chunk in self.child {
-            let chunk = transform_chunk(chunk?, &output_columns);
+            let chunk = transform_chunk(chunk?.compact(), &output_columns);
+            // values are checked in the binder, but the rows of a query may have nulls
+            for (col, array) in columns.values().zip(chunk.arrays()) {
+                if !col.is_nullable() && (0..array.len()).any(|i| array.is_null(i)) {
+                    return Err(ExecuteError::NotNullViolation {
+                        column: col.name().into(),
+                    });
+                }
+            }
+            for check in &checks {
+                let ArrayImpl::Bool(result) = check.expr.eval_array(&chunk)? else {
+                    panic!("check constraint must be boolean");
+                };
+                // rows where the expression is null pass the check
+                if result.iter().any(|v| v == Some(&false)) {
+                    return Err(ExecuteError::CheckViolation {
+                        table: catalog.name(),
+                        constraint: check.name.clone(),
+                    });
+                }
+            }
             count += chunk.cardinality();
-            txn.append(chunk).await?;
+            txn.append(chunk)
+                .await
+                .map_err(|e| unique_violation(&catalog, e))?;
         }
-        txn.commit().await?;
+        txn.commit()
+            .await
+            .map_err(|e| unique_violation(&catalog, e))?;
 
         yield DataChunk::single(count as i32);
     }
 }
 
+/// Report a duplicate key in the storage as a violation of the unique constraint on its
+/// columns.
+fn unique_violation(catalog: &TableCatalog, error: StorageError) -> ExecuteError {
+    let StorageError::DuplicateKey { column_ids, key } = error else {
+        return error.into();
+    };
+    let unique = (catalog.all_uniques().into_iter())
+        .find(|unique| unique.column_ids == column_ids)
+        .expect("no unique constraint on the key");
+    let columns = (column_ids.iter())
+        .map(|&id| catalog.get_column(id).unwrap().name().to_string())
+        .join(", ");
+    ExecuteError::UniqueViolation {
+        constraint: unique.name,
+        key: format!(
+            "({})=({})",
+            columns,
+            key.iter().map(|v| v.to_string()).join(", ")
+        ),
+    }
+}
+
 enum Column {
     /// Pick the column at `index` from child.
     Pick { index: usize },
     /// Null values with `type`.
     Null { type_: DataType },
+    /// The default value of the column with `type`.
+    Default { value: DataValue, type_: DataType },
 }
 
 fn transform_chunk(chunk: DataChunk, output_columns: &[Column]) -> DataChunk {
@@ -70,6 +139,13 @@ fn transform_chunk(chunk: DataChunk, output_columns: &[Column]) -> DataChunk {
                 }
                 builder.finish()
             }
+            Column::Default { value, type_ } => {
+                let mut builder = ArrayBuilderImpl::with_capacity(chunk.cardinality(), type_);
+                for _ in 0..chunk.cardinality() {
+                    builder.push(value);
+                }
+                builder.finish()
+            }
         })
         .collect()
 }
@@ -0,0 +1,74 @@
+use super::*;
+use crate::catalog::{SchemaId, TableRefId, ViewDefinition};
+use crate::storage::StorageRef;
+use crate::types::DataTypeExt;
+
+pub struct CreateMaterializedViewExecutor {
+    pub schema_id: SchemaId,
+    pub view_name: String,
+    pub definition: ViewDefinition,
+    pub catalog: CatalogRef,
+    pub storage: StorageRef,
+    pub child: BoxedExecutor,
+}
+
+impl CreateMaterializedViewExecutor {
+    /// Run the query, and create the view with its output. The view is not created if the
+    /// query fails.
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let mut chunks = vec![];
+        #[for_await]
+        for chunk in self.child {
+            chunks.push(chunk?.compact());
+        }
+        let schema = self.catalog.get_schema(self.schema_id).unwrap();
+        let table_id = schema.add_table(&self.view_name).unwrap();
+        let table = schema.get_table(table_id).unwrap();
+        for (name, ty) in &self.definition.columns {
+            // the columns of a materialized view have no constraints
+            table
+                .add_column(name, ty.kind().nullable().to_column())
+                .unwrap();
+        }
+        table.set_view_definition(self.definition);
+        let table_ref_id = TableRefId::new(self.schema_id, table_id);
+        let columns = table.all_columns().into_values().collect::<Vec<_>>();
+        self.storage.add_table(table_ref_id, &columns, &[])?;
+
+        let storage_table = self.storage.get_table(table_ref_id)?;
+        let mut count = 0;
+        let mut txn = storage_table.write().await?;
+        for chunk in chunks {
+            count += chunk.cardinality();
+            txn.append(chunk).await?;
+        }
+        txn.commit().await?;
+        yield DataChunk::single(count as i32);
+    }
+}
+
+pub struct RefreshMaterializedViewExecutor {
+    pub table_ref_id: TableRefId,
+    pub storage: StorageRef,
+    pub child: BoxedExecutor,
+}
+
+impl RefreshMaterializedViewExecutor {
+    /// Run the query, and replace the rows of the view with its output when it finishes. The
+    /// transactions that began before keep reading the old rows.
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let table = self.storage.get_table(self.table_ref_id)?;
+        let mut count = 0;
+        let mut txn = table.overwrite().await?;
+        #[for_await]
+        for chunk in self.child {
+            let chunk = chunk?.compact();
+            count += chunk.cardinality();
+            txn.append(chunk).await?;
+        }
+        txn.commit().await?;
+        yield DataChunk::single(count as i32);
+    }
+}
@@ -2,26 +2,50 @@ use futures::stream::{BoxStream, StreamExt};
 use futures_async_stream::try_stream;
 
 use crate::array::DataChunk;
-use crate::catalog::CatalogRef;
+use crate::catalog::{CatalogError, CatalogRef};
 use crate::physical_planner::PhysicalPlan;
 use crate::storage::{StorageError, StorageRef};
+use crate::types::ConvertError;
 
+mod agg_state;
+mod aggregate;
+mod alter;
 mod create;
+mod cte;
+mod drop;
 mod dummy;
 mod evaluator;
 mod explain;
+mod filter;
+mod index_scan;
 mod insert;
+mod materialized_view;
+mod nested_loop_join;
 mod projection;
 mod seq_scan;
+mod set_operation;
+mod truncate;
 mod values;
+mod window;
 
+use self::aggregate::*;
+use self::alter::*;
 use self::create::*;
+use self::cte::*;
+use self::drop::*;
 use self::dummy::*;
 use self::explain::*;
+use self::filter::*;
+use self::index_scan::*;
 use self::insert::*;
+use self::materialized_view::*;
+use self::nested_loop_join::*;
 use self::projection::*;
 use self::seq_scan::*;
+use self::set_operation::*;
+use self::truncate::*;
 use self::values::*;
+use self::window::*;
 
 const PROCESSING_WINDOW_SIZE: usize = 1024;
 
@@ -29,6 +53,23 @@ const PROCESSING_WINDOW_SIZE: usize = 1024;
 pub enum ExecuteError {
     #[error("storage error: {0}")]
     Storage(#[from] StorageError),
+    #[error("catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+    #[error("convert error: {0}")]
+    Convert(#[from] ConvertError),
+    #[error("function {0} returned an array of the wrong type or length")]
+    InvalidFunctionResult(String),
+    #[error("new row for relation {table} violates check constraint {constraint}")]
+    CheckViolation { table: String, constraint: String },
+    /// A row has the same key as another row, which is formatted as `(a, b)=(1, 2)`.
+    #[error(
+        "duplicate key value violates unique constraint {constraint}: key {key} already exists"
+    )]
+    UniqueViolation { constraint: String, key: String },
+    #[error("more than one row returned by a subquery used as an expression")]
+    SubqueryTooManyRows,
+    #[error("null value in column {column} violates not-null constraint")]
+    NotNullViolation { column: String },
 }
 
 pub trait Executor {
@@ -37,9 +78,11 @@ pub trait Executor {
 
 pub type BoxedExecutor = BoxStream<'static, Result<DataChunk, ExecuteError>>;
 
+#[derive(Clone)]
 pub struct ExecutorBuilder {
     catalog: CatalogRef,
     storage: StorageRef,
+    ctes: CteTables,
 
     handle: Option<tokio::runtime::Handle>,
 }
@@ -53,6 +96,7 @@ impl ExecutorBuilder {
         ExecutorBuilder {
             catalog,
             storage,
+            ctes: CteTables::default(),
             handle,
         }
     }
@@ -67,6 +111,71 @@ impl ExecutorBuilder {
                 storage: self.storage.clone(),
             }
             .execute(),
+            PhysicalCreateSchema(plan) => CreateSchemaExecutor {
+                plan,
+                catalog: self.catalog.clone(),
+            }
+            .execute(),
+            PhysicalDropSchema(plan) => DropSchemaExecutor {
+                plan,
+                catalog: self.catalog.clone(),
+                storage: self.storage.clone(),
+            }
+            .execute(),
+            PhysicalCreateIndex(plan) => CreateIndexExecutor {
+                plan,
+                catalog: self.catalog.clone(),
+                storage: self.storage.clone(),
+            }
+            .execute(),
+            PhysicalDropIndex(plan) => DropIndexExecutor {
+                plan,
+                catalog: self.catalog.clone(),
+                storage: self.storage.clone(),
+            }
+            .execute(),
+            PhysicalCreateView(plan) => CreateViewExecutor {
+                plan,
+                catalog: self.catalog.clone(),
+            }
+            .execute(),
+            PhysicalDropView(plan) => DropViewExecutor {
+                plan,
+                catalog: self.catalog.clone(),
+            }
+            .execute(),
+            PhysicalCreateMaterializedView(plan) => CreateMaterializedViewExecutor {
+                schema_id: plan.schema_id,
+                view_name: plan.view_name,
+                definition: plan.definition,
+                catalog: self.catalog.clone(),
+                storage: self.storage.clone(),
+                child: self.build(*plan.child),
+            }
+            .execute(),
+            PhysicalRefreshMaterializedView(plan) => RefreshMaterializedViewExecutor {
+                table_ref_id: plan.table_ref_id,
+                storage: self.storage.clone(),
+                child: self.build(*plan.child),
+            }
+            .execute(),
+            PhysicalTruncate(plan) => TruncateExecutor {
+                plan,
+                storage: self.storage.clone(),
+            }
+            .execute(),
+            PhysicalDropTable(plan) => DropTableExecutor {
+                plan,
+                catalog: self.catalog.clone(),
+                storage: self.storage.clone(),
+            }
+            .execute(),
+            PhysicalAlterTable(plan) => AlterTableExecutor {
+                plan,
+                catalog: self.catalog.clone(),
+                storage: self.storage.clone(),
+            }
+            .execute(),
             PhysicalInsert(plan) => InsertExecutor {
                 table_ref_id: plan.table_ref_id,
                 column_ids: plan.column_ids,
@@ -88,11 +197,89 @@ impl ExecutorBuilder {
                 storage: self.storage.clone(),
             }
             .execute(),
+            PhysicalIndexScan(plan) => IndexScanExecutor {
+                table_ref_id: plan.table_ref_id,
+                column_ids: plan.column_ids,
+                index_column_id: plan.index_column_id,
+                range: plan.range,
+                storage: self.storage.clone(),
+            }
+            .execute(),
             PhysicalProjection(plan) => ProjectionExecutor {
                 exprs: plan.exprs,
                 child: self.build(*plan.child),
             }
             .execute(),
+            PhysicalFilter(plan) => FilterExecutor {
+                expr: plan.expr,
+                child: self.build(*plan.child),
+            }
+            .execute(),
+            PhysicalNestedLoopJoin(plan) => NestedLoopJoinExecutor {
+                left_child: self.build(*plan.left),
+                right_child: self.build(*plan.right),
+                join_type: plan.join_type,
+                condition: plan.condition,
+                left_types: plan.left_types,
+                right_types: plan.right_types,
+            }
+            .execute(),
+            PhysicalUnion(plan) => UnionExecutor {
+                left_child: self.build(*plan.left),
+                right_child: self.build(*plan.right),
+            }
+            .execute(),
+            PhysicalHashDistinct(plan) => HashDistinctExecutor {
+                child: self.build(*plan.child),
+            }
+            .execute(),
+            PhysicalHashIntersect(plan) => HashIntersectExecutor {
+                left_child: self.build(*plan.left),
+                right_child: self.build(*plan.right),
+                all: plan.all,
+            }
+            .execute(),
+            PhysicalHashExcept(plan) => HashExceptExecutor {
+                left_child: self.build(*plan.left),
+                right_child: self.build(*plan.right),
+                all: plan.all,
+            }
+            .execute(),
+            PhysicalWindow(plan) => WindowExecutor {
+                windows: plan.windows,
+                child: self.build(*plan.child),
+            }
+            .execute(),
+            PhysicalSimpleAgg(plan) => SimpleAggExecutor {
+                aggs: plan.aggs,
+                child: self.build(*plan.child),
+            }
+            .execute(),
+            PhysicalHashAgg(plan) => HashAggExecutor {
+                aggs: plan.aggs,
+                group_by: plan.group_by,
+                child: self.build(*plan.child),
+            }
+            .execute(),
+            PhysicalWith(plan) => WithExecutor {
+                ctes: plan.ctes,
+                child: *plan.child,
+                builder: self.clone(),
+            }
+            .execute(),
+            PhysicalCteScan(plan) => CteScanExecutor {
+                cte_id: plan.cte_id,
+                tables: self.ctes.clone(),
+            }
+            .execute(),
+            PhysicalRecursiveCte(plan) => RecursiveCteExecutor {
+                cte_id: plan.cte_id,
+                base: self.build(*plan.base),
+                recursive: *plan.recursive,
+                union_all: plan.union_all,
+                builder: self.clone(),
+            }
+            .execute(),
         };
 
         if let Some(handle) = &self.handle {
@@ -0,0 +1,117 @@
+use super::*;
+use crate::array::{ArrayBuilderImpl, ArrayImpl, DataChunk};
+use crate::binder::{BoundExpr, JoinType};
+use crate::types::{DataType, DataValue};
+
+/// Joins each row on the left with all rows on the right, which are kept in memory.
+pub struct NestedLoopJoinExecutor {
+    pub left_child: BoxedExecutor,
+    pub right_child: BoxedExecutor,
+    pub join_type: JoinType,
+    pub condition: BoundExpr,
+    pub left_types: Vec<DataType>,
+    pub right_types: Vec<DataType>,
+}
+
+impl NestedLoopJoinExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let mut right_chunks = vec![];
+        #[for_await]
+        for chunk in self.right_child {
+            right_chunks.push(chunk?);
+        }
+        let right = if right_chunks.is_empty() {
+            builders(&self.right_types, 0)
+                .into_iter()
+                .map(|b| b.finish())
+                .collect()
+        } else {
+            DataChunk::concat(&right_chunks)
+        };
+        let mut right_matched = vec![false; right.cardinality()];
+        let null_row = |types: &[DataType]| vec![DataValue::Null; types.len()];
+
+        #[for_await]
+        for left in self.left_child {
+            let left = left?.compact();
+            let mut left_visible = vec![false; left.cardinality()];
+            let mut builders = builders(
+                &[&self.left_types[..], &self.right_types[..]].concat(),
+                PROCESSING_WINDOW_SIZE,
+            );
+            for (i, visible) in left_visible.iter_mut().enumerate() {
+                let row = left.row(i);
+                let joined = join_row(&row, &self.left_types, &right);
+                let matched = self.condition.eval_visibility(&joined)?;
+                let any_matched = matched.iter().any(|m| *m);
+                if self.join_type == JoinType::LeftSingle
+                    && matched.iter().filter(|m| **m).count() > 1
+                {
+                    return Err(ExecuteError::SubqueryTooManyRows);
+                }
+                match self.join_type {
+                    JoinType::Semi => *visible = any_matched,
+                    JoinType::Anti => *visible = !any_matched,
+                    _ => {
+                        for (right_matched, m) in right_matched.iter_mut().zip(&matched) {
+                            *right_matched |= m;
+                        }
+                        let joined = joined.filter(&matched);
+                        for (builder, array) in builders.iter_mut().zip(joined.arrays()) {
+                            builder.append(array);
+                        }
+                        let outer = matches!(
+                            self.join_type,
+                            JoinType::LeftOuter | JoinType::FullOuter | JoinType::LeftSingle
+                        );
+                        if !any_matched && outer {
+                            let values = row.iter().cloned().chain(null_row(&self.right_types));
+                            for (builder, value) in builders.iter_mut().zip(values) {
+                                builder.push(&value);
+                            }
+                        }
+                    }
+                }
+            }
+            let chunk = match self.join_type {
+                JoinType::Semi | JoinType::Anti => left.filter(&left_visible),
+                _ => builders.into_iter().map(|b| b.finish()).collect(),
+            };
+            if chunk.cardinality() > 0 {
+                yield chunk;
+            }
+        }
+
+        if matches!(self.join_type, JoinType::RightOuter | JoinType::FullOuter) {
+            let mut builders = builders(
+                &[&self.left_types[..], &self.right_types[..]].concat(),
+                PROCESSING_WINDOW_SIZE,
+            );
+            for (j, _) in right_matched.iter().enumerate().filter(|(_, m)| !**m) {
+                let values = null_row(&self.left_types).into_iter().chain(right.row(j));
+                for (builder, value) in builders.iter_mut().zip(values) {
+                    builder.push(&value);
+                }
+            }
+            let chunk: DataChunk = builders.into_iter().map(|b| b.finish()).collect();
+            if chunk.cardinality() > 0 {
+                yield chunk;
+            }
+        }
+    }
+}
+
+fn builders(types: &[DataType], capacity: usize) -> Vec<ArrayBuilderImpl> {
+    types
+        .iter()
+        .map(|ty| ArrayBuilderImpl::with_capacity(capacity, ty))
+        .collect()
+}
+
+/// Concatenate `row` repeated for each row of `right` with `right`.
+fn join_row(row: &[DataValue], types: &[DataType], right: &DataChunk) -> DataChunk {
+    let left = (row.iter().zip(types))
+        .map(|(value, ty)| ArrayImpl::repeat(value, ty, right.cardinality()));
+    left.chain(right.arrays().iter().cloned()).collect()
+}
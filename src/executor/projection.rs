@@ -13,12 +13,17 @@ impl ProjectionExecutor {
         #[for_await]
         for batch in self.child {
             let batch = batch?;
-            let chunk = self
+            let arrays = self
                 .exprs
                 .iter()
                 .map(|expr| expr.eval_array(&batch))
-                .collect::<Result<DataChunk, _>>()?;
-            yield chunk;
+                .collect::<Result<Vec<_>, _>>()?;
+            // the rows that are filtered out stay invisible
+            let chunk: DataChunk = arrays.into_iter().collect();
+            match batch.visibility() {
+                Some(visibility) => yield chunk.with_visibility(visibility),
+                None => yield chunk,
+            }
         }
     }
 }
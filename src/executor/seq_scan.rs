@@ -13,8 +13,8 @@ impl SeqScanExecutor {
     pub async fn execute(self) {
         let table = self.storage.get_table(self.table_ref_id)?;
         let txn = table.read().await?;
-        
-        for chunk in txn.all_chunks().await? {
+
+        for chunk in txn.all_chunks(&self.column_ids).await? {
             yield chunk;
         }
 
@@ -0,0 +1,162 @@
+use std::collections::{HashMap, HashSet};
+
+use super::*;
+use crate::array::DataChunk;
+use crate::types::DataValue;
+
+pub struct UnionExecutor {
+    pub left_child: BoxedExecutor,
+    pub right_child: BoxedExecutor,
+}
+
+impl UnionExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        #[for_await]
+        for chunk in self.left_child {
+            yield chunk?;
+        }
+        #[for_await]
+        for chunk in self.right_child {
+            yield chunk?;
+        }
+    }
+}
+
+pub struct HashDistinctExecutor {
+    pub child: BoxedExecutor,
+}
+
+impl HashDistinctExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let mut seen = HashSet::new();
+        #[for_await]
+        for chunk in self.child {
+            let chunk = chunk?;
+            let visibility = (0..chunk.capacity())
+                .map(|i| chunk.is_visible(i) && seen.insert(chunk.row(i)))
+                .collect::<Vec<_>>();
+            let chunk = chunk.with_visibility(&visibility);
+            if chunk.cardinality() > 0 {
+                yield chunk;
+            }
+        }
+    }
+}
+
+pub struct HashIntersectExecutor {
+    pub left_child: BoxedExecutor,
+    pub right_child: BoxedExecutor,
+    pub all: bool,
+}
+
+impl HashIntersectExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let mut right = RowCounts::new();
+        #[for_await]
+        for chunk in self.right_child {
+            right.add_chunk(&chunk?);
+        }
+        #[for_await]
+        for chunk in self.left_child {
+            let chunk = chunk?;
+            // Each row on the right side can be matched at most once with `ALL`.
+            // Otherwise it is removed after the first match.
+            let visibility = (0..chunk.capacity())
+                .map(|i| {
+                    if !chunk.is_visible(i) {
+                        return false;
+                    }
+                    let row = chunk.row(i);
+                    if self.all {
+                        right.remove_one(&row)
+                    } else {
+                        right.remove_all(&row)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let chunk = chunk.with_visibility(&visibility);
+            if chunk.cardinality() > 0 {
+                yield chunk;
+            }
+        }
+    }
+}
+
+pub struct HashExceptExecutor {
+    pub left_child: BoxedExecutor,
+    pub right_child: BoxedExecutor,
+    pub all: bool,
+}
+
+impl HashExceptExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let mut right = RowCounts::new();
+        #[for_await]
+        for chunk in self.right_child {
+            right.add_chunk(&chunk?);
+        }
+        #[for_await]
+        for chunk in self.left_child {
+            let chunk = chunk?;
+            // Each row on the right side removes at most one row on the left side with `ALL`.
+            // Otherwise an output row is added to the right side to remove its duplicates.
+            let visibility = (0..chunk.capacity())
+                .map(|i| {
+                    if !chunk.is_visible(i) {
+                        return false;
+                    }
+                    let row = chunk.row(i);
+                    if self.all {
+                        !right.remove_one(&row)
+                    } else {
+                        right.insert_new(row)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let chunk = chunk.with_visibility(&visibility);
+            if chunk.cardinality() > 0 {
+                yield chunk;
+            }
+        }
+    }
+}
+
+/// A multiset of rows.
+struct RowCounts(HashMap<Vec<DataValue>, usize>);
+
+impl RowCounts {
+    fn new() -> Self {
+        RowCounts(HashMap::new())
+    }
+
+    fn add_chunk(&mut self, chunk: &DataChunk) {
+        for i in chunk.visible_rows() {
+            *self.0.entry(chunk.row(i)).or_default() += 1;
+        }
+    }
+
+    /// Remove one occurrence of `row`. Returns false if there is none.
+    fn remove_one(&mut self, row: &[DataValue]) -> bool {
+        match self.0.get_mut(row) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove all occurrences of `row`. Returns false if there is none.
+    fn remove_all(&mut self, row: &[DataValue]) -> bool {
+        self.0.remove(row).is_some()
+    }
+
+    /// Insert `row` if it does not exist. Returns false if it exists.
+    fn insert_new(&mut self, row: Vec<DataValue>) -> bool {
+        self.0.insert(row, 1).is_none()
+    }
+}
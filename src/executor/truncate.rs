@@ -0,0 +1,21 @@
+use super::*;
+use crate::physical_planner::PhysicalTruncate;
+use crate::storage::StorageRef;
+
+pub struct TruncateExecutor {
+    pub plan: PhysicalTruncate,
+    pub storage: StorageRef,
+}
+
+impl TruncateExecutor {
+    /// Remove all rows of the tables. The files of the rows are deleted when the transactions
+    /// reading them have finished.
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        for &table_ref_id in &self.plan.table_ref_ids {
+            let table = self.storage.get_table(table_ref_id)?;
+            table.truncate().await?;
+        }
+        yield DataChunk::single(self.plan.table_ref_ids.len() as i32);
+    }
+}
@@ -0,0 +1,196 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use super::agg_state::AggState;
+use super::*;
+use crate::array::{ArrayBuilderImpl, ArrayImpl, DataChunk};
+use crate::binder::{BoundExpr, BoundWindow, FrameBound, WindowFunc};
+use crate::types::DataValue;
+
+/// Appends the results of the window functions to the rows of the child.
+///
+/// All input rows are materialized. For each window function, the rows are sorted by the
+/// partition keys and the order keys, then each partition is scanned in order.
+pub struct WindowExecutor {
+    pub windows: Vec<BoundWindow>,
+    pub child: BoxedExecutor,
+}
+
+impl WindowExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecuteError)]
+    pub async fn execute(self) {
+        let mut chunks = vec![];
+        #[for_await]
+        for chunk in self.child {
+            chunks.push(chunk?);
+        }
+        if chunks.is_empty() {
+            return Ok(());
+        }
+        let input = DataChunk::concat(&chunks);
+        let mut arrays = input.arrays().to_vec();
+        for window in self.windows.iter() {
+            arrays.push(eval_window(window, &input)?);
+        }
+        yield arrays.into_iter().collect();
+    }
+}
+
+/// Evaluate a window function on all rows of `input`.
+fn eval_window(window: &BoundWindow, input: &DataChunk) -> Result<ArrayImpl, ExecuteError> {
+    let eval = |exprs: &mut dyn Iterator<Item = &BoundExpr>| {
+        exprs
+            .map(|e| e.eval_array(input))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let args = eval(&mut window.args.iter())?;
+    let partition_by = eval(&mut window.partition_by.iter())?;
+    let order_by = eval(&mut window.order_by.iter().map(|o| &o.expr))?;
+    let row_of = |arrays: &[ArrayImpl], i: usize| arrays.iter().map(|a| a.get(i)).collect();
+
+    let partition_keys: Vec<Vec<DataValue>> = (0..input.cardinality())
+        .map(|i| row_of(&partition_by, i))
+        .collect();
+    let order_keys: Vec<Vec<DataValue>> = (0..input.cardinality())
+        .map(|i| row_of(&order_by, i))
+        .collect();
+    let mut sorted = (0..input.cardinality()).collect::<Vec<_>>();
+    sorted.sort_by(|&a, &b| {
+        let partition = (partition_keys[a].iter().zip(&partition_keys[b]))
+            .map(|(a, b)| compare(a, b, false, false));
+        let order = (order_keys[a].iter().zip(&order_keys[b]))
+            .zip(&window.order_by)
+            .map(|((a, b), o)| compare(a, b, o.desc, o.nulls_first));
+        partition
+            .chain(order)
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut results = vec![DataValue::Null; input.cardinality()];
+    let value = |arg: usize, row: usize| args[arg].get(row);
+    for partition in runs(&sorted, |a, b| partition_keys[a] == partition_keys[b]) {
+        let rows = &sorted[partition];
+        // The range of the peers of each row, i.e. the rows with equal order keys.
+        let mut peers = vec![];
+        for group in runs(rows, |a, b| order_keys[a] == order_keys[b]) {
+            peers.extend(std::iter::repeat(group.clone()).take(group.len()));
+        }
+        let frame = |i: usize| {
+            let frame = &window.frame;
+            let len = rows.len();
+            let start = match frame.start {
+                FrameBound::UnboundedPreceding => 0,
+                FrameBound::Preceding(n) => i.saturating_sub(n),
+                FrameBound::CurrentRow if frame.rows => i,
+                FrameBound::CurrentRow => peers[i].start,
+                FrameBound::Following(n) => (i + n).min(len),
+                FrameBound::UnboundedFollowing => len,
+            };
+            let end = match frame.end {
+                FrameBound::UnboundedPreceding => 0,
+                FrameBound::Preceding(n) => (i + 1).saturating_sub(n),
+                FrameBound::CurrentRow if frame.rows => i + 1,
+                FrameBound::CurrentRow => peers[i].end,
+                FrameBound::Following(n) => (i + n + 1).min(len),
+                FrameBound::UnboundedFollowing => len,
+            };
+            start..end.max(start)
+        };
+
+        let mut dense_rank = 0;
+        // The state of the aggregation over the rows before `added`, if the frame starts at
+        // the beginning of the partition.
+        let mut running = None;
+        let mut added = 0;
+        for (i, &row) in rows.iter().enumerate() {
+            if peers[i].start == i {
+                dense_rank += 1;
+            }
+            results[row] = match &window.func {
+                WindowFunc::RowNumber => DataValue::Int32(i as i32 + 1),
+                WindowFunc::Rank => DataValue::Int32(peers[i].start as i32 + 1),
+                WindowFunc::DenseRank => DataValue::Int32(dense_rank),
+                WindowFunc::Lag { offset } => match i.checked_sub(*offset) {
+                    Some(j) => value(0, rows[j]),
+                    None if args.len() > 1 => value(1, row),
+                    None => DataValue::Null,
+                },
+                WindowFunc::Lead { offset } => match rows.get(i + offset) {
+                    Some(&j) => value(0, j),
+                    None if args.len() > 1 => value(1, row),
+                    None => DataValue::Null,
+                },
+                WindowFunc::FirstValue => match frame(i) {
+                    f if f.is_empty() => DataValue::Null,
+                    f => value(0, rows[f.start]),
+                },
+                WindowFunc::LastValue => match frame(i) {
+                    f if f.is_empty() => DataValue::Null,
+                    f => value(0, rows[f.end - 1]),
+                },
+                WindowFunc::Agg(kind) => {
+                    let arg = |j: usize| {
+                        (0..args.len())
+                            .map(|k| value(k, rows[j]))
+                            .collect::<Vec<_>>()
+                    };
+                    let frame = frame(i);
+                    if window.frame.start == FrameBound::UnboundedPreceding {
+                        // The end of the frame never moves backward.
+                        let state = running.get_or_insert_with(|| AggState::new(kind));
+                        for j in added..frame.end {
+                            state.update(&arg(j))?;
+                        }
+                        added = added.max(frame.end);
+                        state.output()
+                    } else {
+                        let mut state = AggState::new(kind);
+                        for j in frame {
+                            state.update(&arg(j))?;
+                        }
+                        state.output()
+                    }
+                }
+            };
+        }
+    }
+
+    let mut builder = ArrayBuilderImpl::with_capacity(results.len(), &window.return_type);
+    for value in results.iter() {
+        builder.push(value);
+    }
+    Ok(builder.finish())
+}
+
+/// Compare two values in the given order.
+fn compare(a: &DataValue, b: &DataValue, desc: bool, nulls_first: bool) -> Ordering {
+    match (a.is_null(), b.is_null()) {
+        (true, true) => Ordering::Equal,
+        (true, false) if nulls_first => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        (false, true) if nulls_first => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => {
+            let order = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+            if desc {
+                order.reverse()
+            } else {
+                order
+            }
+        }
+    }
+}
+
+/// Split `rows` into runs of adjacent rows on which `same` holds.
+fn runs(rows: &[usize], same: impl Fn(usize, usize) -> bool) -> Vec<Range<usize>> {
+    let mut runs = vec![];
+    let mut start = 0;
+    for i in 1..=rows.len() {
+        if i == rows.len() || !same(rows[i - 1], rows[i]) {
+            runs.push(start..i);
+            start = i;
+        }
+    }
+    runs
+}
@@ -0,0 +1,431 @@
+//! The built-in scalar functions.
+
+use std::borrow::Borrow;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::RoundingStrategy;
+
+use super::*;
+use crate::array::*;
+use crate::types::{DataValue, Decimal, ExactNumberInfo, TemporalField, TimezoneInfo};
+
+const SMALLINT: DataTypeKind = DataTypeKind::SmallInt(None);
+const INT: DataTypeKind = DataTypeKind::Int(None);
+const BIGINT: DataTypeKind = DataTypeKind::BigInt(None);
+const DOUBLE: DataTypeKind = DataTypeKind::Double;
+const DECIMAL: DataTypeKind = DataTypeKind::Decimal(ExactNumberInfo::None);
+const VARCHAR: DataTypeKind = DataTypeKind::Varchar(None);
+const BOOLEAN: DataTypeKind = DataTypeKind::Boolean;
+const DATE: DataTypeKind = DataTypeKind::Date;
+const TIMESTAMP: DataTypeKind = DataTypeKind::Timestamp(None, TimezoneInfo::None);
+const INTERVAL: DataTypeKind = DataTypeKind::Interval;
+const BYTEA: DataTypeKind = DataTypeKind::Bytea;
+
+pub(super) fn register(registry: &FunctionRegistry) {
+    let add = |name: &str, args: &[DataTypeKind], ret: DataTypeKind, f: ScalarFn| {
+        registry.register_scalar(ScalarFunction {
+            name: name.into(),
+            arg_types: args.to_vec(),
+            variadic: false,
+            return_type: ret,
+            eval: f,
+        })
+    };
+
+    // math
+    add(
+        "abs",
+        &[SMALLINT],
+        SMALLINT,
+        unary::<I16Array, I16Array>(|a| a.checked_abs().ok_or(ConvertError::Overflow)),
+    );
+    add(
+        "abs",
+        &[INT],
+        INT,
+        unary::<I32Array, I32Array>(|a| a.checked_abs().ok_or(ConvertError::Overflow)),
+    );
+    add(
+        "abs",
+        &[BIGINT],
+        BIGINT,
+        unary::<I64Array, I64Array>(|a| a.checked_abs().ok_or(ConvertError::Overflow)),
+    );
+    add("abs", &[DOUBLE], DOUBLE, f64_unary(|a| Ok(a.abs())));
+    add("abs", &[DECIMAL], DECIMAL, decimal_unary(|a| Ok(a.abs())));
+    add(
+        "round",
+        &[DOUBLE],
+        DOUBLE,
+        f64_unary(|a| Ok(round_ties_even(a))),
+    );
+    add(
+        "round",
+        &[DECIMAL],
+        DECIMAL,
+        decimal_unary(|a| Ok(round(a, 0))),
+    );
+    add(
+        "round",
+        &[DOUBLE, INT],
+        DOUBLE,
+        binary::<F64Array, I32Array, F64Array>(|a, n| {
+            let scale = 10f64.powi(*n);
+            if !scale.is_finite() || scale == 0.0 {
+                return Ok(*a);
+            }
+            Ok(round_ties_even(a * scale) / scale)
+        }),
+    );
+    add(
+        "round",
+        &[DECIMAL, INT],
+        DECIMAL,
+        binary::<DecimalArray, I32Array, DecimalArray>(|a, n| match *n {
+            // round to tens, hundreds, ...
+            n if n < 0 => {
+                let scale = Decimal::from_i128_with_scale(10i128.pow(n.unsigned_abs().min(28)), 0);
+                (round(a / scale, 0).checked_mul(scale)).ok_or(ConvertError::Overflow)
+            }
+            n => Ok(round(*a, n as u32)),
+        }),
+    );
+    for name in ["ceil", "ceiling"] {
+        add(name, &[DOUBLE], DOUBLE, f64_unary(|a| Ok(a.ceil())));
+        add(name, &[DECIMAL], DECIMAL, decimal_unary(|a| Ok(a.ceil())));
+    }
+    add("floor", &[DOUBLE], DOUBLE, f64_unary(|a| Ok(a.floor())));
+    add(
+        "floor",
+        &[DECIMAL],
+        DECIMAL,
+        decimal_unary(|a| Ok(a.floor())),
+    );
+    add(
+        "sqrt",
+        &[DOUBLE],
+        DOUBLE,
+        f64_unary(|a| match a < 0.0 {
+            true => Err(invalid("cannot take square root of a negative number")),
+            false => Ok(a.sqrt()),
+        }),
+    );
+    for name in ["power", "pow"] {
+        add(
+            name,
+            &[DOUBLE, DOUBLE],
+            DOUBLE,
+            binary::<F64Array, F64Array, F64Array>(|a, b| {
+                if *a == 0.0 && *b < 0.0 {
+                    return Err(invalid("zero raised to a negative power is undefined"));
+                }
+                if *a < 0.0 && b.fract() != 0.0 {
+                    return Err(invalid(
+                        "a negative number raised to a non-integer power yields a complex result",
+                    ));
+                }
+                Ok(a.powf(*b))
+            }),
+        );
+    }
+
+    // string
+    for name in ["length", "char_length", "character_length"] {
+        add(
+            name,
+            &[VARCHAR],
+            INT,
+            unary::<Utf8Array, I32Array>(|s| {
+                i32::try_from(s.chars().count()).map_err(|_| ConvertError::Overflow)
+            }),
+        );
+    }
+    add(
+        "octet_length",
+        &[VARCHAR],
+        INT,
+        unary::<Utf8Array, I32Array>(|s| {
+            i32::try_from(s.len()).map_err(|_| ConvertError::Overflow)
+        }),
+    );
+
+    // binary
+    for name in ["length", "octet_length"] {
+        add(
+            name,
+            &[BYTEA],
+            INT,
+            unary::<BytesArray, I32Array>(|b| {
+                i32::try_from(b.len()).map_err(|_| ConvertError::Overflow)
+            }),
+        );
+    }
+    add(
+        "upper",
+        &[VARCHAR],
+        VARCHAR,
+        unary::<Utf8Array, Utf8Array>(|s| Ok(s.to_uppercase())),
+    );
+    add(
+        "lower",
+        &[VARCHAR],
+        VARCHAR,
+        unary::<Utf8Array, Utf8Array>(|s| Ok(s.to_lowercase())),
+    );
+    for name in ["substring", "substr"] {
+        add(
+            name,
+            &[VARCHAR, INT],
+            VARCHAR,
+            binary::<Utf8Array, I32Array, Utf8Array>(|s, start| substring(s, *start, None)),
+        );
+        add(
+            name,
+            &[VARCHAR, INT, INT],
+            VARCHAR,
+            ternary::<Utf8Array, I32Array, I32Array, Utf8Array, _>(|s, start, len| {
+                substring(s, *start, Some(*len))
+            }),
+        );
+    }
+    type Trim = fn(&str, &dyn Fn(char) -> bool) -> String;
+    let trims: [(&str, Trim); 3] = [
+        ("btrim", |s, f| s.trim_matches(f).into()),
+        ("ltrim", |s, f| s.trim_start_matches(f).into()),
+        ("rtrim", |s, f| s.trim_end_matches(f).into()),
+    ];
+    for (name, trim) in trims {
+        add(
+            name,
+            &[VARCHAR],
+            VARCHAR,
+            unary::<Utf8Array, Utf8Array>(move |s| Ok(trim(s, &|c| c == ' '))),
+        );
+        add(
+            name,
+            &[VARCHAR, VARCHAR],
+            VARCHAR,
+            binary::<Utf8Array, Utf8Array, Utf8Array>(move |s, chars| {
+                Ok(trim(s, &|c| chars.contains(c)))
+            }),
+        );
+    }
+    add(
+        "replace",
+        &[VARCHAR, VARCHAR, VARCHAR],
+        VARCHAR,
+        ternary::<Utf8Array, Utf8Array, Utf8Array, Utf8Array, _>(|s, from, to| match from {
+            "" => Ok(s.into()),
+            _ => Ok(s.replace(from, to)),
+        }),
+    );
+    // NULL arguments are ignored.
+    registry.register_scalar(
+        ScalarFunction::new("concat", vec![VARCHAR], VARCHAR, |args| {
+            let mut builder = Utf8ArrayBuilder::with_capacity(args[0].len());
+            for i in 0..args[0].len() {
+                let mut s = String::new();
+                for arg in args {
+                    if let DataValue::String(v) = arg.get(i) {
+                        s.push_str(&v);
+                    }
+                }
+                builder.push(Some(&s));
+            }
+            Ok(builder.finish().into())
+        })
+        .variadic(),
+    );
+
+    // date and time
+    // `EXTRACT(field FROM source)` is bound to `extract('field', source)`. Dates are casted to
+    // timestamps.
+    add(
+        "extract",
+        &[VARCHAR, TIMESTAMP],
+        DECIMAL,
+        binary::<Utf8Array, TimestampArray, DecimalArray>(|field, t| t.extract(field.parse()?)),
+    );
+    add(
+        "extract",
+        &[VARCHAR, INTERVAL],
+        DECIMAL,
+        binary::<Utf8Array, IntervalArray, DecimalArray>(|field, i| i.extract(field.parse()?)),
+    );
+    add(
+        "date_part",
+        &[VARCHAR, TIMESTAMP],
+        DOUBLE,
+        binary::<Utf8Array, TimestampArray, F64Array>(|field, t| {
+            Ok(t.extract(field.parse()?)?.to_f64().unwrap())
+        }),
+    );
+    add(
+        "date_part",
+        &[VARCHAR, INTERVAL],
+        DOUBLE,
+        binary::<Utf8Array, IntervalArray, F64Array>(|field, i| {
+            Ok(i.extract(field.parse()?)?.to_f64().unwrap())
+        }),
+    );
+    add(
+        "date_trunc",
+        &[VARCHAR, TIMESTAMP],
+        TIMESTAMP,
+        binary::<Utf8Array, TimestampArray, TimestampArray>(|field, t| {
+            t.truncate(field.parse::<TemporalField>()?)
+        }),
+    );
+
+    // conditional
+    for ty in [
+        SMALLINT, INT, BIGINT, DOUBLE, DECIMAL, VARCHAR, BOOLEAN, DATE, TIMESTAMP, INTERVAL, BYTEA,
+    ] {
+        let coalesce = ScalarFunction::new("coalesce", vec![ty.clone()], ty.clone(), |args| {
+            Ok(map_rows(args, |values| {
+                values.iter().find(|v| !v.is_null()).cloned()
+            }))
+        });
+        registry.register_scalar(coalesce.variadic());
+        let nullif = ScalarFunction::new("nullif", vec![ty.clone(); 2], ty.clone(), |args| {
+            Ok(map_rows(args, |values| match values[0] == values[1] {
+                true => None,
+                false => Some(values[0].clone()),
+            }))
+        });
+        registry.register_scalar(nullif);
+        // NULL arguments are ignored.
+        for (name, greatest) in [("greatest", true), ("least", false)] {
+            let func = ScalarFunction::new(name, vec![ty.clone()], ty.clone(), move |args| {
+                Ok(map_rows(args, |values| {
+                    let values = values.iter().filter(|v| !v.is_null());
+                    match greatest {
+                        true => values.fold(None, |max, v| max.filter(|m| *m >= v).or(Some(v))),
+                        false => values.fold(None, |min, v| min.filter(|m| *m <= v).or(Some(v))),
+                    }
+                    .cloned()
+                }))
+            });
+            registry.register_scalar(func.variadic());
+        }
+    }
+}
+
+/// Round half to even, like `rint` which PostgreSQL uses to round floats.
+fn round_ties_even(a: f64) -> f64 {
+    let r = a.round();
+    if (r - a).abs() == 0.5 {
+        2.0 * (a / 2.0).round()
+    } else {
+        r
+    }
+}
+
+/// Round half away from zero, like PostgreSQL rounds numerics.
+fn round(d: Decimal, dp: u32) -> Decimal {
+    d.round_dp_with_strategy(dp, RoundingStrategy::MidpointAwayFromZero)
+}
+
+fn invalid(msg: &str) -> ConvertError {
+    ConvertError::InvalidArgument(msg.into())
+}
+
+/// Returns the characters of `s` from the 1-based position `start`, at most `len` of them.
+/// Positions before the beginning of the string are counted in `len`.
+fn substring(s: &str, start: i32, len: Option<i32>) -> Result<String, ConvertError> {
+    let end = match len {
+        Some(len) if len < 0 => return Err(invalid("negative substring length not allowed")),
+        Some(len) => start as i64 + len as i64,
+        None => i64::MAX,
+    };
+    let start = (start as i64).max(1);
+    let len = (end - start).max(0) as usize;
+    Ok(s.chars().skip(start as usize - 1).take(len).collect())
+}
+
+type Owned<A> = <<A as Array>::Item as ToOwned>::Owned;
+
+fn typed<'a, A>(array: &'a ArrayImpl) -> &'a A
+where
+    A: Array,
+    &'a A: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+{
+    array.try_into().expect("argument type mismatch")
+}
+
+fn unary<A, O>(
+    f: impl Fn(&A::Item) -> Result<Owned<O>, ConvertError> + Send + Sync + 'static,
+) -> ScalarFn
+where
+    A: Array,
+    O: Array + Into<ArrayImpl>,
+    for<'a> &'a A: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+{
+    Arc::new(move |args| Ok(unary_op::<A, O, _>(typed::<A>(&args[0]), &f)?.into()))
+}
+
+fn f64_unary(f: fn(f64) -> Result<f64, ConvertError>) -> ScalarFn {
+    unary::<F64Array, F64Array>(move |a| f(*a))
+}
+
+fn decimal_unary(f: fn(Decimal) -> Result<Decimal, ConvertError>) -> ScalarFn {
+    unary::<DecimalArray, DecimalArray>(move |a| f(*a))
+}
+
+fn binary<A, B, O>(
+    f: impl Fn(&A::Item, &B::Item) -> Result<Owned<O>, ConvertError> + Send + Sync + 'static,
+) -> ScalarFn
+where
+    A: Array,
+    B: Array,
+    O: Array + Into<ArrayImpl>,
+    for<'a> &'a A: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+    for<'a> &'a B: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+{
+    Arc::new(move |args| {
+        let (a, b) = (typed::<A>(&args[0]), typed::<B>(&args[1]));
+        Ok(binary_op::<A, B, O, _>(a, b, &f)?.into())
+    })
+}
+
+/// Apply `f` on each triple of items. The output is null if any input is null.
+fn ternary<A, B, C, O, F>(f: F) -> ScalarFn
+where
+    F: Fn(&A::Item, &B::Item, &C::Item) -> Result<Owned<O>, ConvertError> + Send + Sync + 'static,
+    A: Array,
+    B: Array,
+    C: Array,
+    O: Array + Into<ArrayImpl>,
+    for<'a> &'a A: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+    for<'a> &'a B: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+    for<'a> &'a C: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+{
+    Arc::new(move |args| {
+        let (a, b, c) = (
+            typed::<A>(&args[0]),
+            typed::<B>(&args[1]),
+            typed::<C>(&args[2]),
+        );
+        let mut builder = O::Builder::with_capacity(a.len());
+        for ((a, b), c) in a.iter().zip(b.iter()).zip(c.iter()) {
+            match (a, b, c) {
+                (Some(a), Some(b), Some(c)) => builder.push(Some(f(a, b, c)?.borrow())),
+                _ => builder.push(None),
+            }
+        }
+        Ok(builder.finish().into())
+    })
+}
+
+/// Compute each row of the output from the values of the arguments, or NULL if `f` returns
+/// `None`. All arguments and the output have the same type.
+fn map_rows(args: &[ArrayImpl], f: impl Fn(&[DataValue]) -> Option<DataValue>) -> ArrayImpl {
+    let mut builder = ArrayBuilderImpl::from_type_of_array(&args[0]);
+    let mut values = vec![];
+    for i in 0..args[0].len() {
+        values.clear();
+        values.extend(args.iter().map(|a| a.get(i)));
+        builder.push(&f(&values).unwrap_or(DataValue::Null));
+    }
+    builder.finish()
+}
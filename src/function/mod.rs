@@ -0,0 +1,263 @@
+//! Scalar and aggregate functions that can be called in expressions.
+//!
+//! A function may have several overloads with different argument types. Each overload is a
+//! [`ScalarFunction`] or an [`AggregateFunction`] registered in the [`FunctionRegistry`] of
+//! the database. The built-in aggregate functions are not in the registry, see
+//! [`AggKind`](crate::binder::AggKind).
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::array::ArrayImpl;
+use crate::types::{ConvertError, DataTypeKind, DataTypeKindExt, DataValue};
+
+mod builtin;
+
+/// The vectorized implementation of a scalar function.
+///
+/// It takes an array for each argument, all of the same length, and returns an array of that
+/// length. The arrays have the argument types and the return type of the overload.
+pub type ScalarFn = Arc<dyn Fn(&[ArrayImpl]) -> Result<ArrayImpl, ConvertError> + Send + Sync>;
+
+/// An overload of a scalar function.
+#[derive(Clone)]
+pub struct ScalarFunction {
+    pub name: String,
+    pub arg_types: Vec<DataTypeKind>,
+    /// Whether the last argument can be repeated any number of times.
+    pub variadic: bool,
+    pub return_type: DataTypeKind,
+    pub eval: ScalarFn,
+}
+
+impl ScalarFunction {
+    pub fn new(
+        name: &str,
+        arg_types: Vec<DataTypeKind>,
+        return_type: DataTypeKind,
+        eval: impl Fn(&[ArrayImpl]) -> Result<ArrayImpl, ConvertError> + Send + Sync + 'static,
+    ) -> Self {
+        ScalarFunction {
+            name: name.to_lowercase(),
+            arg_types,
+            variadic: false,
+            return_type,
+            eval: Arc::new(eval),
+        }
+    }
+
+    /// Make the last argument repeatable.
+    pub fn variadic(self) -> Self {
+        ScalarFunction {
+            variadic: true,
+            ..self
+        }
+    }
+
+    /// Returns the type of the `i`-th argument.
+    pub fn arg_type(&self, i: usize) -> &DataTypeKind {
+        &self.arg_types[i.min(self.arg_types.len() - 1)]
+    }
+
+    fn same_signature(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.arg_types == other.arg_types
+            && self.variadic == other.variadic
+    }
+}
+
+impl std::fmt::Debug for ScalarFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl PartialEq for ScalarFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_signature(other) && self.return_type == other.return_type
+    }
+}
+
+/// The state of a user-defined aggregate function over a group of rows.
+pub trait Accumulator: Send + Sync {
+    /// Add a row with a value for each argument. Rows where any argument is NULL are skipped.
+    fn update(&mut self, args: &[DataValue]) -> Result<(), ConvertError>;
+
+    /// Returns the result over the rows added so far. It must be NULL or a value of the
+    /// return type of the function.
+    fn output(&self) -> DataValue;
+}
+
+/// Creates the initial state of an aggregate function.
+pub type AccumulatorFn = Arc<dyn Fn() -> Box<dyn Accumulator> + Send + Sync>;
+
+/// An overload of a user-defined aggregate function.
+#[derive(Clone)]
+pub struct AggregateFunction {
+    pub name: String,
+    pub arg_types: Vec<DataTypeKind>,
+    pub return_type: DataTypeKind,
+    pub create: AccumulatorFn,
+}
+
+impl AggregateFunction {
+    pub fn new(
+        name: &str,
+        arg_types: Vec<DataTypeKind>,
+        return_type: DataTypeKind,
+        create: impl Fn() -> Box<dyn Accumulator> + Send + Sync + 'static,
+    ) -> Self {
+        AggregateFunction {
+            name: name.to_lowercase(),
+            arg_types,
+            return_type,
+            create: Arc::new(create),
+        }
+    }
+}
+
+impl std::fmt::Debug for AggregateFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl PartialEq for AggregateFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.arg_types == other.arg_types
+            && self.return_type == other.return_type
+    }
+}
+
+impl Eq for AggregateFunction {}
+
+/// Returns the cost of calling a function with arguments of the types, or `None` if it can not
+/// be called with them. Untyped NULLs are `None`.
+fn match_cost(
+    arg_types: &[DataTypeKind],
+    variadic: bool,
+    args: &[Option<DataTypeKind>],
+) -> Option<u32> {
+    let arity_matches = match variadic {
+        true => !arg_types.is_empty() && args.len() >= arg_types.len(),
+        false => args.len() == arg_types.len(),
+    };
+    if !arity_matches {
+        return None;
+    }
+    let mut cost = 0;
+    for (i, arg) in args.iter().enumerate() {
+        cost += cast_cost(arg.as_ref(), &arg_types[i.min(arg_types.len() - 1)])?;
+    }
+    Some(cost)
+}
+
+/// Returns the item with the least cost, or the first one if several have the least cost.
+fn min_cost<T>(items: &[T], cost: impl Fn(&T) -> Option<u32>) -> Option<&T> {
+    let mut best: Option<(u32, &T)> = None;
+    for item in items {
+        match cost(item) {
+            Some(c) if best.map_or(true, |(b, _)| c < b) => best = Some((c, item)),
+            _ => {}
+        }
+    }
+    best.map(|(_, item)| item)
+}
+
+/// Returns the cost of implicitly converting an argument of type `from` to `to`, or `None` if
+/// it is not allowed.
+fn cast_cost(from: Option<&DataTypeKind>, to: &DataTypeKind) -> Option<u32> {
+    let from = match from {
+        Some(from) => from,
+        None => return Some(0),
+    };
+    if from == to || from.canonical() == to.canonical() || (from.is_decimal() && to.is_decimal()) {
+        Some(0)
+    } else if from.is_integer() && to.is_integer() {
+        // only widening, so that `abs(BIGINT)` does not pick `abs(INT)`
+        (from.integer_bits() < to.integer_bits()).then_some(1)
+    } else if from.is_integer() && to.is_decimal() {
+        Some(2)
+    } else if from == &DataTypeKind::Date && to.is_timestamp() {
+        Some(1)
+    } else if from.is_number() && to.is_float() {
+        Some(3)
+    } else if to.is_string() && (from.is_number() || from == &DataTypeKind::Boolean) {
+        Some(4)
+    } else {
+        None
+    }
+}
+
+/// The scalar functions and user-defined aggregate functions of a database.
+#[derive(Default)]
+pub struct FunctionRegistry {
+    scalars: RwLock<HashMap<String, Vec<ScalarFunction>>>,
+    aggregates: RwLock<HashMap<String, Vec<AggregateFunction>>>,
+}
+
+impl FunctionRegistry {
+    /// Create a registry with the built-in functions.
+    pub fn new() -> Self {
+        let registry = Self::default();
+        builtin::register(&registry);
+        registry
+    }
+
+    /// Add an overload of a scalar function. It replaces the overload with the same
+    /// argument types if there is one.
+    pub fn register_scalar(&self, func: ScalarFunction) {
+        let mut scalars = self.scalars.write().unwrap();
+        let overloads = scalars.entry(func.name.clone()).or_default();
+        match overloads.iter_mut().find(|f| f.same_signature(&func)) {
+            Some(f) => *f = func,
+            None => overloads.push(func),
+        }
+    }
+
+    /// Returns true if there is a scalar function called `name`.
+    pub fn contains_scalar(&self, name: &str) -> bool {
+        self.scalars.read().unwrap().contains_key(name)
+    }
+
+    /// Returns the overload of `name` that matches the argument types with the least
+    /// conversions. If several overloads match equally, the first registered one is returned.
+    pub fn resolve_scalar(
+        &self,
+        name: &str,
+        args: &[Option<DataTypeKind>],
+    ) -> Option<ScalarFunction> {
+        let scalars = self.scalars.read().unwrap();
+        let overloads = scalars.get(name)?;
+        min_cost(overloads, |f| match_cost(&f.arg_types, f.variadic, args)).cloned()
+    }
+
+    /// Add an overload of an aggregate function. It replaces the overload with the same
+    /// argument types if there is one.
+    pub fn register_aggregate(&self, func: AggregateFunction) {
+        let mut aggregates = self.aggregates.write().unwrap();
+        let overloads = aggregates.entry(func.name.clone()).or_default();
+        match overloads.iter_mut().find(|f| f.arg_types == func.arg_types) {
+            Some(f) => *f = func,
+            None => overloads.push(func),
+        }
+    }
+
+    /// Returns true if there is a user-defined aggregate function called `name`.
+    pub fn contains_aggregate(&self, name: &str) -> bool {
+        self.aggregates.read().unwrap().contains_key(name)
+    }
+
+    /// Returns the overload of the aggregate function `name` that matches the argument types
+    /// with the least conversions, like [`FunctionRegistry::resolve_scalar`].
+    pub fn resolve_aggregate(
+        &self,
+        name: &str,
+        args: &[Option<DataTypeKind>],
+    ) -> Option<AggregateFunction> {
+        let aggregates = self.aggregates.read().unwrap();
+        let overloads = aggregates.get(name)?;
+        min_cost(overloads, |f| match_cost(&f.arg_types, false, args)).cloned()
+    }
+}
@@ -27,6 +27,7 @@ pub mod executor;
 
 pub mod array;
 pub mod catalog;
+pub mod function;
 pub mod storage;
 pub mod types;
 
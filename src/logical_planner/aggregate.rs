@@ -0,0 +1,102 @@
+use super::*;
+use crate::binder::{BoundAggCall, BoundColumnRef, BoundExpr};
+
+/// Groups the rows of `child` by `group_by` and computes the aggregations of each group.
+///
+/// The output row contains the values of `group_by` followed by the results of `aggs`.
+/// Without `group_by`, there is exactly one group, even if the child is empty.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalAggregate {
+    pub aggs: Vec<BoundAggCall>,
+    pub group_by: Vec<BoundExpr>,
+    pub child: LogicalPlanRef,
+}
+
+impl LogicalPlanner {
+    /// Compute the aggregations in `exprs` on top of `plan`, and replace the aggregate function
+    /// calls and the expressions of `group_by` with the columns of the results.
+    pub(super) fn plan_aggregate(
+        &self,
+        plan: LogicalPlan,
+        group_by: Vec<BoundExpr>,
+        exprs: &mut [BoundExpr],
+    ) -> Result<LogicalPlan, LogicalPlanError> {
+        if group_by.iter().any(|e| e.contains_subquery()) {
+            return Err(LogicalPlanError::UnsupportedSubquery(
+                "subquery in GROUP BY",
+            ));
+        }
+        let mut correlated = false;
+        for expr in exprs.iter() {
+            expr.visit(&mut |e| {
+                if let BoundExpr::Subquery(s) = e {
+                    correlated |= s.query.max_outer_depth() > 0;
+                }
+            });
+        }
+        if correlated {
+            return Err(LogicalPlanError::UnsupportedSubquery(
+                "correlated subquery in aggregated query",
+            ));
+        }
+
+        let mut aggs: Vec<BoundAggCall> = vec![];
+        for expr in exprs.iter_mut() {
+            expr.rewrite(&mut |e| {
+                let (index, return_type) = if let Some(i) = group_by.iter().position(|g| g == e) {
+                    (i, group_by[i].return_type().unwrap())
+                } else if let BoundExpr::AggCall(agg) = e {
+                    let i = match aggs.iter().position(|a| a == agg) {
+                        Some(i) => i,
+                        None => {
+                            aggs.push(agg.clone());
+                            aggs.len() - 1
+                        }
+                    };
+                    (group_by.len() + i, agg.return_type.clone())
+                } else {
+                    return false;
+                };
+                *e = BoundExpr::ColumnRef(BoundColumnRef {
+                    index,
+                    depth: 0,
+                    return_type,
+                });
+                true
+            });
+        }
+        if aggs
+            .iter()
+            .any(|a| a.args.iter().any(|e| e.contains_subquery()))
+        {
+            return Err(LogicalPlanError::UnsupportedSubquery(
+                "subquery in aggregate function arguments",
+            ));
+        }
+        Ok(LogicalAggregate {
+            aggs,
+            group_by,
+            child: plan.into(),
+        }
+        .into())
+    }
+}
+
+impl Explain for LogicalAggregate {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Aggregate: group_by: {:?}, aggs: {:?}",
+            self.group_by, self.aggs
+        )?;
+        self.child.explain(level + 1, f)
+    }
+}
+
+impl PlanNode for LogicalAggregate {
+    fn out_types(&self) -> Vec<DataType> {
+        let group_types = self.group_by.iter().map(|e| e.return_type().unwrap());
+        let agg_types = self.aggs.iter().map(|a| a.return_type.clone());
+        group_types.chain(agg_types).collect()
+    }
+}
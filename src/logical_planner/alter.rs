@@ -0,0 +1,36 @@
+use super::*;
+use crate::binder::{AlterTableAction, BoundAlterTable};
+use crate::catalog::TableRefId;
+use crate::types::{DataType, DataTypeExt, DataTypeKind};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalAlterTable {
+    pub table_ref_id: TableRefId,
+    pub action: AlterTableAction,
+}
+
+impl LogicalPlanner {
+    pub fn plan_alter_table(&self, stmt: BoundAlterTable) -> Result<LogicalPlan, LogicalPlanError> {
+        Ok(LogicalAlterTable {
+            table_ref_id: stmt.table_ref_id,
+            action: stmt.action,
+        }
+        .into())
+    }
+}
+
+impl Explain for LogicalAlterTable {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "AlterTable: table #{}, action: {:?}",
+            self.table_ref_id.table_id, self.action
+        )
+    }
+}
+
+impl PlanNode for LogicalAlterTable {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
@@ -1,14 +1,43 @@
 use itertools::Itertools;
 
 use super::*;
-use crate::binder::BoundCreateTable;
-use crate::catalog::{ColumnDesc, SchemaId};
+use crate::binder::{
+    BoundCreateIndex, BoundCreateSchema, BoundCreateTable, BoundCreateView, BoundExpr,
+};
+use crate::catalog::{
+    ColumnDesc, ColumnId, SchemaId, TableRefId, UniqueConstraint, ViewDefinition,
+};
+use crate::types::{DataType, DataTypeExt, DataTypeKind};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct LogicalCreateTable {
     pub schema_id: SchemaId,
     pub table_name: String,
     pub columns: Vec<(String, ColumnDesc)>,
+    pub uniques: Vec<UniqueConstraint>,
+    pub checks: Vec<(String, BoundExpr)>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalCreateSchema {
+    pub schema_name: String,
+    pub if_not_exists: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalCreateIndex {
+    pub table_ref_id: TableRefId,
+    pub index_name: String,
+    pub column_id: ColumnId,
+    pub if_not_exists: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalCreateView {
+    pub schema_id: SchemaId,
+    pub view_name: String,
+    pub definition: ViewDefinition,
+    pub or_replace: bool,
 }
 
 impl LogicalPlanner {
@@ -20,6 +49,42 @@ impl LogicalPlanner {
             schema_id: stmt.schema_id,
             table_name: stmt.table_name,
             columns: stmt.columns,
+            uniques: stmt.uniques,
+            checks: stmt.checks,
+        }
+        .into())
+    }
+
+    pub fn plan_create_schema(
+        &self,
+        stmt: BoundCreateSchema,
+    ) -> Result<LogicalPlan, LogicalPlanError> {
+        Ok(LogicalCreateSchema {
+            schema_name: stmt.schema_name,
+            if_not_exists: stmt.if_not_exists,
+        }
+        .into())
+    }
+
+    pub fn plan_create_index(
+        &self,
+        stmt: BoundCreateIndex,
+    ) -> Result<LogicalPlan, LogicalPlanError> {
+        Ok(LogicalCreateIndex {
+            table_ref_id: stmt.table_ref_id,
+            index_name: stmt.index_name,
+            column_id: stmt.column_id,
+            if_not_exists: stmt.if_not_exists,
+        }
+        .into())
+    }
+
+    pub fn plan_create_view(&self, stmt: BoundCreateView) -> Result<LogicalPlan, LogicalPlanError> {
+        Ok(LogicalCreateView {
+            schema_id: stmt.schema_id,
+            view_name: stmt.view_name,
+            definition: stmt.definition,
+            or_replace: stmt.or_replace,
         }
         .into())
     }
@@ -38,3 +103,61 @@ impl Explain for LogicalCreateTable {
         )
     }
 }
+
+impl PlanNode for LogicalCreateTable {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
+
+impl Explain for LogicalCreateSchema {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "CreateSchema: name: {}, if_not_exists: {}",
+            self.schema_name, self.if_not_exists
+        )
+    }
+}
+
+impl PlanNode for LogicalCreateSchema {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
+
+impl Explain for LogicalCreateIndex {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "CreateIndex: name: {}, table #{}, column: {}",
+            self.index_name, self.table_ref_id.table_id, self.column_id
+        )
+    }
+}
+
+impl PlanNode for LogicalCreateIndex {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
+
+impl Explain for LogicalCreateView {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "CreateView: name: {}, columns: [{}], or_replace: {}",
+            self.view_name,
+            (self.definition.columns.iter())
+                .map(|(name, ty)| format!("{}: {:?}", name, ty))
+                .join(", "),
+            self.or_replace
+        )
+    }
+}
+
+impl PlanNode for LogicalCreateView {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
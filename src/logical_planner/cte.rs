@@ -0,0 +1,114 @@
+use super::*;
+use crate::binder::BoundCte;
+
+/// Materializes the CTEs in order, then outputs the rows of `child`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalWith {
+    pub ctes: Vec<(usize, LogicalPlanRef)>,
+    pub child: LogicalPlanRef,
+}
+
+/// Reads the materialized rows of a CTE.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalCteScan {
+    pub cte_id: usize,
+    pub column_types: Vec<DataType>,
+}
+
+/// Evaluates `recursive` repeatedly on the rows of the previous iteration, starting from the
+/// rows of `base`, and outputs all of them. The working table is read by the CTE scans of
+/// `cte_id` in `recursive`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalRecursiveCte {
+    pub cte_id: usize,
+    pub base: LogicalPlanRef,
+    pub recursive: LogicalPlanRef,
+    pub union_all: bool,
+}
+
+impl LogicalPlanner {
+    /// Plan the CTEs so that they are materialized before `plan` is executed.
+    pub(super) fn plan_with(
+        &self,
+        plan: LogicalPlan,
+        ctes: Vec<BoundCte>,
+    ) -> Result<LogicalPlan, LogicalPlanError> {
+        if ctes.is_empty() {
+            return Ok(plan);
+        }
+        let mut planned = vec![];
+        for cte in ctes {
+            let correlated = cte.query.max_outer_depth() > 0
+                || (cte.recursive.as_ref()).map_or(false, |q| q.max_outer_depth() > 0);
+            if correlated {
+                return Err(LogicalPlanError::UnsupportedSubquery(
+                    "correlated common table expression",
+                ));
+            }
+            let base = self.plan_select(*cte.query)?;
+            let cte_plan = match cte.recursive {
+                Some(recursive) => LogicalRecursiveCte {
+                    cte_id: cte.id,
+                    base: base.into(),
+                    recursive: self.plan_select(*recursive)?.into(),
+                    union_all: cte.union_all,
+                }
+                .into(),
+                None => base,
+            };
+            planned.push((cte.id, cte_plan.into()));
+        }
+        Ok(LogicalWith {
+            ctes: planned,
+            child: plan.into(),
+        }
+        .into())
+    }
+}
+
+impl Explain for LogicalWith {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "With:")?;
+        for (id, plan) in self.ctes.iter() {
+            writeln!(f, "{}Cte: id: {}", "  ".repeat(level + 1), id)?;
+            plan.explain(level + 2, f)?;
+        }
+        self.child.explain(level + 1, f)
+    }
+}
+
+impl Explain for LogicalCteScan {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "CteScan: id: {}", self.cte_id)
+    }
+}
+
+impl Explain for LogicalRecursiveCte {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "RecursiveCte: id: {}, all: {}",
+            self.cte_id, self.union_all
+        )?;
+        self.base.explain(level + 1, f)?;
+        self.recursive.explain(level + 1, f)
+    }
+}
+
+impl PlanNode for LogicalWith {
+    fn out_types(&self) -> Vec<DataType> {
+        self.child.out_types()
+    }
+}
+
+impl PlanNode for LogicalCteScan {
+    fn out_types(&self) -> Vec<DataType> {
+        self.column_types.clone()
+    }
+}
+
+impl PlanNode for LogicalRecursiveCte {
+    fn out_types(&self) -> Vec<DataType> {
+        self.base.out_types()
+    }
+}
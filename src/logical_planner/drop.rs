@@ -0,0 +1,123 @@
+use itertools::Itertools;
+
+use super::*;
+use crate::binder::{BoundDropIndex, BoundDropSchema, BoundDropTable, BoundDropView};
+use crate::catalog::{SchemaId, TableRefId};
+use crate::types::{DataType, DataTypeExt, DataTypeKind};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalDropTable {
+    pub table_ref_ids: Vec<TableRefId>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalDropSchema {
+    pub schema_ids: Vec<SchemaId>,
+    pub cascade: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalDropIndex {
+    pub indexes: Vec<(TableRefId, String)>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalDropView {
+    pub view_ref_ids: Vec<TableRefId>,
+}
+
+impl LogicalPlanner {
+    pub fn plan_drop_table(&self, stmt: BoundDropTable) -> Result<LogicalPlan, LogicalPlanError> {
+        Ok(LogicalDropTable {
+            table_ref_ids: stmt.table_ref_ids,
+        }
+        .into())
+    }
+
+    pub fn plan_drop_schema(&self, stmt: BoundDropSchema) -> Result<LogicalPlan, LogicalPlanError> {
+        Ok(LogicalDropSchema {
+            schema_ids: stmt.schema_ids,
+            cascade: stmt.cascade,
+        }
+        .into())
+    }
+
+    pub fn plan_drop_view(&self, stmt: BoundDropView) -> Result<LogicalPlan, LogicalPlanError> {
+        Ok(LogicalDropView {
+            view_ref_ids: stmt.view_ref_ids,
+        }
+        .into())
+    }
+
+    pub fn plan_drop_index(&self, stmt: BoundDropIndex) -> Result<LogicalPlan, LogicalPlanError> {
+        Ok(LogicalDropIndex {
+            indexes: stmt.indexes,
+        }
+        .into())
+    }
+}
+
+impl Explain for LogicalDropTable {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "DropTable: tables [{}]",
+            self.table_ref_ids.iter().map(|id| id.table_id).join(", ")
+        )
+    }
+}
+
+impl PlanNode for LogicalDropTable {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
+
+impl Explain for LogicalDropSchema {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "DropSchema: schemas [{}], cascade: {}",
+            self.schema_ids.iter().join(", "),
+            self.cascade
+        )
+    }
+}
+
+impl PlanNode for LogicalDropSchema {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
+
+impl Explain for LogicalDropIndex {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "DropIndex: indexes [{}]",
+            self.indexes.iter().map(|(_, name)| name).join(", ")
+        )
+    }
+}
+
+impl PlanNode for LogicalDropIndex {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
+
+impl Explain for LogicalDropView {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "DropView: views [{}]",
+            self.view_ref_ids.iter().map(|id| id.table_id).join(", ")
+        )
+    }
+}
+
+impl PlanNode for LogicalDropView {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::types::{DataType, DataTypeExt, DataTypeKind};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct LogicalExplain {
@@ -19,3 +20,9 @@ impl Explain for LogicalExplain {
         writeln!(f, "Huh, explain myself?")
     }
 }
+
+impl PlanNode for LogicalExplain {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Varchar(None).not_null()]
+    }
+}
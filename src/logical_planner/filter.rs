@@ -0,0 +1,57 @@
+use super::*;
+use crate::binder::BoundExpr;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalFilter {
+    pub expr: BoundExpr,
+    pub child: LogicalPlanRef,
+}
+
+/// Plan a filter on top of `plan`. The filter is pushed through projections, e.g. the ones
+/// of views and derived tables, and merged with a filter below them if it can not fail on
+/// the rows that the filter removes. So a filter on a view can be planned on an index scan
+/// of the table.
+pub(super) fn plan_filter(plan: LogicalPlan, mut expr: BoundExpr) -> LogicalPlan {
+    match plan {
+        LogicalPlan::LogicalProjection(projection) if expr.max_outer_depth() == 0 => {
+            expr.rewrite(&mut |e| match e {
+                BoundExpr::ColumnRef(c) => {
+                    *e = projection.exprs[c.index].clone();
+                    true
+                }
+                _ => false,
+            });
+            LogicalProjection {
+                child: plan_filter(unwrap(projection.child), expr).into(),
+                exprs: projection.exprs,
+            }
+            .into()
+        }
+        LogicalPlan::LogicalFilter(filter) if !expr.may_fail() => {
+            let expr = BoundExpr::and_all(vec![filter.expr, expr]);
+            plan_filter(unwrap(filter.child), expr)
+        }
+        plan => LogicalFilter {
+            expr,
+            child: plan.into(),
+        }
+        .into(),
+    }
+}
+
+fn unwrap(plan: LogicalPlanRef) -> LogicalPlan {
+    Rc::try_unwrap(plan).unwrap_or_else(|plan| (*plan).clone())
+}
+
+impl Explain for LogicalFilter {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Filter: expr: {:?}", self.expr)?;
+        self.child.explain(level + 1, f)
+    }
+}
+
+impl PlanNode for LogicalFilter {
+    fn out_types(&self) -> Vec<DataType> {
+        self.child.out_types()
+    }
+}
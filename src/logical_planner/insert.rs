@@ -1,9 +1,9 @@
 use itertools::Itertools;
 
 use super::*;
-use crate::binder::{BoundExpr, BoundInsert};
+use crate::binder::{BoundExpr, BoundInsert, BoundInsertSource};
 use crate::catalog::{ColumnId, TableRefId};
-use crate::types::DataType;
+use crate::types::{DataType, DataTypeExt, DataTypeKind};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct LogicalInsert {
@@ -20,16 +20,18 @@ pub struct LogicalValues {
 
 impl LogicalPlanner {
     pub fn plan_insert(&self, stmt: BoundInsert) -> Result<LogicalPlan, LogicalPlanError> {
+        let child = match stmt.source {
+            BoundInsertSource::Values(values) => LogicalValues {
+                column_types: stmt.column_types,
+                values,
+            }
+            .into(),
+            BoundInsertSource::Query(query) => self.plan_select(query)?,
+        };
         Ok(LogicalInsert {
             table_ref_id: stmt.table_ref_id,
             column_ids: stmt.column_ids,
-            child: Rc::new(
-                LogicalValues {
-                    column_types: stmt.column_types,
-                    values: stmt.values,
-                }
-                .into(),
-            ),
+            child: Rc::new(child),
         }
         .into())
     }
@@ -52,3 +54,15 @@ impl Explain for LogicalValues {
         writeln!(f, "Values: {} rows", self.values.len())
     }
 }
+
+impl PlanNode for LogicalInsert {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
+
+impl PlanNode for LogicalValues {
+    fn out_types(&self) -> Vec<DataType> {
+        self.column_types.clone()
+    }
+}
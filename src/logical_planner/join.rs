@@ -0,0 +1,42 @@
+use super::*;
+use crate::binder::{BoundExpr, JoinType};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalJoin {
+    pub left: LogicalPlanRef,
+    pub right: LogicalPlanRef,
+    pub join_type: JoinType,
+    pub condition: BoundExpr,
+}
+
+impl Explain for LogicalJoin {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Join: type: {:?}, on: {:?}",
+            self.join_type, self.condition
+        )?;
+        self.left.explain(level + 1, f)?;
+        self.right.explain(level + 1, f)
+    }
+}
+
+impl PlanNode for LogicalJoin {
+    fn out_types(&self) -> Vec<DataType> {
+        let nullable = |types: Vec<DataType>| {
+            types
+                .into_iter()
+                .map(|ty| DataType::new(ty.kind(), true))
+                .collect::<Vec<_>>()
+        };
+        let left = self.left.out_types();
+        let right = self.right.out_types();
+        match self.join_type {
+            JoinType::Inner => [left, right].concat(),
+            JoinType::LeftOuter | JoinType::LeftSingle => [left, nullable(right)].concat(),
+            JoinType::RightOuter => [nullable(left), right].concat(),
+            JoinType::FullOuter => [nullable(left), nullable(right)].concat(),
+            JoinType::Semi | JoinType::Anti => left,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use itertools::Itertools;
+
+use super::*;
+use crate::binder::{BoundCreateMaterializedView, BoundRefreshMaterializedView};
+use crate::catalog::{SchemaId, TableRefId, ViewDefinition};
+use crate::types::{DataType, DataTypeExt, DataTypeKind};
+
+/// Create a materialized view and fill it with the output of the child.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalCreateMaterializedView {
+    pub schema_id: SchemaId,
+    pub view_name: String,
+    pub definition: ViewDefinition,
+    pub child: LogicalPlanRef,
+}
+
+/// Replace the rows of a materialized view with the output of the child.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalRefreshMaterializedView {
+    pub table_ref_id: TableRefId,
+    pub child: LogicalPlanRef,
+}
+
+impl LogicalPlanner {
+    pub fn plan_create_materialized_view(
+        &self,
+        stmt: BoundCreateMaterializedView,
+    ) -> Result<LogicalPlan, LogicalPlanError> {
+        Ok(LogicalCreateMaterializedView {
+            schema_id: stmt.schema_id,
+            view_name: stmt.view_name,
+            definition: stmt.definition,
+            child: Rc::new(self.plan_select(stmt.query)?),
+        }
+        .into())
+    }
+
+    pub fn plan_refresh_materialized_view(
+        &self,
+        stmt: BoundRefreshMaterializedView,
+    ) -> Result<LogicalPlan, LogicalPlanError> {
+        Ok(LogicalRefreshMaterializedView {
+            table_ref_id: stmt.table_ref_id,
+            child: Rc::new(self.plan_select(stmt.query)?),
+        }
+        .into())
+    }
+}
+
+impl Explain for LogicalCreateMaterializedView {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "CreateMaterializedView: name: {}, columns: [{}]",
+            self.view_name,
+            (self.definition.columns.iter())
+                .map(|(name, ty)| format!("{}: {:?}", name, ty))
+                .join(", ")
+        )?;
+        self.child.explain(level + 1, f)
+    }
+}
+
+impl PlanNode for LogicalCreateMaterializedView {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
+
+impl Explain for LogicalRefreshMaterializedView {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "RefreshMaterializedView: table #{}",
+            self.table_ref_id.table_id
+        )?;
+        self.child.explain(level + 1, f)
+    }
+}
+
+impl PlanNode for LogicalRefreshMaterializedView {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
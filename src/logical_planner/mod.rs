@@ -3,27 +3,70 @@ use std::rc::Rc;
 use enum_dispatch::enum_dispatch;
 
 use crate::binder::BoundStatement;
+use crate::types::DataType;
 
+mod aggregate;
+mod alter;
 mod create;
+mod cte;
+mod drop;
 mod explain;
+mod filter;
 mod insert;
+mod join;
+mod materialized_view;
 mod select;
+mod set_operation;
+mod subquery;
+mod truncate;
+mod window;
 
+pub use self::aggregate::*;
+pub use self::alter::*;
 pub use self::create::*;
+pub use self::cte::*;
+pub use self::drop::*;
 pub use self::explain::*;
+pub use self::filter::*;
 pub use self::insert::*;
+pub use self::join::*;
+pub use self::materialized_view::*;
 pub use self::select::*;
+pub use self::set_operation::*;
+pub use self::truncate::*;
+pub use self::window::*;
 
-#[enum_dispatch(Explain)]
+#[enum_dispatch(Explain, PlanNode)]
 #[derive(Debug, PartialEq, Clone)]
 pub enum LogicalPlan {
     LogicalCreateTable,
+    LogicalDropTable,
+    LogicalAlterTable,
+    LogicalCreateSchema,
+    LogicalDropSchema,
+    LogicalCreateIndex,
+    LogicalDropIndex,
+    LogicalCreateView,
+    LogicalDropView,
+    LogicalTruncate,
+    LogicalCreateMaterializedView,
+    LogicalRefreshMaterializedView,
     LogicalInsert,
     LogicalValues,
     LogicalExplain,
     LogicalDummy,
     LogicalGet,
     LogicalProjection,
+    LogicalFilter,
+    LogicalJoin,
+    LogicalWith,
+    LogicalCteScan,
+    LogicalRecursiveCte,
+    LogicalUnion,
+    LogicalIntersect,
+    LogicalExcept,
+    LogicalWindow,
+    LogicalAggregate,
 }
 
 pub type LogicalPlanRef = Rc<LogicalPlan>;
@@ -38,13 +81,34 @@ impl std::fmt::Display for LogicalPlan {
 pub struct LogicalPlanner;
 
 #[derive(thiserror::Error, Debug, PartialEq)]
-pub enum LogicalPlanError {}
+pub enum LogicalPlanError {
+    #[error("unsupported subquery: {0}")]
+    UnsupportedSubquery(&'static str),
+}
 
 impl LogicalPlanner {
     pub fn plan(&self, stmt: BoundStatement) -> Result<LogicalPlan, LogicalPlanError> {
         match stmt {
             BoundStatement::CreateTable(stmt) => self.plan_create_table(stmt),
             BoundStatement::Insert(stmt) => self.plan_insert(stmt),
+            BoundStatement::DropTable(stmt) => self.plan_drop_table(stmt),
+            BoundStatement::AlterTable(stmt) => self.plan_alter_table(stmt),
+            BoundStatement::CreateSchema(stmt) => self.plan_create_schema(stmt),
+            BoundStatement::DropSchema(stmt) => self.plan_drop_schema(stmt),
+            BoundStatement::CreateIndex(stmt) => self.plan_create_index(stmt),
+            BoundStatement::DropIndex(stmt) => self.plan_drop_index(stmt),
+            BoundStatement::CreateView(stmt) => self.plan_create_view(stmt),
+            BoundStatement::DropView(stmt) => self.plan_drop_view(stmt),
+            BoundStatement::Truncate(stmt) => self.plan_truncate(stmt),
+            BoundStatement::CreateMaterializedView(stmt) => {
+                self.plan_create_materialized_view(stmt)
+            }
+            BoundStatement::RefreshMaterializedView(stmt) => {
+                self.plan_refresh_materialized_view(stmt)
+            }
+            BoundStatement::SetSearchPath(_) | BoundStatement::ShowSearchPath => {
+                unreachable!("session statements are not planned")
+            }
             BoundStatement::Select(stmt) => self.plan_select(stmt),
             BoundStatement::Explain(stmt) => self.plan_explain(*stmt),
         }
@@ -60,3 +124,9 @@ pub trait Explain {
         self.explain_inner(level, f)
     }
 }
+
+#[enum_dispatch]
+pub trait PlanNode {
+    /// Returns the types of the output columns.
+    fn out_types(&self) -> Vec<DataType>;
+}
@@ -1,6 +1,7 @@
 use super::*;
-use crate::binder::{BoundExpr, BoundSelect};
+use crate::binder::{BoundExpr, BoundSelect, BoundTableRef};
 use crate::catalog::{ColumnId, TableRefId};
+use crate::types::{DataTypeExt, DataTypeKind};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct LogicalDummy;
@@ -9,6 +10,7 @@ pub struct LogicalDummy;
 pub struct LogicalGet {
     pub table_ref_id: TableRefId,
     pub column_ids: Vec<ColumnId>,
+    pub column_types: Vec<DataType>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -19,23 +21,85 @@ pub struct LogicalProjection {
 
 impl LogicalPlanner {
     pub fn plan_select(&self, stmt: BoundSelect) -> Result<LogicalPlan, LogicalPlanError> {
-        let mut plan: LogicalPlan = LogicalDummy.into();
-
-        if let Some(table_ref) = stmt.from_list.get(0) {
-            plan = LogicalGet {
-                table_ref_id: table_ref.table_ref_id,
-                column_ids: table_ref.column_ids.clone(),
+        let aggregated = stmt.is_aggregated();
+        let mut plan = match stmt.from_table {
+            Some(table_ref) => self.plan_table_ref(table_ref)?,
+            None => LogicalDummy.into(),
+        };
+        if let Some(expr) = stmt.where_clause {
+            plan = self.plan_where(plan, expr)?;
+        }
+        let mut select_list = stmt.select_list;
+        if aggregated {
+            // The HAVING clause is planned with the select list, so that they share the
+            // results of the aggregate functions.
+            let having_pos = select_list.len();
+            select_list.extend(stmt.having);
+            plan = self.plan_aggregate(plan, stmt.group_by, &mut select_list)?;
+            if select_list.len() > having_pos {
+                let having = select_list.pop().unwrap();
+                plan = self.plan_where(plan, having)?;
             }
-            .into();
         }
-        if !stmt.select_list.is_empty() {
+        plan = self.plan_scalar_subqueries(plan, &mut select_list)?;
+        plan = self.plan_window(plan, &mut select_list);
+        if !select_list.is_empty() {
             plan = LogicalProjection {
-                exprs: stmt.select_list,
+                exprs: select_list,
                 child: plan.into(),
             }
             .into();
         }
-        Ok(plan)
+        self.plan_with(plan, stmt.ctes)
+    }
+
+    pub fn plan_table_ref(
+        &self,
+        table_ref: BoundTableRef,
+    ) -> Result<LogicalPlan, LogicalPlanError> {
+        match table_ref {
+            BoundTableRef::BaseTable {
+                table_ref_id,
+                column_ids,
+                column_types,
+            } => Ok(LogicalGet {
+                table_ref_id,
+                column_ids,
+                column_types,
+            }
+            .into()),
+            BoundTableRef::Subquery(query) => {
+                if query.max_outer_depth() > 0 {
+                    return Err(LogicalPlanError::UnsupportedSubquery(
+                        "correlated subquery in FROM clause",
+                    ));
+                }
+                self.plan_select(*query)
+            }
+            BoundTableRef::Cte {
+                cte_id,
+                column_types,
+            } => Ok(LogicalCteScan {
+                cte_id,
+                column_types,
+            }
+            .into()),
+            BoundTableRef::SetOperation(op) => self.plan_set_operation(op),
+            BoundTableRef::Join(join) => {
+                if join.condition.contains_subquery() {
+                    return Err(LogicalPlanError::UnsupportedSubquery(
+                        "subquery in join condition",
+                    ));
+                }
+                Ok(LogicalJoin {
+                    left: self.plan_table_ref(*join.left)?.into(),
+                    right: self.plan_table_ref(*join.right)?.into(),
+                    join_type: join.join_type,
+                    condition: join.condition,
+                }
+                .into())
+            }
+        }
     }
 }
 
@@ -61,3 +125,24 @@ impl Explain for LogicalProjection {
         self.child.explain(level + 1, f)
     }
 }
+
+impl PlanNode for LogicalDummy {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
+
+impl PlanNode for LogicalGet {
+    fn out_types(&self) -> Vec<DataType> {
+        self.column_types.clone()
+    }
+}
+
+impl PlanNode for LogicalProjection {
+    fn out_types(&self) -> Vec<DataType> {
+        self.exprs
+            .iter()
+            .map(|expr| expr.return_type().unwrap())
+            .collect()
+    }
+}
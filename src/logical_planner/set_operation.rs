@@ -0,0 +1,89 @@
+use super::*;
+use crate::binder::BoundSetOperation;
+use crate::parser::SetOperator;
+
+/// Outputs the rows of both children.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalUnion {
+    pub left: LogicalPlanRef,
+    pub right: LogicalPlanRef,
+    pub all: bool,
+}
+
+/// Outputs the rows of `left` that are also in `right`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalIntersect {
+    pub left: LogicalPlanRef,
+    pub right: LogicalPlanRef,
+    pub all: bool,
+}
+
+/// Outputs the rows of `left` that are not in `right`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalExcept {
+    pub left: LogicalPlanRef,
+    pub right: LogicalPlanRef,
+    pub all: bool,
+}
+
+impl LogicalPlanner {
+    pub fn plan_set_operation(
+        &self,
+        stmt: BoundSetOperation,
+    ) -> Result<LogicalPlan, LogicalPlanError> {
+        let left = self.plan_select(*stmt.left)?.into();
+        let right = self.plan_select(*stmt.right)?.into();
+        let all = stmt.all;
+        Ok(match stmt.op {
+            SetOperator::Union => LogicalUnion { left, right, all }.into(),
+            SetOperator::Intersect => LogicalIntersect { left, right, all }.into(),
+            SetOperator::Except => LogicalExcept { left, right, all }.into(),
+        })
+    }
+}
+
+impl Explain for LogicalUnion {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Union: all: {}", self.all)?;
+        self.left.explain(level + 1, f)?;
+        self.right.explain(level + 1, f)
+    }
+}
+
+impl Explain for LogicalIntersect {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Intersect: all: {}", self.all)?;
+        self.left.explain(level + 1, f)?;
+        self.right.explain(level + 1, f)
+    }
+}
+
+impl Explain for LogicalExcept {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Except: all: {}", self.all)?;
+        self.left.explain(level + 1, f)?;
+        self.right.explain(level + 1, f)
+    }
+}
+
+impl PlanNode for LogicalUnion {
+    fn out_types(&self) -> Vec<DataType> {
+        (self.left.out_types().into_iter())
+            .zip(self.right.out_types())
+            .map(|(l, r)| DataType::new(l.kind(), l.is_nullable() || r.is_nullable()))
+            .collect()
+    }
+}
+
+/// The output rows of `INTERSECT` and `EXCEPT` are from the left side.
+impl PlanNode for LogicalIntersect {
+    fn out_types(&self) -> Vec<DataType> {
+        self.left.out_types()
+    }
+}
+
+impl PlanNode for LogicalExcept {
+    fn out_types(&self) -> Vec<DataType> {
+        self.left.out_types()
+    }
+}
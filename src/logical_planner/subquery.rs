@@ -0,0 +1,465 @@
+//! Planning of subqueries.
+//!
+//! Subqueries are never executed per row of the outer query. Instead they are joined with the
+//! outer query:
+//!
+//! - `[NOT] EXISTS` and `[NOT] IN` in the top-level conjunctions of a WHERE clause become
+//!   semi (anti) joins.
+//! - Scalar subqueries become left single joins, which fail if a row of the outer query matches
+//!   more than one row, and the subquery is replaced by a reference to the joined column.
+//!
+//! A correlated subquery is decorrelated by moving the conjunctions of its WHERE clause that
+//! reference the outer query into the join condition. An aggregated scalar subquery is also
+//! grouped by the columns that the conjunctions compare with the outer query.
+
+use std::mem::take;
+
+use super::*;
+use crate::binder::{
+    AggKind, BoundBinaryOp, BoundCase, BoundColumnRef, BoundExpr, BoundIsNull, BoundSelect,
+    BoundSubquery, BoundUnaryOp, JoinType, SubqueryKind,
+};
+use crate::parser::{BinaryOperator, UnaryOperator};
+use crate::types::{DataType, DataTypeExt, DataTypeKind, DataValue};
+
+/// A subquery with its correlated predicates pulled out.
+struct Decorrelated {
+    /// The plan of the subquery without its correlated predicates and select list.
+    plan: LogicalPlan,
+    /// The correlated predicates over the row of the join output.
+    conditions: Vec<BoundExpr>,
+    /// The select list over the row of the join output.
+    select_list: Vec<BoundExpr>,
+}
+
+impl LogicalPlanner {
+    /// Plan the WHERE clause on top of `plan`.
+    pub(super) fn plan_where(
+        &self,
+        mut plan: LogicalPlan,
+        expr: BoundExpr,
+    ) -> Result<LogicalPlan, LogicalPlanError> {
+        let mut filters = vec![];
+        for expr in expr.into_conjunctions() {
+            match expr {
+                BoundExpr::Subquery(subquery) if subquery.kind != SubqueryKind::Scalar => {
+                    plan = self.plan_semi_join(plan, subquery, false)?;
+                }
+                BoundExpr::UnaryOp(BoundUnaryOp {
+                    op: UnaryOperator::Not,
+                    expr,
+                    ..
+                }) if is_semi_join_subquery(&expr) => {
+                    let BoundExpr::Subquery(subquery) = *expr else { unreachable!() };
+                    plan = self.plan_semi_join(plan, subquery, true)?;
+                }
+                expr => filters.push(expr),
+            }
+        }
+        if filters.is_empty() {
+            return Ok(plan);
+        }
+        let mut filters = [BoundExpr::and_all(filters)];
+        plan = self.plan_scalar_subqueries(plan, &mut filters)?;
+        let [expr] = filters;
+        Ok(plan_filter(plan, expr))
+    }
+
+    /// Join scalar subqueries in `exprs` to `plan`, and replace them with the joined columns.
+    pub(super) fn plan_scalar_subqueries(
+        &self,
+        mut plan: LogicalPlan,
+        exprs: &mut [BoundExpr],
+    ) -> Result<LogicalPlan, LogicalPlanError> {
+        let mut result = Ok(());
+        for expr in exprs.iter_mut() {
+            expr.visit_mut(&mut |e| {
+                let BoundExpr::Subquery(subquery) = e else { return };
+                if result.is_err() {
+                    return;
+                }
+                if subquery.kind != SubqueryKind::Scalar {
+                    result = Err(LogicalPlanError::UnsupportedSubquery(
+                        "EXISTS or IN subquery outside of the conjunctions of WHERE clause",
+                    ));
+                    return;
+                }
+                let left = std::mem::replace(&mut plan, LogicalDummy.into());
+                let subquery = std::mem::replace(subquery, empty_subquery());
+                match self.plan_scalar_subquery(left, subquery) {
+                    Ok((join, value)) => {
+                        plan = join;
+                        *e = value;
+                    }
+                    Err(err) => result = Err(err),
+                }
+            });
+        }
+        result.map(|_| plan)
+    }
+
+    /// Returns the join and the expression of the subquery value.
+    fn plan_scalar_subquery(
+        &self,
+        plan: LogicalPlan,
+        subquery: BoundSubquery,
+    ) -> Result<(LogicalPlan, BoundExpr), LogicalPlanError> {
+        let offset = plan.out_types().len();
+        let return_type = subquery.return_type();
+        let (right, conditions, mut select_list) = if !subquery.is_correlated() {
+            let value = BoundExpr::ColumnRef(BoundColumnRef {
+                index: offset,
+                depth: 0,
+                return_type,
+            });
+            (self.plan_select(*subquery.query)?, vec![], vec![value])
+        } else if subquery.query.is_aggregated() {
+            let d = self.decorrelate_aggregate(*subquery.query, offset, return_type)?;
+            (d.plan, d.conditions, d.select_list)
+        } else {
+            let d = self.decorrelate(*subquery.query, offset)?;
+            (d.plan, d.conditions, d.select_list)
+        };
+        let join = LogicalJoin {
+            left: plan.into(),
+            right: right.into(),
+            join_type: JoinType::LeftSingle,
+            condition: BoundExpr::and_all(conditions),
+        };
+        Ok((join.into(), select_list.remove(0)))
+    }
+
+    /// Plan an `EXISTS` or `IN` subquery as a semi join, or an anti join if negated.
+    fn plan_semi_join(
+        &self,
+        plan: LogicalPlan,
+        subquery: BoundSubquery,
+        negated: bool,
+    ) -> Result<LogicalPlan, LogicalPlanError> {
+        let offset = plan.out_types().len();
+        let correlated = subquery.is_correlated();
+        let (right, mut conditions, select_list) = if correlated {
+            let d = self.decorrelate(*subquery.query, offset)?;
+            (d.plan, d.conditions, d.select_list)
+        } else {
+            let right = self.plan_select(*subquery.query)?;
+            let select_list = (right.out_types().into_iter().enumerate())
+                .map(|(i, return_type)| {
+                    BoundExpr::ColumnRef(BoundColumnRef {
+                        index: offset + i,
+                        depth: 0,
+                        return_type,
+                    })
+                })
+                .collect();
+            (right, vec![], select_list)
+        };
+        let negated = match subquery.kind {
+            SubqueryKind::Exists { negated: n } => n ^ negated,
+            SubqueryKind::In { expr, negated: n } => {
+                if expr.contains_subquery() {
+                    return Err(LogicalPlanError::UnsupportedSubquery(
+                        "subquery in the left side of IN",
+                    ));
+                }
+                let left = *expr;
+                let right = select_list.into_iter().next().unwrap();
+                let mut condition = binary_op(left.clone(), BinaryOperator::Eq, right.clone());
+                if n ^ negated {
+                    // `x NOT IN (..)` is not true if any `x = y` is null.
+                    condition = binary_op(condition, BinaryOperator::Or, is_null(left));
+                    condition = binary_op(condition, BinaryOperator::Or, is_null(right));
+                }
+                conditions.push(condition);
+                n ^ negated
+            }
+            SubqueryKind::Scalar => unreachable!(),
+        };
+        Ok(LogicalJoin {
+            left: plan.into(),
+            right: right.into(),
+            join_type: if negated {
+                JoinType::Anti
+            } else {
+                JoinType::Semi
+            },
+            condition: BoundExpr::and_all(conditions),
+        }
+        .into())
+    }
+
+    /// Decorrelate a subquery whose outer query outputs `offset` columns.
+    fn decorrelate(
+        &self,
+        mut query: BoundSelect,
+        offset: usize,
+    ) -> Result<Decorrelated, LogicalPlanError> {
+        check_correlated(&query)?;
+        if query.is_aggregated() {
+            return Err(LogicalPlanError::UnsupportedSubquery(
+                "aggregation in correlated EXISTS or IN subquery",
+            ));
+        }
+        let (filters, mut conditions) = split_correlated(query.where_clause.take())?;
+
+        let mut plan = match query.from_table.take() {
+            Some(table_ref) => self.plan_table_ref(table_ref)?,
+            None => LogicalDummy.into(),
+        };
+        if !filters.is_empty() {
+            plan = self.plan_where(plan, BoundExpr::and_all(filters))?;
+        }
+        let mut select_list = take(&mut query.select_list);
+        plan = self.plan_scalar_subqueries(plan, &mut select_list)?;
+        plan = self.plan_with(plan, take(&mut query.ctes))?;
+
+        // The outer query is on the left side of the join, and the subquery on the right side.
+        let mut shift = |e: &mut BoundExpr| {
+            if let BoundExpr::ColumnRef(c) = e {
+                match c.depth {
+                    0 => c.index += offset,
+                    _ => c.depth = 0,
+                }
+            }
+        };
+        for expr in conditions.iter_mut().chain(select_list.iter_mut()) {
+            expr.visit_mut(&mut shift);
+        }
+        Ok(Decorrelated {
+            plan,
+            conditions,
+            select_list,
+        })
+    }
+
+    /// Decorrelate an aggregated scalar subquery whose outer query outputs `offset` columns.
+    ///
+    /// The correlated predicates must compare an expression over the subquery with one over the
+    /// outer query for equality. The subquery is grouped by the former, so that a row of the
+    /// outer query matches at most one group.
+    fn decorrelate_aggregate(
+        &self,
+        mut query: BoundSelect,
+        offset: usize,
+        return_type: DataType,
+    ) -> Result<Decorrelated, LogicalPlanError> {
+        check_correlated(&query)?;
+        let (filters, conditions) = split_correlated(query.where_clause.take())?;
+        let mut keys = vec![];
+        for expr in conditions {
+            let BoundExpr::BinaryOp(BoundBinaryOp {
+                op: BinaryOperator::Eq,
+                left_expr,
+                right_expr,
+                ..
+            }) = expr else {
+                return Err(LogicalPlanError::UnsupportedSubquery(
+                    "correlated predicate other than equality in aggregated subquery",
+                ));
+            };
+            match (is_outer(&left_expr), is_outer(&right_expr)) {
+                (false, true) if left_expr.max_outer_depth() == 0 => {
+                    keys.push((*left_expr, *right_expr))
+                }
+                (true, false) if right_expr.max_outer_depth() == 0 => {
+                    keys.push((*right_expr, *left_expr))
+                }
+                _ => {
+                    return Err(LogicalPlanError::UnsupportedSubquery(
+                        "correlated predicate other than equality in aggregated subquery",
+                    ))
+                }
+            }
+        }
+
+        // Without GROUP BY, the aggregates are computed even if the subquery has no rows, but
+        // the groups by the keys only have the rows that match. So the HAVING clause is
+        // evaluated after the join, when the row on the outer query is known to match or not.
+        let grouped = !query.group_by.is_empty();
+        let exprs = query.select_list.iter().chain(&query.having);
+        if !grouped && exprs.clone().any(|e| e.contains_subquery()) {
+            return Err(LogicalPlanError::UnsupportedSubquery(
+                "subquery in aggregated correlated subquery",
+            ));
+        }
+        let having = match grouped {
+            true => None,
+            false => query.having.take(),
+        };
+        let value = query.select_list.remove(0);
+        query.select_list = [value.clone()].into_iter().chain(having.clone()).collect();
+        let key_pos = query.select_list.len();
+        for (inner, _) in &keys {
+            query.select_list.push(inner.clone());
+            query.group_by.push(inner.clone());
+        }
+        query.where_clause = Some(filters)
+            .filter(|filters| !filters.is_empty())
+            .map(BoundExpr::and_all);
+        let plan = self.plan_select(query)?;
+
+        let types = plan.out_types();
+        let column = |index: usize| {
+            BoundExpr::ColumnRef(BoundColumnRef {
+                index: offset + index,
+                depth: 0,
+                return_type: types[index].kind().nullable(),
+            })
+        };
+        let conditions = (keys.into_iter().enumerate())
+            .map(|(i, (_, mut outer))| {
+                outer.visit_mut(&mut |e| {
+                    if let BoundExpr::ColumnRef(c) = e {
+                        c.depth = 0;
+                    }
+                });
+                binary_op(column(key_pos + i), BinaryOperator::Eq, outer)
+            })
+            .collect();
+        if grouped {
+            return Ok(Decorrelated {
+                plan,
+                conditions,
+                select_list: vec![column(0)],
+            });
+        }
+        // A row of the outer query that matches no group gets the value over no rows, e.g. 0
+        // from `count(*)`. The keys of a matched group are not null.
+        let unmatched = is_null(column(key_pos));
+        let mut select = case(unmatched.clone(), no_rows(value), column(0), &return_type);
+        if let Some(having) = having {
+            let boolean = DataTypeKind::Boolean.nullable();
+            let having = case(unmatched, no_rows(having), column(1), &boolean);
+            select = BoundExpr::Case(BoundCase {
+                conditions: vec![having],
+                results: vec![select],
+                else_result: None,
+                return_type,
+            });
+        }
+        Ok(Decorrelated {
+            plan,
+            conditions,
+            select_list: vec![select],
+        })
+    }
+}
+
+fn empty_subquery() -> BoundSubquery {
+    BoundSubquery {
+        kind: SubqueryKind::Scalar,
+        query: BoundSelect {
+            ctes: vec![],
+            select_list: vec![],
+            aliases: vec![],
+            from_table: None,
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+        }
+        .into(),
+    }
+}
+
+/// Returns true if the expression references the outer query, and not the query it is in.
+fn is_outer(expr: &BoundExpr) -> bool {
+    let mut inner = false;
+    expr.visit(&mut |e| inner |= matches!(e, BoundExpr::ColumnRef(c) if c.depth == 0));
+    !inner && expr.max_outer_depth() == 1
+}
+
+/// Returns true if the expression is an `EXISTS` or `IN` subquery.
+fn is_semi_join_subquery(expr: &BoundExpr) -> bool {
+    matches!(expr, BoundExpr::Subquery(s) if s.kind != SubqueryKind::Scalar)
+}
+
+/// Check that a correlated subquery only references the query it is in, and only in the WHERE
+/// clause.
+fn check_correlated(query: &BoundSelect) -> Result<(), LogicalPlanError> {
+    if query.max_outer_depth() > 1 {
+        return Err(LogicalPlanError::UnsupportedSubquery(
+            "reference to a query more than one level out",
+        ));
+    }
+    let from_correlated = (query.from_table.as_ref()).map_or(false, |t| t.max_outer_depth() > 0);
+    let exprs = (query.select_list.iter())
+        .chain(&query.group_by)
+        .chain(&query.having);
+    if from_correlated || exprs.clone().any(|e| e.max_outer_depth() > 0) {
+        return Err(LogicalPlanError::UnsupportedSubquery(
+            "outer reference outside of WHERE clause",
+        ));
+    }
+    if query.select_list.iter().any(|e| e.contains_window()) {
+        return Err(LogicalPlanError::UnsupportedSubquery(
+            "window function in correlated subquery",
+        ));
+    }
+    Ok(())
+}
+
+/// Split the WHERE clause of a correlated subquery into the conjunctions that only reference
+/// the subquery, and the ones that reference the outer query.
+fn split_correlated(
+    where_clause: Option<BoundExpr>,
+) -> Result<(Vec<BoundExpr>, Vec<BoundExpr>), LogicalPlanError> {
+    let mut filters = vec![];
+    let mut conditions = vec![];
+    for expr in where_clause.map_or(vec![], |e| e.into_conjunctions()) {
+        if expr.max_outer_depth() == 0 {
+            filters.push(expr);
+        } else if expr.contains_subquery() {
+            return Err(LogicalPlanError::UnsupportedSubquery(
+                "subquery in correlated predicate",
+            ));
+        } else {
+            conditions.push(expr);
+        }
+    }
+    Ok((filters, conditions))
+}
+
+/// Returns the value of an expression over the aggregates of no rows.
+fn no_rows(mut expr: BoundExpr) -> BoundExpr {
+    expr.rewrite(&mut |e| {
+        let BoundExpr::AggCall(agg) = e else { return false };
+        let value = match &agg.kind {
+            AggKind::Count => DataValue::Int64(0),
+            AggKind::User(f) => (f.create)().output(),
+            _ => DataValue::Null,
+        };
+        *e = BoundExpr::Constant(value).cast_if_null(agg.return_type.kind());
+        true
+    });
+    expr
+}
+
+fn case(
+    condition: BoundExpr,
+    result: BoundExpr,
+    else_result: BoundExpr,
+    return_type: &DataType,
+) -> BoundExpr {
+    BoundExpr::Case(BoundCase {
+        conditions: vec![condition],
+        results: vec![result.cast_to(&return_type.kind())],
+        else_result: Some(else_result.into()),
+        return_type: return_type.clone(),
+    })
+}
+
+fn binary_op(left: BoundExpr, op: BinaryOperator, right: BoundExpr) -> BoundExpr {
+    BoundExpr::BinaryOp(BoundBinaryOp {
+        op,
+        left_expr: left.into(),
+        right_expr: right.into(),
+        return_type: Some(DataTypeKind::Boolean.nullable()),
+    })
+}
+
+fn is_null(expr: BoundExpr) -> BoundExpr {
+    BoundExpr::IsNull(BoundIsNull {
+        expr: expr.into(),
+        negated: false,
+    })
+}
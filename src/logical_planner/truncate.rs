@@ -0,0 +1,36 @@
+use itertools::Itertools;
+
+use super::*;
+use crate::binder::BoundTruncate;
+use crate::catalog::TableRefId;
+use crate::types::{DataType, DataTypeExt, DataTypeKind};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalTruncate {
+    pub table_ref_ids: Vec<TableRefId>,
+}
+
+impl LogicalPlanner {
+    pub fn plan_truncate(&self, stmt: BoundTruncate) -> Result<LogicalPlan, LogicalPlanError> {
+        Ok(LogicalTruncate {
+            table_ref_ids: stmt.table_ref_ids,
+        }
+        .into())
+    }
+}
+
+impl Explain for LogicalTruncate {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Truncate: tables [{}]",
+            self.table_ref_ids.iter().map(|id| id.table_id).join(", ")
+        )
+    }
+}
+
+impl PlanNode for LogicalTruncate {
+    fn out_types(&self) -> Vec<DataType> {
+        vec![DataTypeKind::Int(None).not_null()]
+    }
+}
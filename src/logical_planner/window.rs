@@ -0,0 +1,55 @@
+use super::*;
+use crate::binder::{BoundColumnRef, BoundExpr, BoundWindow};
+
+/// Appends the results of the window functions to the rows of `child`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalWindow {
+    pub windows: Vec<BoundWindow>,
+    pub child: LogicalPlanRef,
+}
+
+impl LogicalPlanner {
+    /// Compute the window functions in `exprs` on top of `plan`, and replace them with the
+    /// columns of the results.
+    pub(super) fn plan_window(&self, plan: LogicalPlan, exprs: &mut [BoundExpr]) -> LogicalPlan {
+        let offset = plan.out_types().len();
+        let mut windows = vec![];
+        for expr in exprs.iter_mut() {
+            expr.visit_mut(&mut |e| {
+                let BoundExpr::Window(window) = e else { return };
+                let column_ref = BoundExpr::ColumnRef(BoundColumnRef {
+                    index: offset + windows.len(),
+                    depth: 0,
+                    return_type: window.return_type.clone(),
+                });
+                let BoundExpr::Window(window) = std::mem::replace(e, column_ref) else {
+                    unreachable!()
+                };
+                windows.push(window);
+            });
+        }
+        if windows.is_empty() {
+            return plan;
+        }
+        LogicalWindow {
+            windows,
+            child: plan.into(),
+        }
+        .into()
+    }
+}
+
+impl Explain for LogicalWindow {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Window: exprs: {:?}", self.windows)?;
+        self.child.explain(level + 1, f)
+    }
+}
+
+impl PlanNode for LogicalWindow {
+    fn out_types(&self) -> Vec<DataType> {
+        let mut types = self.child.out_types();
+        types.extend(self.windows.iter().map(|w| w.return_type.clone()));
+        types
+    }
+}
@@ -1,9 +1,124 @@
 pub use sqlparser::ast::*;
 use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::keywords::Keyword;
 use sqlparser::parser::Parser;
 pub use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+/// The flag of the `CACHE` statement that `REFRESH MATERIALIZED VIEW name` is parsed into, as
+/// sqlparser has no statement for it.
+const REFRESH_MATERIALIZED_VIEW: &str = "REFRESH MATERIALIZED VIEW";
 
 pub fn parse(sql: &str) -> Result<Vec<Statement>, ParserError> {
     let dialect = PostgreSqlDialect {};
-    Parser::parse_sql(&dialect, sql)
+    let tokens = Tokenizer::new(&dialect, sql).tokenize()?;
+    let mut parser = Parser::new(tokens, &dialect);
+    let mut stmts = vec![];
+    let mut expecting_statement_delimiter = false;
+    loop {
+        // ignore empty statements
+        while parser.consume_token(&Token::SemiColon) {
+            expecting_statement_delimiter = false;
+        }
+        if parser.peek_token() == Token::EOF {
+            break;
+        }
+        if expecting_statement_delimiter {
+            return parser.expected("end of statement", parser.peek_token());
+        }
+        stmts.push(parse_statement(&mut parser)?);
+        expecting_statement_delimiter = true;
+    }
+    Ok(stmts)
+}
+
+/// Parse a statement, including the ones that sqlparser does not support:
+///
+/// - `REFRESH MATERIALIZED VIEW name`, see [`refreshed_materialized_view`].
+/// - `DROP MATERIALIZED VIEW`, which is parsed as `DROP TABLE`, as materialized views are
+///   stored as tables.
+/// - `TRUNCATE [TABLE] name [, ...]`, see [`truncated_tables`].
+fn parse_statement(parser: &mut Parser) -> Result<Statement, ParserError> {
+    if parser.parse_keyword(Keyword::TRUNCATE) {
+        let _ = parser.parse_keyword(Keyword::TABLE);
+        let mut names = parser.parse_comma_separated(Parser::parse_object_name)?;
+        let table_name = names.remove(0);
+        let others = names
+            .into_iter()
+            .map(|name| Expr::CompoundIdentifier(name.0));
+        let partitions = Some(others.collect::<Vec<_>>()).filter(|exprs| !exprs.is_empty());
+        return Ok(Statement::Truncate {
+            table_name,
+            partitions,
+        });
+    }
+    if is_word(parser.peek_token(), "REFRESH") {
+        parser.next_token();
+        parser.expect_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])?;
+        let table_name = parser.parse_object_name()?;
+        return Ok(Statement::Cache {
+            table_flag: Some(ObjectName(vec![Ident::new(REFRESH_MATERIALIZED_VIEW)])),
+            table_name,
+            has_as: false,
+            options: vec![],
+            query: None,
+        });
+    }
+    if is_word(parser.peek_token(), "DROP") && is_word(parser.peek_nth_token(1), "MATERIALIZED") {
+        parser.next_token();
+        parser.next_token();
+        parser.expect_keyword(Keyword::VIEW)?;
+        let if_exists = parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let names = parser.parse_comma_separated(Parser::parse_object_name)?;
+        let cascade = parser.parse_keyword(Keyword::CASCADE);
+        let restrict = !cascade && parser.parse_keyword(Keyword::RESTRICT);
+        return Ok(Statement::Drop {
+            object_type: ObjectType::Table,
+            if_exists,
+            names,
+            cascade,
+            restrict,
+            purge: false,
+        });
+    }
+    parser.parse_statement()
+}
+
+/// Returns the names of the tables if the statement is `TRUNCATE`. sqlparser has room for only
+/// one table in the statement, so the names after the first one are put in its partitions.
+pub fn truncated_tables(stmt: &Statement) -> Option<Vec<ObjectName>> {
+    let Statement::Truncate { table_name, partitions } = stmt else {
+        return None;
+    };
+    let mut names = vec![table_name.clone()];
+    for expr in partitions.iter().flatten() {
+        match expr {
+            Expr::CompoundIdentifier(idents) => names.push(ObjectName(idents.clone())),
+            _ => panic!("invalid table name in TRUNCATE: {}", expr),
+        }
+    }
+    Some(names)
+}
+
+/// Returns true if the token is the unquoted word, which may not be a keyword of sqlparser.
+fn is_word(token: Token, word: &str) -> bool {
+    match token {
+        Token::Word(w) => w.quote_style.is_none() && w.value.eq_ignore_ascii_case(word),
+        _ => false,
+    }
+}
+
+/// Returns the name of the materialized view if the statement is
+/// `REFRESH MATERIALIZED VIEW name`.
+pub fn refreshed_materialized_view(stmt: &Statement) -> Option<&ObjectName> {
+    match stmt {
+        Statement::Cache {
+            table_flag: Some(ObjectName(flag)),
+            table_name,
+            ..
+        } if matches!(flag.as_slice(), [ident] if ident.value == REFRESH_MATERIALIZED_VIEW) => {
+            Some(table_name)
+        }
+        _ => None,
+    }
 }
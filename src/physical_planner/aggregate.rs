@@ -0,0 +1,58 @@
+use super::*;
+use crate::binder::{BoundAggCall, BoundExpr};
+use crate::logical_planner::LogicalAggregate;
+
+/// Aggregates all rows into a single row.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalSimpleAgg {
+    pub aggs: Vec<BoundAggCall>,
+    pub child: Box<PhysicalPlan>,
+}
+
+/// Groups rows using a hash table and aggregates each group into a row.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalHashAgg {
+    pub aggs: Vec<BoundAggCall>,
+    pub group_by: Vec<BoundExpr>,
+    pub child: Box<PhysicalPlan>,
+}
+
+impl PhysicalPlanner {
+    pub fn plan_aggregate(
+        &self,
+        plan: &LogicalAggregate,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        let child = self.plan(&plan.child)?.into();
+        if plan.group_by.is_empty() {
+            return Ok(PhysicalSimpleAgg {
+                aggs: plan.aggs.clone(),
+                child,
+            }
+            .into());
+        }
+        Ok(PhysicalHashAgg {
+            aggs: plan.aggs.clone(),
+            group_by: plan.group_by.clone(),
+            child,
+        }
+        .into())
+    }
+}
+
+impl Explain for PhysicalSimpleAgg {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "SimpleAgg: aggs: {:?}", self.aggs)?;
+        self.child.explain(level + 1, f)
+    }
+}
+
+impl Explain for PhysicalHashAgg {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "HashAgg: group_by: {:?}, aggs: {:?}",
+            self.group_by, self.aggs
+        )?;
+        self.child.explain(level + 1, f)
+    }
+}
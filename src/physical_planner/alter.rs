@@ -0,0 +1,33 @@
+use super::*;
+use crate::binder::AlterTableAction;
+use crate::catalog::TableRefId;
+use crate::logical_planner::LogicalAlterTable;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalAlterTable {
+    pub table_ref_id: TableRefId,
+    pub action: AlterTableAction,
+}
+
+impl PhysicalPlanner {
+    pub fn plan_alter_table(
+        &self,
+        plan: &LogicalAlterTable,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalAlterTable {
+            table_ref_id: plan.table_ref_id,
+            action: plan.action.clone(),
+        }
+        .into())
+    }
+}
+
+impl Explain for PhysicalAlterTable {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "AlterTable: table #{}, action: {:?}",
+            self.table_ref_id.table_id, self.action
+        )
+    }
+}
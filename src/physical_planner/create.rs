@@ -1,14 +1,43 @@
 use itertools::Itertools;
 
 use super::*;
-use crate::catalog::{ColumnDesc, SchemaId};
-use crate::logical_planner::LogicalCreateTable;
+use crate::binder::BoundExpr;
+use crate::catalog::{
+    ColumnDesc, ColumnId, SchemaId, TableRefId, UniqueConstraint, ViewDefinition,
+};
+use crate::logical_planner::{
+    LogicalCreateIndex, LogicalCreateSchema, LogicalCreateTable, LogicalCreateView,
+};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct PhysicalCreateTable {
     pub schema_id: SchemaId,
     pub table_name: String,
     pub columns: Vec<(String, ColumnDesc)>,
+    pub uniques: Vec<UniqueConstraint>,
+    pub checks: Vec<(String, BoundExpr)>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalCreateSchema {
+    pub schema_name: String,
+    pub if_not_exists: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalCreateIndex {
+    pub table_ref_id: TableRefId,
+    pub index_name: String,
+    pub column_id: ColumnId,
+    pub if_not_exists: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalCreateView {
+    pub schema_id: SchemaId,
+    pub view_name: String,
+    pub definition: ViewDefinition,
+    pub or_replace: bool,
 }
 
 impl PhysicalPlanner {
@@ -20,6 +49,45 @@ impl PhysicalPlanner {
             schema_id: plan.schema_id,
             table_name: plan.table_name.clone(),
             columns: plan.columns.clone(),
+            uniques: plan.uniques.clone(),
+            checks: plan.checks.clone(),
+        }
+        .into())
+    }
+
+    pub fn plan_create_schema(
+        &self,
+        plan: &LogicalCreateSchema,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalCreateSchema {
+            schema_name: plan.schema_name.clone(),
+            if_not_exists: plan.if_not_exists,
+        }
+        .into())
+    }
+
+    pub fn plan_create_index(
+        &self,
+        plan: &LogicalCreateIndex,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalCreateIndex {
+            table_ref_id: plan.table_ref_id,
+            index_name: plan.index_name.clone(),
+            column_id: plan.column_id,
+            if_not_exists: plan.if_not_exists,
+        }
+        .into())
+    }
+
+    pub fn plan_create_view(
+        &self,
+        plan: &LogicalCreateView,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalCreateView {
+            schema_id: plan.schema_id,
+            view_name: plan.view_name.clone(),
+            definition: plan.definition.clone(),
+            or_replace: plan.or_replace,
         }
         .into())
     }
@@ -38,3 +106,37 @@ impl Explain for PhysicalCreateTable {
         )
     }
 }
+
+impl Explain for PhysicalCreateSchema {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "CreateSchema: name: {}, if_not_exists: {}",
+            self.schema_name, self.if_not_exists
+        )
+    }
+}
+
+impl Explain for PhysicalCreateIndex {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "CreateIndex: name: {}, table #{}, column: {}",
+            self.index_name, self.table_ref_id.table_id, self.column_id
+        )
+    }
+}
+
+impl Explain for PhysicalCreateView {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "CreateView: name: {}, columns: [{}], or_replace: {}",
+            self.view_name,
+            (self.definition.columns.iter())
+                .map(|(name, ty)| format!("{}: {:?}", name, ty))
+                .join(", "),
+            self.or_replace
+        )
+    }
+}
@@ -0,0 +1,84 @@
+use super::*;
+use crate::logical_planner::{LogicalCteScan, LogicalRecursiveCte, LogicalWith};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalWith {
+    pub ctes: Vec<(usize, PhysicalPlan)>,
+    pub child: Box<PhysicalPlan>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalCteScan {
+    pub cte_id: usize,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalRecursiveCte {
+    pub cte_id: usize,
+    pub base: Box<PhysicalPlan>,
+    pub recursive: Box<PhysicalPlan>,
+    pub union_all: bool,
+}
+
+impl PhysicalPlanner {
+    pub fn plan_with(&self, plan: &LogicalWith) -> Result<PhysicalPlan, PhysicalPlanError> {
+        let mut ctes = vec![];
+        for (id, cte) in plan.ctes.iter() {
+            ctes.push((*id, self.plan(cte)?));
+        }
+        Ok(PhysicalWith {
+            ctes,
+            child: self.plan(&plan.child)?.into(),
+        }
+        .into())
+    }
+
+    pub fn plan_cte_scan(&self, plan: &LogicalCteScan) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalCteScan {
+            cte_id: plan.cte_id,
+        }
+        .into())
+    }
+
+    pub fn plan_recursive_cte(
+        &self,
+        plan: &LogicalRecursiveCte,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalRecursiveCte {
+            cte_id: plan.cte_id,
+            base: self.plan(&plan.base)?.into(),
+            recursive: self.plan(&plan.recursive)?.into(),
+            union_all: plan.union_all,
+        }
+        .into())
+    }
+}
+
+impl Explain for PhysicalWith {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "With:")?;
+        for (id, plan) in self.ctes.iter() {
+            writeln!(f, "{}Cte: id: {}", "  ".repeat(level + 1), id)?;
+            plan.explain(level + 2, f)?;
+        }
+        self.child.explain(level + 1, f)
+    }
+}
+
+impl Explain for PhysicalCteScan {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "CteScan: id: {}", self.cte_id)
+    }
+}
+
+impl Explain for PhysicalRecursiveCte {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "RecursiveCte: id: {}, all: {}",
+            self.cte_id, self.union_all
+        )?;
+        self.base.explain(level + 1, f)?;
+        self.recursive.explain(level + 1, f)
+    }
+}
@@ -0,0 +1,112 @@
+use itertools::Itertools;
+
+use super::*;
+use crate::catalog::{SchemaId, TableRefId};
+use crate::logical_planner::{
+    LogicalDropIndex, LogicalDropSchema, LogicalDropTable, LogicalDropView,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalDropTable {
+    pub table_ref_ids: Vec<TableRefId>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalDropSchema {
+    pub schema_ids: Vec<SchemaId>,
+    pub cascade: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalDropIndex {
+    pub indexes: Vec<(TableRefId, String)>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalDropView {
+    pub view_ref_ids: Vec<TableRefId>,
+}
+
+impl PhysicalPlanner {
+    pub fn plan_drop_table(
+        &self,
+        plan: &LogicalDropTable,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalDropTable {
+            table_ref_ids: plan.table_ref_ids.clone(),
+        }
+        .into())
+    }
+
+    pub fn plan_drop_schema(
+        &self,
+        plan: &LogicalDropSchema,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalDropSchema {
+            schema_ids: plan.schema_ids.clone(),
+            cascade: plan.cascade,
+        }
+        .into())
+    }
+
+    pub fn plan_drop_view(
+        &self,
+        plan: &LogicalDropView,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalDropView {
+            view_ref_ids: plan.view_ref_ids.clone(),
+        }
+        .into())
+    }
+
+    pub fn plan_drop_index(
+        &self,
+        plan: &LogicalDropIndex,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalDropIndex {
+            indexes: plan.indexes.clone(),
+        }
+        .into())
+    }
+}
+
+impl Explain for PhysicalDropTable {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "DropTable: tables [{}]",
+            self.table_ref_ids.iter().map(|id| id.table_id).join(", ")
+        )
+    }
+}
+
+impl Explain for PhysicalDropSchema {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "DropSchema: schemas [{}], cascade: {}",
+            self.schema_ids.iter().join(", "),
+            self.cascade
+        )
+    }
+}
+
+impl Explain for PhysicalDropIndex {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "DropIndex: indexes [{}]",
+            self.indexes.iter().map(|(_, name)| name).join(", ")
+        )
+    }
+}
+
+impl Explain for PhysicalDropView {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "DropView: views [{}]",
+            self.view_ref_ids.iter().map(|id| id.table_id).join(", ")
+        )
+    }
+}
@@ -0,0 +1,37 @@
+use super::*;
+use crate::binder::BoundExpr;
+use crate::logical_planner::{LogicalFilter, LogicalPlan};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalFilter {
+    pub expr: BoundExpr,
+    pub child: Box<PhysicalPlan>,
+}
+
+impl PhysicalPlanner {
+    /// Plan a filter. A filter on a table is planned on an index scan if it restricts an
+    /// indexed column to a range, and is kept for the other conditions.
+    pub fn plan_filter(&self, plan: &LogicalFilter) -> Result<PhysicalPlan, PhysicalPlanError> {
+        if let LogicalPlan::LogicalGet(get) = &*plan.child {
+            if let Some(scan) = self.plan_index_scan(get, &plan.expr) {
+                return Ok(PhysicalFilter {
+                    expr: plan.expr.clone(),
+                    child: scan.into(),
+                }
+                .into());
+            }
+        }
+        Ok(PhysicalFilter {
+            expr: plan.expr.clone(),
+            child: self.plan(&plan.child)?.into(),
+        }
+        .into())
+    }
+}
+
+impl Explain for PhysicalFilter {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Filter: expr: {:?}", self.expr)?;
+        self.child.explain(level + 1, f)
+    }
+}
@@ -0,0 +1,198 @@
+use std::ops::Bound;
+
+use super::*;
+use crate::binder::{BoundBetween, BoundBinaryOp, BoundExpr};
+use crate::catalog::{ColumnId, TableRefId};
+use crate::logical_planner::LogicalGet;
+use crate::parser::BinaryOperator;
+use crate::storage::IndexRange;
+use crate::types::{DataTypeKind, DataTypeKindExt, DataValue};
+
+/// Scan the rows of a table whose values in an indexed column are in a range.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalIndexScan {
+    pub table_ref_id: TableRefId,
+    pub column_ids: Vec<ColumnId>,
+    /// The indexed column.
+    pub index_column_id: ColumnId,
+    pub range: IndexRange,
+}
+
+impl PhysicalPlanner {
+    /// Returns an index scan of the table if the filter restricts an indexed column to a
+    /// range. An equality is preferred to other ranges.
+    pub fn plan_index_scan(&self, get: &LogicalGet, expr: &BoundExpr) -> Option<PhysicalPlan> {
+        let schema = self.catalog.get_schema(get.table_ref_id.schema_id)?;
+        let table = schema.get_table(get.table_ref_id.table_id)?;
+        let mut conjuncts = vec![];
+        split_conjuncts(expr, &mut conjuncts);
+        let mut best: Option<(ColumnId, IndexRange)> = None;
+        for index in table.all_indexes() {
+            let Some(pos) = get.column_ids.iter().position(|&id| id == index.column_id) else {
+                continue;
+            };
+            let kind = get.column_types[pos].kind();
+            let mut range = (Bound::Unbounded, Bound::Unbounded);
+            for conjunct in &conjuncts {
+                if let Some((low, high)) = column_range(conjunct, pos, &kind) {
+                    range.0 = tighter(range.0, low, true);
+                    range.1 = tighter(range.1, high, false);
+                }
+            }
+            if range == (Bound::Unbounded, Bound::Unbounded) {
+                continue;
+            }
+            if best
+                .as_ref()
+                .map_or(true, |(_, best)| !is_point(best) && is_point(&range))
+            {
+                best = Some((index.column_id, range));
+            }
+        }
+        let (index_column_id, range) = best?;
+        Some(
+            PhysicalIndexScan {
+                table_ref_id: get.table_ref_id,
+                column_ids: get.column_ids.clone(),
+                index_column_id,
+                range,
+            }
+            .into(),
+        )
+    }
+}
+
+/// Collect the expressions joined by `AND`.
+fn split_conjuncts<'a>(expr: &'a BoundExpr, conjuncts: &mut Vec<&'a BoundExpr>) {
+    match expr {
+        BoundExpr::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::And,
+            left_expr,
+            right_expr,
+            ..
+        }) => {
+            split_conjuncts(left_expr, conjuncts);
+            split_conjuncts(right_expr, conjuncts);
+        }
+        expr => conjuncts.push(expr),
+    }
+}
+
+/// Returns the range that the condition restricts the column at `pos` of the input to, if it
+/// compares the column with constants of its type.
+fn column_range(expr: &BoundExpr, pos: usize, kind: &DataTypeKind) -> Option<IndexRange> {
+    let is_column =
+        |e: &BoundExpr| matches!(e, BoundExpr::ColumnRef(c) if c.depth == 0 && c.index == pos);
+    match expr {
+        BoundExpr::BinaryOp(BoundBinaryOp {
+            op,
+            left_expr,
+            right_expr,
+            ..
+        }) => {
+            // the column is put on the left
+            let (op, value) = if is_column(left_expr) {
+                (op.clone(), constant(right_expr, kind)?)
+            } else if is_column(right_expr) {
+                let op = match op {
+                    BinaryOperator::Lt => BinaryOperator::Gt,
+                    BinaryOperator::LtEq => BinaryOperator::GtEq,
+                    BinaryOperator::Gt => BinaryOperator::Lt,
+                    BinaryOperator::GtEq => BinaryOperator::LtEq,
+                    op => op.clone(),
+                };
+                (op, constant(left_expr, kind)?)
+            } else {
+                return None;
+            };
+            match op {
+                BinaryOperator::Eq => {
+                    Some((Bound::Included(value.clone()), Bound::Included(value)))
+                }
+                BinaryOperator::Lt => Some((Bound::Unbounded, Bound::Excluded(value))),
+                BinaryOperator::LtEq => Some((Bound::Unbounded, Bound::Included(value))),
+                BinaryOperator::Gt => Some((Bound::Excluded(value), Bound::Unbounded)),
+                BinaryOperator::GtEq => Some((Bound::Included(value), Bound::Unbounded)),
+                _ => None,
+            }
+        }
+        BoundExpr::Between(BoundBetween {
+            expr,
+            low,
+            high,
+            negated: false,
+            ..
+        }) if is_column(expr) => Some((
+            Bound::Included(constant(low, kind)?),
+            Bound::Included(constant(high, kind)?),
+        )),
+        _ => None,
+    }
+}
+
+/// Evaluate an expression that does not depend on the input, if it returns a non-null value
+/// that can be compared with the values of `kind`.
+fn constant(expr: &BoundExpr, kind: &DataTypeKind) -> Option<DataValue> {
+    let mut is_constant = true;
+    expr.visit(&mut |e| {
+        is_constant &= !matches!(
+            e,
+            BoundExpr::ColumnRef(_)
+                | BoundExpr::Subquery(_)
+                | BoundExpr::AggCall(_)
+                | BoundExpr::Window(_)
+        )
+    });
+    let ty = expr.return_type()?.kind();
+    let comparable = ty.canonical() == kind.canonical() || (ty.is_decimal() && kind.is_decimal());
+    if !is_constant || !comparable {
+        return None;
+    }
+    // an error, e.g. a division by zero, is left to be raised by the filter
+    match expr.eval_const().ok()? {
+        DataValue::Null => None,
+        value => Some(value),
+    }
+}
+
+/// Returns the tighter of two lower bounds, or of two upper bounds if `is_low` is false.
+fn tighter(a: Bound<DataValue>, b: Bound<DataValue>, is_low: bool) -> Bound<DataValue> {
+    let value = |bound: &Bound<DataValue>| match bound {
+        Bound::Included(v) | Bound::Excluded(v) => Some(v.clone()),
+        Bound::Unbounded => None,
+    };
+    let (Some(va), Some(vb)) = (value(&a), value(&b)) else {
+        return if matches!(a, Bound::Unbounded) { b } else { a };
+    };
+    match va.partial_cmp(&vb) {
+        Some(std::cmp::Ordering::Equal) if matches!(a, Bound::Excluded(_)) => a,
+        Some(std::cmp::Ordering::Equal) => b,
+        Some(std::cmp::Ordering::Greater) if is_low => a,
+        Some(std::cmp::Ordering::Less) if !is_low => a,
+        _ => b,
+    }
+}
+
+fn is_point(range: &IndexRange) -> bool {
+    matches!(range, (Bound::Included(low), Bound::Included(high)) if low == high)
+}
+
+impl Explain for PhysicalIndexScan {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let low = match &self.range.0 {
+            Bound::Included(v) => format!("[{}", v.to_string()),
+            Bound::Excluded(v) => format!("({}", v.to_string()),
+            Bound::Unbounded => "(-inf".into(),
+        };
+        let high = match &self.range.1 {
+            Bound::Included(v) => format!("{}]", v.to_string()),
+            Bound::Excluded(v) => format!("{})", v.to_string()),
+            Bound::Unbounded => "+inf)".into(),
+        };
+        writeln!(
+            f,
+            "IndexScan: table #{}, columns: {:?}, index column: {}, range: {}, {}",
+            self.table_ref_id.table_id, self.column_ids, self.index_column_id, low, high,
+        )
+    }
+}
@@ -0,0 +1,40 @@
+use super::*;
+use crate::binder::{BoundExpr, JoinType};
+use crate::logical_planner::{LogicalJoin, PlanNode};
+use crate::types::DataType;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalNestedLoopJoin {
+    pub left: Box<PhysicalPlan>,
+    pub right: Box<PhysicalPlan>,
+    pub join_type: JoinType,
+    pub condition: BoundExpr,
+    pub left_types: Vec<DataType>,
+    pub right_types: Vec<DataType>,
+}
+
+impl PhysicalPlanner {
+    pub fn plan_join(&self, plan: &LogicalJoin) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalNestedLoopJoin {
+            left: self.plan(&plan.left)?.into(),
+            right: self.plan(&plan.right)?.into(),
+            join_type: plan.join_type,
+            condition: plan.condition.clone(),
+            left_types: plan.left.out_types(),
+            right_types: plan.right.out_types(),
+        }
+        .into())
+    }
+}
+
+impl Explain for PhysicalNestedLoopJoin {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "NestedLoopJoin: type: {:?}, on: {:?}",
+            self.join_type, self.condition
+        )?;
+        self.left.explain(level + 1, f)?;
+        self.right.explain(level + 1, f)
+    }
+}
@@ -0,0 +1,70 @@
+use itertools::Itertools;
+
+use super::*;
+use crate::catalog::{SchemaId, TableRefId, ViewDefinition};
+use crate::logical_planner::{LogicalCreateMaterializedView, LogicalRefreshMaterializedView};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalCreateMaterializedView {
+    pub schema_id: SchemaId,
+    pub view_name: String,
+    pub definition: ViewDefinition,
+    pub child: Box<PhysicalPlan>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalRefreshMaterializedView {
+    pub table_ref_id: TableRefId,
+    pub child: Box<PhysicalPlan>,
+}
+
+impl PhysicalPlanner {
+    pub fn plan_create_materialized_view(
+        &self,
+        plan: &LogicalCreateMaterializedView,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalCreateMaterializedView {
+            schema_id: plan.schema_id,
+            view_name: plan.view_name.clone(),
+            definition: plan.definition.clone(),
+            child: self.plan(&plan.child)?.into(),
+        }
+        .into())
+    }
+
+    pub fn plan_refresh_materialized_view(
+        &self,
+        plan: &LogicalRefreshMaterializedView,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalRefreshMaterializedView {
+            table_ref_id: plan.table_ref_id,
+            child: self.plan(&plan.child)?.into(),
+        }
+        .into())
+    }
+}
+
+impl Explain for PhysicalCreateMaterializedView {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "CreateMaterializedView: name: {}, columns: [{}]",
+            self.view_name,
+            (self.definition.columns.iter())
+                .map(|(name, ty)| format!("{}: {:?}", name, ty))
+                .join(", ")
+        )?;
+        self.child.explain(level + 1, f)
+    }
+}
+
+impl Explain for PhysicalRefreshMaterializedView {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "RefreshMaterializedView: table #{}",
+            self.table_ref_id.table_id
+        )?;
+        self.child.explain(level + 1, f)
+    }
+}
@@ -1,31 +1,78 @@
 use enum_dispatch::enum_dispatch;
 
+use crate::catalog::CatalogRef;
 use crate::logical_planner::{Explain, LogicalPlan};
 
+mod aggregate;
+mod alter;
 mod create;
+mod cte;
+mod drop;
 mod dummy;
 mod explain;
+mod filter;
+mod index_scan;
 mod insert;
+mod join;
+mod materialized_view;
 mod projection;
 mod seq_scan;
+mod set_operation;
+mod truncate;
+mod window;
 
+pub use self::aggregate::*;
+pub use self::alter::*;
 pub use self::create::*;
+pub use self::cte::*;
+pub use self::drop::*;
 pub use self::dummy::*;
 pub use self::explain::*;
+pub use self::filter::*;
+pub use self::index_scan::*;
 pub use self::insert::*;
+pub use self::join::*;
+pub use self::materialized_view::*;
 pub use self::projection::*;
 pub use self::seq_scan::*;
+pub use self::set_operation::*;
+pub use self::truncate::*;
+pub use self::window::*;
 
 #[enum_dispatch(Explain)]
 #[derive(Debug, PartialEq, Clone)]
 pub enum PhysicalPlan {
     PhysicalCreateTable,
+    PhysicalDropTable,
+    PhysicalAlterTable,
+    PhysicalCreateSchema,
+    PhysicalDropSchema,
+    PhysicalCreateIndex,
+    PhysicalDropIndex,
+    PhysicalCreateView,
+    PhysicalDropView,
+    PhysicalTruncate,
+    PhysicalCreateMaterializedView,
+    PhysicalRefreshMaterializedView,
     PhysicalInsert,
     PhysicalValues,
     PhysicalExplain,
     PhysicalDummy,
     PhysicalSeqScan,
+    PhysicalIndexScan,
     PhysicalProjection,
+    PhysicalFilter,
+    PhysicalNestedLoopJoin,
+    PhysicalWith,
+    PhysicalCteScan,
+    PhysicalRecursiveCte,
+    PhysicalUnion,
+    PhysicalHashDistinct,
+    PhysicalHashIntersect,
+    PhysicalHashExcept,
+    PhysicalWindow,
+    PhysicalSimpleAgg,
+    PhysicalHashAgg,
 }
 
 impl std::fmt::Display for PhysicalPlan {
@@ -34,23 +81,50 @@ impl std::fmt::Display for PhysicalPlan {
     }
 }
 
-#[derive(Default)]
-pub struct PhysicalPlanner;
+pub struct PhysicalPlanner {
+    /// The catalog, in which the planner looks up the indexes of tables.
+    catalog: CatalogRef,
+}
 
 #[derive(thiserror::Error, Debug, PartialEq)]
 pub enum PhysicalPlanError {}
 
 impl PhysicalPlanner {
+    pub fn new(catalog: CatalogRef) -> Self {
+        PhysicalPlanner { catalog }
+    }
+
     pub fn plan(&self, plan: &LogicalPlan) -> Result<PhysicalPlan, PhysicalPlanError> {
         use LogicalPlan::*;
         match plan {
             LogicalCreateTable(plan) => self.plan_create_table(plan),
+            LogicalDropTable(plan) => self.plan_drop_table(plan),
+            LogicalAlterTable(plan) => self.plan_alter_table(plan),
+            LogicalCreateSchema(plan) => self.plan_create_schema(plan),
+            LogicalDropSchema(plan) => self.plan_drop_schema(plan),
+            LogicalCreateIndex(plan) => self.plan_create_index(plan),
+            LogicalDropIndex(plan) => self.plan_drop_index(plan),
+            LogicalCreateView(plan) => self.plan_create_view(plan),
+            LogicalDropView(plan) => self.plan_drop_view(plan),
+            LogicalTruncate(plan) => self.plan_truncate(plan),
+            LogicalCreateMaterializedView(plan) => self.plan_create_materialized_view(plan),
+            LogicalRefreshMaterializedView(plan) => self.plan_refresh_materialized_view(plan),
             LogicalInsert(plan) => self.plan_insert(plan),
             LogicalValues(plan) => self.plan_values(plan),
             LogicalExplain(plan) => self.plan_explain(plan),
             LogicalDummy(plan) => self.plan_dummy(plan),
             LogicalGet(plan) => self.plan_get(plan),
             LogicalProjection(plan) => self.plan_projection(plan),
+            LogicalFilter(plan) => self.plan_filter(plan),
+            LogicalJoin(plan) => self.plan_join(plan),
+            LogicalWith(plan) => self.plan_with(plan),
+            LogicalCteScan(plan) => self.plan_cte_scan(plan),
+            LogicalRecursiveCte(plan) => self.plan_recursive_cte(plan),
+            LogicalUnion(plan) => self.plan_union(plan),
+            LogicalIntersect(plan) => self.plan_intersect(plan),
+            LogicalExcept(plan) => self.plan_except(plan),
+            LogicalWindow(plan) => self.plan_window(plan),
+            LogicalAggregate(plan) => self.plan_aggregate(plan),
         }
     }
 }
@@ -0,0 +1,99 @@
+use super::*;
+use crate::logical_planner::{LogicalExcept, LogicalIntersect, LogicalUnion};
+
+/// Outputs the rows of both children, keeping duplicates.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalUnion {
+    pub left: Box<PhysicalPlan>,
+    pub right: Box<PhysicalPlan>,
+}
+
+/// Removes duplicate rows using a hash set.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalHashDistinct {
+    pub child: Box<PhysicalPlan>,
+}
+
+/// Builds a hash table on the right side and probes it with the rows on the left side.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalHashIntersect {
+    pub left: Box<PhysicalPlan>,
+    pub right: Box<PhysicalPlan>,
+    pub all: bool,
+}
+
+/// Builds a hash table on the right side and probes it with the rows on the left side.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalHashExcept {
+    pub left: Box<PhysicalPlan>,
+    pub right: Box<PhysicalPlan>,
+    pub all: bool,
+}
+
+impl PhysicalPlanner {
+    pub fn plan_union(&self, plan: &LogicalUnion) -> Result<PhysicalPlan, PhysicalPlanError> {
+        let union = PhysicalUnion {
+            left: self.plan(&plan.left)?.into(),
+            right: self.plan(&plan.right)?.into(),
+        };
+        if plan.all {
+            return Ok(union.into());
+        }
+        Ok(PhysicalHashDistinct {
+            child: PhysicalPlan::from(union).into(),
+        }
+        .into())
+    }
+
+    pub fn plan_intersect(
+        &self,
+        plan: &LogicalIntersect,
+    ) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalHashIntersect {
+            left: self.plan(&plan.left)?.into(),
+            right: self.plan(&plan.right)?.into(),
+            all: plan.all,
+        }
+        .into())
+    }
+
+    pub fn plan_except(&self, plan: &LogicalExcept) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalHashExcept {
+            left: self.plan(&plan.left)?.into(),
+            right: self.plan(&plan.right)?.into(),
+            all: plan.all,
+        }
+        .into())
+    }
+}
+
+impl Explain for PhysicalUnion {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Union:")?;
+        self.left.explain(level + 1, f)?;
+        self.right.explain(level + 1, f)
+    }
+}
+
+impl Explain for PhysicalHashDistinct {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "HashDistinct:")?;
+        self.child.explain(level + 1, f)
+    }
+}
+
+impl Explain for PhysicalHashIntersect {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "HashIntersect: all: {}", self.all)?;
+        self.left.explain(level + 1, f)?;
+        self.right.explain(level + 1, f)
+    }
+}
+
+impl Explain for PhysicalHashExcept {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "HashExcept: all: {}", self.all)?;
+        self.left.explain(level + 1, f)?;
+        self.right.explain(level + 1, f)
+    }
+}
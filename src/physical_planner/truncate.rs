@@ -0,0 +1,29 @@
+use itertools::Itertools;
+
+use super::*;
+use crate::catalog::TableRefId;
+use crate::logical_planner::LogicalTruncate;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalTruncate {
+    pub table_ref_ids: Vec<TableRefId>,
+}
+
+impl PhysicalPlanner {
+    pub fn plan_truncate(&self, plan: &LogicalTruncate) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalTruncate {
+            table_ref_ids: plan.table_ref_ids.clone(),
+        }
+        .into())
+    }
+}
+
+impl Explain for PhysicalTruncate {
+    fn explain_inner(&self, _level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Truncate: tables [{}]",
+            self.table_ref_ids.iter().map(|id| id.table_id).join(", ")
+        )
+    }
+}
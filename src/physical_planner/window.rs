@@ -0,0 +1,26 @@
+use super::*;
+use crate::binder::BoundWindow;
+use crate::logical_planner::LogicalWindow;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhysicalWindow {
+    pub windows: Vec<BoundWindow>,
+    pub child: Box<PhysicalPlan>,
+}
+
+impl PhysicalPlanner {
+    pub fn plan_window(&self, plan: &LogicalWindow) -> Result<PhysicalPlan, PhysicalPlanError> {
+        Ok(PhysicalWindow {
+            windows: plan.windows.clone(),
+            child: self.plan(&plan.child)?.into(),
+        }
+        .into())
+    }
+}
+
+impl Explain for PhysicalWindow {
+    fn explain_inner(&self, level: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Window: exprs: {:?}", self.windows)?;
+        self.child.explain(level + 1, f)
+    }
+}
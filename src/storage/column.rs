@@ -2,12 +2,103 @@ use anyhow::anyhow;
 use bytes::{Buf, BufMut};
 
 use super::StorageResult;
-use crate::array::{Array, ArrayBuilder, I32Array, I32ArrayBuilder};
+use crate::array::{
+    Array, ArrayBuilder, ArrayImpl, DictionaryArray, Primitive, PrimitiveArray,
+    PrimitiveArrayBuilder, VarArray, VarArrayBuilder, VarItem,
+};
+use crate::types::{Date, Decimal, Interval, Timestamp};
 
-pub fn encode_int32_column(a: &I32Array, mut buffer: impl BufMut) -> StorageResult<()> {
+/// A primitive type that is encoded in a fixed number of bytes in little endian.
+pub trait FixedWidth: Primitive {
+    const WIDTH: usize;
+
+    fn put(&self, buffer: impl BufMut);
+
+    fn get(data: impl Buf) -> Self;
+}
+
+macro_rules! impl_fixed_width {
+    ($t:ty, $put:ident, $get:ident) => {
+        impl FixedWidth for $t {
+            const WIDTH: usize = std::mem::size_of::<$t>();
+
+            fn put(&self, mut buffer: impl BufMut) {
+                buffer.$put(*self);
+            }
+
+            fn get(mut data: impl Buf) -> Self {
+                data.$get()
+            }
+        }
+    };
+}
+
+impl_fixed_width! { i16, put_i16_le, get_i16_le }
+impl_fixed_width! { i32, put_i32_le, get_i32_le }
+impl_fixed_width! { i64, put_i64_le, get_i64_le }
+impl_fixed_width! { u32, put_u32_le, get_u32_le }
+
+impl FixedWidth for Decimal {
+    const WIDTH: usize = 16;
+
+    fn put(&self, mut buffer: impl BufMut) {
+        buffer.put_slice(&self.serialize());
+    }
+
+    fn get(mut data: impl Buf) -> Self {
+        let mut bytes = [0; 16];
+        data.copy_to_slice(&mut bytes);
+        Decimal::deserialize(bytes)
+    }
+}
+
+impl FixedWidth for Date {
+    const WIDTH: usize = 4;
+
+    fn put(&self, mut buffer: impl BufMut) {
+        buffer.put_i32_le(self.days());
+    }
+
+    fn get(mut data: impl Buf) -> Self {
+        Date::from_days(data.get_i32_le()).expect("date out of range")
+    }
+}
+
+impl FixedWidth for Timestamp {
+    const WIDTH: usize = 8;
+
+    fn put(&self, mut buffer: impl BufMut) {
+        buffer.put_i64_le(self.micros());
+    }
+
+    fn get(mut data: impl Buf) -> Self {
+        Timestamp::from_micros(data.get_i64_le()).expect("timestamp out of range")
+    }
+}
+
+impl FixedWidth for Interval {
+    const WIDTH: usize = 16;
+
+    fn put(&self, mut buffer: impl BufMut) {
+        buffer.put_i32_le(self.months());
+        buffer.put_i32_le(self.days());
+        buffer.put_i64_le(self.micros());
+    }
+
+    fn get(mut data: impl Buf) -> Self {
+        let months = data.get_i32_le();
+        let days = data.get_i32_le();
+        Interval::new(months, days, data.get_i64_le())
+    }
+}
+
+pub fn encode_fixed_width_column<T: FixedWidth>(
+    a: &PrimitiveArray<T>,
+    mut buffer: impl BufMut,
+) -> StorageResult<()> {
     for item in a.iter() {
         if let Some(item) = item {
-            buffer.put_i32_le(*item);
+            item.put(&mut buffer);
         } else {
             return Err(anyhow!("nullable encoding not supported!").into());
         }
@@ -15,10 +106,93 @@ pub fn encode_int32_column(a: &I32Array, mut buffer: impl BufMut) -> StorageResu
     Ok(())
 }
 
-pub fn decode_int32_column(mut data: impl Buf) -> StorageResult<I32Array> {
-    let mut builder = I32ArrayBuilder::with_capacity(data.remaining() / 4);
+pub fn decode_fixed_width_column<T: FixedWidth>(
+    mut data: impl Buf,
+) -> StorageResult<PrimitiveArray<T>> {
+    let mut builder = PrimitiveArrayBuilder::<T>::with_capacity(data.remaining() / T::WIDTH);
     while data.has_remaining() {
-        builder.push(Some(&data.get_i32_le()));
+        builder.push(Some(&T::get(&mut data)));
     }
     Ok(builder.finish())
 }
+
+/// Encode each item as its length in 4 bytes in little endian, followed by its bytes.
+pub fn encode_var_width_column<T: VarItem + ?Sized>(
+    a: &VarArray<T>,
+    mut buffer: impl BufMut,
+) -> StorageResult<()> {
+    for item in a.iter() {
+        if let Some(item) = item {
+            let bytes = item.as_bytes();
+            let len = u32::try_from(bytes.len()).map_err(|_| anyhow!("value too long"))?;
+            buffer.put_u32_le(len);
+            buffer.put_slice(bytes);
+        } else {
+            return Err(anyhow!("nullable encoding not supported!").into());
+        }
+    }
+    Ok(())
+}
+
+pub fn decode_var_width_column<T: VarItem + ?Sized>(mut data: &[u8]) -> StorageResult<VarArray<T>> {
+    let mut builder = VarArrayBuilder::<T>::with_capacity(0);
+    while data.has_remaining() {
+        if data.remaining() < 4 {
+            return Err(anyhow!("truncated column").into());
+        }
+        let len = data.get_u32_le() as usize;
+        let bytes = data.get(..len).ok_or_else(|| anyhow!("truncated column"))?;
+        let item = T::from_bytes(bytes).ok_or_else(|| anyhow!("invalid value in column"))?;
+        builder.push(Some(item));
+        data.advance(len);
+    }
+    Ok(builder.finish())
+}
+
+/// The first byte of a string column, which tells how the strings are encoded.
+const PLAIN: u8 = 0;
+const DICTIONARY: u8 = 1;
+
+/// Encode a string column. If there are at most half as many distinct strings as rows, the
+/// column is dictionary-encoded: the byte length of the dictionary, the dictionary and the
+/// code of each row. Otherwise each string is encoded as in a variable-width column.
+pub fn encode_string_column(a: &DictionaryArray, mut buffer: impl BufMut) -> StorageResult<()> {
+    if a.dictionary().len() * 2 <= a.len() {
+        let mut dict = vec![];
+        encode_var_width_column(a.dictionary(), &mut dict)?;
+        let len = u32::try_from(dict.len()).map_err(|_| anyhow!("dictionary too long"))?;
+        buffer.put_u8(DICTIONARY);
+        buffer.put_u32_le(len);
+        buffer.put_slice(&dict);
+        encode_fixed_width_column(a.codes(), buffer)
+    } else {
+        buffer.put_u8(PLAIN);
+        encode_var_width_column(&a.decode(), buffer)
+    }
+}
+
+/// Decode a string column. Dictionary-encoded columns are decoded to dictionary arrays.
+pub fn decode_string_column(mut data: &[u8]) -> StorageResult<ArrayImpl> {
+    if data.is_empty() {
+        return Ok(ArrayImpl::Utf8(VarArrayBuilder::with_capacity(0).finish()));
+    }
+    match data.get_u8() {
+        PLAIN => Ok(ArrayImpl::Utf8(decode_var_width_column(data)?)),
+        DICTIONARY if data.remaining() >= 4 => {
+            let len = data.get_u32_le() as usize;
+            if data.remaining() < len {
+                return Err(anyhow!("truncated column").into());
+            }
+            let dict = decode_var_width_column::<str>(&data[..len])?;
+            let codes = decode_fixed_width_column::<u32>(&data[len..])?;
+            if codes.iter().flatten().any(|c| *c as usize >= dict.len()) {
+                return Err(anyhow!("dictionary code out of range").into());
+            }
+            Ok(ArrayImpl::Dictionary(DictionaryArray::new(
+                codes,
+                dict.into(),
+            )))
+        }
+        _ => Err(anyhow!("invalid string column").into()),
+    }
+}
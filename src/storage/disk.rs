@@ -1,17 +1,23 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    ops::{Bound, RangeBounds},
     path::PathBuf,
-    sync::{atomic::AtomicU32, Arc, RwLock},
+    sync::{
+        atomic::{AtomicBool, AtomicU32},
+        Arc, RwLock,
+    },
 };
 
 use anyhow::anyhow;
+use itertools::Itertools;
 
 use crate::{
-    array::DataChunk,
-    catalog::{ColumnDesc, TableRefId},
+    array::{Array, ArrayImpl, DataChunk},
+    catalog::{ColumnCatalog, ColumnId, TableRefId},
+    types::DataValue,
 };
 
-use super::{DiskRowset, RowsetBuilder, StorageResult};
+use super::{DiskRowset, IndexRange, RowsetBuilder, StorageError, StorageResult};
 
 pub type StorageTableRef = Arc<DiskTable>;
 
@@ -34,14 +40,28 @@ impl DiskStorage {
         }
     }
 
-    pub fn add_table(&self, id: TableRefId, column_descs: &[ColumnDesc]) -> StorageResult<()> {
+    /// Add a table. The keys on each set of columns in `unique_keys` must be unique.
+    pub fn add_table(
+        &self,
+        id: TableRefId,
+        columns: &[ColumnCatalog],
+        unique_keys: &[Vec<ColumnId>],
+    ) -> StorageResult<()> {
         let mut tables = self.tables.write().unwrap();
         let table = DiskTable {
             id,
             options: self.options.clone(),
-            column_descs: column_descs.into(),
+            columns: RwLock::new(columns.into()),
+            defaults: RwLock::new(HashMap::new()),
+            keys: RwLock::new(
+                (unique_keys.iter())
+                    .map(|column_ids| (column_ids.clone(), HashSet::new()))
+                    .collect(),
+            ),
+            indexes: RwLock::new(Vec::new()),
             rowsets: RwLock::new(Vec::new()),
             rowset_id_generator: self.rowset_id_generator.clone(),
+            dropped: AtomicBool::new(false),
         };
         let res = tables.insert(id, table.into());
         if res.is_some() {
@@ -57,41 +77,186 @@ impl DiskStorage {
             .ok_or_else(|| anyhow!("table not found: {:?}", id).into())
             .cloned()
     }
+
+    /// Remove a table. Its files are deleted once the transactions on it have finished.
+    pub fn drop_table(&self, id: TableRefId) -> StorageResult<()> {
+        let mut tables = self.tables.write().unwrap();
+        let table = tables
+            .remove(&id)
+            .ok_or_else(|| anyhow!("table not found: {:?}", id))?;
+        table
+            .dropped
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        Ok(())
+    }
 }
 
 pub struct DiskTable {
     id: TableRefId,
-    column_descs: Arc<[ColumnDesc]>,
+    /// The columns of the table, in the order of the chunks that are read and written.
+    columns: RwLock<Arc<[ColumnCatalog]>>,
+    /// The values of the columns added by `ALTER TABLE`, in the rowsets written before.
+    /// Columns without a value here are null in those rowsets.
+    defaults: RwLock<HashMap<ColumnId, DataValue>>,
+    /// The keys in the committed rowsets on each set of columns whose keys must be unique.
+    /// Keys with nulls are not included, as they never conflict.
+    keys: RwLock<HashMap<Vec<ColumnId>, HashSet<Vec<DataValue>>>>,
+    /// The indexed columns. The rowsets written after a column is indexed have its index.
+    indexes: RwLock<Vec<ColumnId>>,
     options: Arc<StorageOptions>,
     rowset_id_generator: Arc<AtomicU32>,
     rowsets: RwLock<Vec<DiskRowset>>,
+    /// Whether the table has been dropped, in which case its files are deleted when the
+    /// last reference to it, e.g. held by a transaction, goes away.
+    dropped: AtomicBool,
+}
+
+impl Drop for DiskTable {
+    fn drop(&mut self) {
+        if !*self.dropped.get_mut() {
+            return;
+        }
+        match std::fs::remove_dir_all(self.table_path()) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                warn!("failed to delete dropped table {:?}: {}", self.id, e)
+            }
+            _ => {}
+        }
+    }
 }
 
 impl DiskTable {
+    /// Add a column to the table. The rowsets already written are not rewritten: the column
+    /// reads as `default` in them.
+    pub fn add_column(&self, column: ColumnCatalog, default: DataValue) -> StorageResult<()> {
+        let mut columns = self.columns.write().unwrap();
+        if columns.iter().any(|col| col.id() == column.id()) {
+            return Err(anyhow!("column already exists: {}", column.id()).into());
+        }
+        if !default.is_null() {
+            self.defaults.write().unwrap().insert(column.id(), default);
+        }
+        *columns = columns.iter().cloned().chain([column]).collect();
+        Ok(())
+    }
+
+    /// Drop a column from the table. Its data is left in the rowsets, but is never read again.
+    /// The unique keys on the column are dropped with it.
+    pub fn drop_column(&self, id: ColumnId) -> StorageResult<()> {
+        let mut columns = self.columns.write().unwrap();
+        if !columns.iter().any(|col| col.id() == id) {
+            return Err(anyhow!("column not found: {}", id).into());
+        }
+        *columns = columns
+            .iter()
+            .filter(|col| col.id() != id)
+            .cloned()
+            .collect();
+        let mut keys = self.keys.write().unwrap();
+        keys.retain(|column_ids, _| !column_ids.contains(&id));
+        self.indexes.write().unwrap().retain(|&col| col != id);
+        Ok(())
+    }
+
+    /// Index a column. The index is built for the rowsets already written, and for each
+    /// rowset written later. The rowsets written by transactions that began before are read
+    /// without the index.
+    pub async fn create_index(&self, id: ColumnId) -> StorageResult<()> {
+        {
+            let columns = self.columns.read().unwrap();
+            if !columns.iter().any(|col| col.id() == id) {
+                return Err(anyhow!("column not found: {}", id).into());
+            }
+            let mut indexes = self.indexes.write().unwrap();
+            if indexes.contains(&id) {
+                return Err(anyhow!("index already exists: {}", id).into());
+            }
+            indexes.push(id);
+        }
+        let snapshot = self.rowsets.read().unwrap().clone();
+        let mut indexed = vec![];
+        for rowset in &snapshot {
+            indexed.push(rowset.add_index(id).await?);
+        }
+        self.replace_rowsets(indexed);
+        Ok(())
+    }
+
+    /// Drop the index of a column, and delete its index files.
+    pub async fn drop_index(&self, id: ColumnId) -> StorageResult<()> {
+        {
+            let mut indexes = self.indexes.write().unwrap();
+            if !indexes.contains(&id) {
+                return Err(anyhow!("index not found: {}", id).into());
+            }
+            indexes.retain(|&col| col != id);
+        }
+        let snapshot = self.rowsets.read().unwrap().clone();
+        let mut unindexed = vec![];
+        for rowset in &snapshot {
+            unindexed.push(rowset.drop_index(id).await?);
+        }
+        self.replace_rowsets(unindexed);
+        Ok(())
+    }
+
+    /// Replace the rowsets with the same ids as the given ones.
+    fn replace_rowsets(&self, new_rowsets: Vec<DiskRowset>) {
+        let mut rowsets = self.rowsets.write().unwrap();
+        for rowset in rowsets.iter_mut() {
+            let id = rowset.rowset_id();
+            if let Some(new_rowset) = new_rowsets.iter().find(|r| r.rowset_id() == id) {
+                *rowset = new_rowset.clone();
+            }
+        }
+    }
+
     pub async fn write(self: &Arc<Self>) -> StorageResult<DiskTransaction> {
         let rowsets = self.rowsets.read().unwrap();
         Ok(DiskTransaction {
             read_only: false,
             table: self.clone(),
+            columns: self.columns.read().unwrap().clone(),
+            indexes: self.indexes.read().unwrap().clone(),
             rowset_snapshot: rowsets.clone(),
             builder: None,
+            pending_keys: HashMap::new(),
+            overwrite: false,
             finished: false,
         })
     }
 
+    /// Begin a transaction whose rows replace all rows of the table when it commits.
+    pub async fn overwrite(self: &Arc<Self>) -> StorageResult<DiskTransaction> {
+        let mut txn = self.write().await?;
+        txn.overwrite = true;
+        Ok(txn)
+    }
+
+    /// Remove all rows of the table. Only the list of rowsets is replaced, so it takes the
+    /// same time however large the table is.
+    pub async fn truncate(self: &Arc<Self>) -> StorageResult<()> {
+        self.overwrite().await?.commit().await
+    }
+
     pub async fn read(self: &Arc<Self>) -> StorageResult<DiskTransaction> {
         let rowsets = self.rowsets.read().unwrap();
         Ok(DiskTransaction {
             read_only: true,
             table: self.clone(),
+            columns: self.columns.read().unwrap().clone(),
+            indexes: self.indexes.read().unwrap().clone(),
             rowset_snapshot: rowsets.clone(),
             builder: None,
+            pending_keys: HashMap::new(),
+            overwrite: false,
             finished: false,
         })
     }
 
     pub fn table_path(&self) -> PathBuf {
-        self.options.base_path.join(self.id.table_id.to_string())
+        let name = format!("{}_{}", self.id.schema_id, self.id.table_id);
+        self.options.base_path.join(name)
     }
 
     pub fn rowset_path_of(&self, rowset_id: u32) -> PathBuf {
@@ -102,8 +267,16 @@ impl DiskTable {
 pub struct DiskTransaction {
     read_only: bool,
     table: Arc<DiskTable>,
+    /// The columns of the table when the transaction began.
+    columns: Arc<[ColumnCatalog]>,
+    /// The indexed columns when the transaction began.
+    indexes: Vec<ColumnId>,
     rowset_snapshot: Vec<DiskRowset>,
     builder: Option<RowsetBuilder>,
+    /// The unique keys of the rows appended in the transaction.
+    pending_keys: HashMap<Vec<ColumnId>, HashSet<Vec<DataValue>>>,
+    /// Whether the rows of the transaction replace all rows of the table.
+    overwrite: bool,
     finished: bool,
 }
 
@@ -120,8 +293,12 @@ impl DiskTransaction {
         if self.read_only {
             return Err(anyhow!("cannot append chunks in read only txn!").into());
         }
+        self.check_keys(&chunk)?;
         if self.builder.is_none() {
-            self.builder = Some(RowsetBuilder::new(self.table.column_descs.clone()));
+            self.builder = Some(RowsetBuilder::new(
+                self.columns.clone(),
+                self.indexes.clone(),
+            ));
         }
         let builder = self.builder.as_mut().unwrap();
         builder.append(chunk)?;
@@ -132,6 +309,7 @@ impl DiskTransaction {
     pub async fn commit(mut self) -> StorageResult<()> {
         self.finished = true;
 
+        let mut new_rowset = None;
         if let Some(builder) = self.builder.take() {
             use std::sync::atomic::Ordering::SeqCst; // 强制有序
             let rowset_id = self.table.rowset_id_generator.fetch_add(1, SeqCst);
@@ -140,18 +318,291 @@ impl DiskTransaction {
                 .options
                 .base_path
                 .join(self.table.rowset_path_of(rowset_id));
-            let rowset = builder.flush(rowset_id, rowset_path).await?;
-            let mut rowsets = self.table.rowsets.write().unwrap();
-            rowsets.push(rowset);
+            new_rowset = Some(builder.flush(rowset_id, rowset_path).await?);
+        }
+        // the keys and the rowsets are changed together, so that the keys are always the ones
+        // in the rowsets
+        let mut keys = self.table.keys.write().unwrap();
+        let mut rowsets = self.table.rowsets.write().unwrap();
+        if self.overwrite {
+            let mut old_keys = vec![];
+            for (column_ids, committed) in keys.iter_mut() {
+                let pending = self.pending_keys.remove(column_ids).unwrap_or_default();
+                old_keys.push(std::mem::replace(committed, pending));
+            }
+            let old_rowsets = std::mem::replace(&mut *rowsets, new_rowset.into_iter().collect());
+            drop((keys, rowsets));
+            // the files of the old rowsets are deleted once no transaction reads them, which
+            // may be right away
+            for rowset in &old_rowsets {
+                rowset.set_dropped();
+            }
+            // freeing the keys of all rows takes time, which is not spent on committing
+            tokio::task::spawn_blocking(move || drop(old_keys));
+        } else {
+            if let Err(e) = self.commit_keys(&mut keys) {
+                drop((keys, rowsets));
+                if let Some(rowset) = &new_rowset {
+                    rowset.set_dropped();
+                }
+                return Err(e);
+            }
+            rowsets.extend(new_rowset);
+        }
+        Ok(())
+    }
+
+    /// Check that the unique keys of the chunk are not in the table or the transaction, and
+    /// add them to the keys of the transaction.
+    fn check_keys(&mut self, chunk: &DataChunk) -> StorageResult<()> {
+        let keys = self.table.keys.read().unwrap();
+        for (column_ids, committed) in keys.iter() {
+            let positions = column_ids
+                .iter()
+                .map(|id| self.columns.iter().position(|col| col.id() == *id))
+                .collect::<Option<Vec<_>>>();
+            // the key is on a column added after the transaction began
+            let Some(positions) = positions else {
+                continue;
+            };
+            let pending = self.pending_keys.entry(column_ids.clone()).or_default();
+            for row in chunk.visible_rows() {
+                let key = (positions.iter())
+                    .map(|&i| chunk.arrays()[i].get(row))
+                    .collect::<Vec<_>>();
+                if key.iter().any(|v| v.is_null()) {
+                    continue;
+                }
+                let conflicts = !self.overwrite && committed.contains(&key);
+                if conflicts || !pending.insert(key.clone()) {
+                    return Err(StorageError::DuplicateKey {
+                        column_ids: column_ids.clone(),
+                        key,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the unique keys of the transaction to the committed `keys` of the table, unless any
+    /// of them has been committed by another transaction since it was checked.
+    fn commit_keys(
+        &self,
+        keys: &mut HashMap<Vec<ColumnId>, HashSet<Vec<DataValue>>>,
+    ) -> StorageResult<()> {
+        for (column_ids, pending) in &self.pending_keys {
+            let Some(committed) = keys.get(column_ids) else {
+                continue;
+            };
+            if let Some(key) = pending.iter().find(|key| committed.contains(*key)) {
+                return Err(StorageError::DuplicateKey {
+                    column_ids: column_ids.clone(),
+                    key: key.clone(),
+                });
+            }
+        }
+        for (column_ids, pending) in &self.pending_keys {
+            if let Some(committed) = keys.get_mut(column_ids) {
+                committed.extend(pending.iter().cloned());
+            }
         }
         Ok(())
     }
 
-    pub async fn all_chunks(&self) -> StorageResult<Vec<DataChunk>> {
+    /// Read the columns of `column_ids` from all rowsets.
+    pub async fn all_chunks(&self, column_ids: &[ColumnId]) -> StorageResult<Vec<DataChunk>> {
+        let columns = self.find_columns(column_ids)?;
+        let mut chunks = vec![];
+        for rowset in &self.rowset_snapshot {
+            chunks.push(self.read_columns(rowset, &columns).await?);
+        }
+        Ok(chunks)
+    }
+
+    /// Read the columns of `column_ids` in the rows whose value in the column of `index_id`
+    /// is not null and in `range`. The index of the column is used in the rowsets that have
+    /// it, and the rowsets without matching rows are skipped.
+    pub async fn index_chunks(
+        &self,
+        column_ids: &[ColumnId],
+        index_id: ColumnId,
+        range: &IndexRange,
+    ) -> StorageResult<Vec<DataChunk>> {
+        let columns = self.find_columns(column_ids)?;
+        let index_column = self.find_columns(&[index_id])?.remove(0);
+        let in_range = |value: &DataValue| !value.is_null() && range.contains(value);
         let mut chunks = vec![];
         for rowset in &self.rowset_snapshot {
-            chunks.push(rowset.as_chunk().await?);
+            let column = self.read_columns(rowset, &[index_column]).await?;
+            let column = &column.arrays()[0];
+            let visibility = match rowset.read_index(index_id).await? {
+                Some(offsets) => {
+                    let offsets = offsets.iter().map(|o| *o.unwrap() as usize).collect_vec();
+                    let below = |&o: &usize| match &range.0 {
+                        Bound::Included(low) => column.get(o) < *low,
+                        Bound::Excluded(low) => column.get(o) <= *low,
+                        Bound::Unbounded => false,
+                    };
+                    let not_above = |&o: &usize| match &range.1 {
+                        Bound::Included(high) => column.get(o) <= *high,
+                        Bound::Excluded(high) => column.get(o) < *high,
+                        Bound::Unbounded => true,
+                    };
+                    let start = offsets.partition_point(below);
+                    let end = start + offsets[start..].partition_point(not_above);
+                    let mut visibility = vec![false; rowset.num_rows()];
+                    for &o in &offsets[start..end] {
+                        visibility[o] = true;
+                    }
+                    visibility
+                }
+                None => (0..column.len())
+                    .map(|i| in_range(&column.get(i)))
+                    .collect(),
+            };
+            if !visibility.contains(&true) {
+                continue;
+            }
+            let chunk = self.read_columns(rowset, &columns).await?;
+            chunks.push(chunk.with_visibility(&visibility).compact());
         }
         Ok(chunks)
     }
+
+    fn find_columns(&self, column_ids: &[ColumnId]) -> StorageResult<Vec<&ColumnCatalog>> {
+        let columns = column_ids
+            .iter()
+            .map(|&id| {
+                (self.columns.iter())
+                    .find(|col| col.id() == id)
+                    .ok_or_else(|| anyhow!("column not found: {}", id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(columns)
+    }
+
+    /// Read the columns from a rowset. The columns added after the rowset was written are
+    /// filled with their default values.
+    async fn read_columns(
+        &self,
+        rowset: &DiskRowset,
+        columns: &[&ColumnCatalog],
+    ) -> StorageResult<DataChunk> {
+        let mut arrays = vec![];
+        for column in columns {
+            let array = match rowset.read_column(column.id()).await? {
+                Some(array) => array,
+                None => {
+                    let defaults = self.table.defaults.read().unwrap();
+                    let default = defaults.get(&column.id()).unwrap_or(&DataValue::Null);
+                    ArrayImpl::repeat(default, &column.datatype(), rowset.num_rows())
+                }
+            };
+            arrays.push(array);
+        }
+        Ok(arrays.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::I32Array;
+    use crate::catalog::{DatabaseCatalog, DEFAULT_SCHEMA_NAME};
+    use crate::types::{DataTypeExt, DataTypeKind};
+
+    #[test]
+    fn test_truncate() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let storage = DiskStorage::new(StorageOptions {
+            base_path: tempdir.path().into(),
+        });
+        let catalog = DatabaseCatalog::new();
+        let schema = catalog.get_schema_by_name(DEFAULT_SCHEMA_NAME).unwrap();
+        let table_id = schema.add_table("t").unwrap();
+        let table = schema.get_table(table_id).unwrap();
+        let desc = DataTypeKind::Int(None).not_null().to_column();
+        table.add_column("v", desc).unwrap();
+        let columns = table.all_columns().into_values().collect::<Vec<_>>();
+        let id = TableRefId::new(schema.id(), table_id);
+        storage.add_table(id, &columns, &[]).unwrap();
+        let table = storage.get_table(id).unwrap();
+        let rowset_dirs = || std::fs::read_dir(table.table_path()).unwrap().count();
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            for v in [1, 2] {
+                let mut txn = table.write().await.unwrap();
+                let array = [Some(v)].into_iter().collect::<I32Array>();
+                txn.append([array.into()].into_iter().collect())
+                    .await
+                    .unwrap();
+                txn.commit().await.unwrap();
+            }
+            assert_eq!(rowset_dirs(), 2);
+
+            // the rows are still read by the transaction that began before, until it finishes
+            let old_txn = table.read().await.unwrap();
+            table.truncate().await.unwrap();
+            let txn = table.read().await.unwrap();
+            assert!(txn.all_chunks(&[0]).await.unwrap().is_empty());
+            txn.commit().await.unwrap();
+            assert_eq!(old_txn.all_chunks(&[0]).await.unwrap().len(), 2);
+            assert_eq!(rowset_dirs(), 2);
+            old_txn.commit().await.unwrap();
+            assert_eq!(rowset_dirs(), 0);
+        });
+    }
+
+    #[test]
+    fn test_append_during_overwrite() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let storage = DiskStorage::new(StorageOptions {
+            base_path: tempdir.path().into(),
+        });
+        let catalog = DatabaseCatalog::new();
+        let schema = catalog.get_schema_by_name(DEFAULT_SCHEMA_NAME).unwrap();
+        let table_id = schema.add_table("t").unwrap();
+        let table = schema.get_table(table_id).unwrap();
+        let desc = DataTypeKind::Int(None).not_null().to_column();
+        let column_id = table.add_column("v", desc).unwrap();
+        let columns = table.all_columns().into_values().collect::<Vec<_>>();
+        let id = TableRefId::new(schema.id(), table_id);
+        storage.add_table(id, &columns, &[vec![column_id]]).unwrap();
+        let table = storage.get_table(id).unwrap();
+        let chunk = |v: i32| {
+            [[Some(v)].into_iter().collect::<I32Array>().into()]
+                .into_iter()
+                .collect()
+        };
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let mut txn = table.write().await.unwrap();
+            txn.append(chunk(1)).await.unwrap();
+            txn.commit().await.unwrap();
+
+            // the rows and the keys of a transaction committed after an overwrite are kept
+            let mut txn = table.write().await.unwrap();
+            txn.append(chunk(2)).await.unwrap();
+            let mut overwrite = table.overwrite().await.unwrap();
+            overwrite.append(chunk(3)).await.unwrap();
+            overwrite.commit().await.unwrap();
+            txn.commit().await.unwrap();
+
+            let txn = table.read().await.unwrap();
+            assert_eq!(txn.all_chunks(&[column_id]).await.unwrap().len(), 2);
+            txn.commit().await.unwrap();
+            for (v, duplicate) in [(1, false), (2, true), (3, true)] {
+                let mut txn = table.write().await.unwrap();
+                assert_eq!(txn.append(chunk(v)).await.is_err(), duplicate);
+                txn.commit().await.unwrap();
+            }
+        });
+    }
 }
@@ -38,7 +38,16 @@ impl InMemoryStorage {
             .unwrap()
             .get(&id)
             .cloned()
-            .ok_or(anyhow!("table not found: {:?}", id).into())
+            .ok_or_else(|| anyhow!("table not found: {:?}", id).into())
+    }
+
+    pub fn drop_table(&self, id: TableRefId) -> StorageResult<()> {
+        self.tables
+            .lock()
+            .unwrap()
+            .remove(&id)
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("table not found: {:?}", id).into())
     }
 }
 
@@ -1,5 +1,9 @@
+use std::ops::Bound;
 use std::sync::Arc;
 
+use crate::catalog::ColumnId;
+use crate::types::DataValue;
+
 mod column;
 mod disk;
 mod memory;
@@ -11,8 +15,19 @@ pub use self::memory::*;
 pub use self::rowset::*;
 
 #[derive(thiserror::Error, Debug)]
-#[error("{0:?}")]
-pub struct StorageError(#[from] anyhow::Error);
+pub enum StorageError {
+    /// A row has the same key as another row, on columns whose keys must be unique.
+    #[error("duplicate key {key:?} on columns {column_ids:?}")]
+    DuplicateKey {
+        column_ids: Vec<ColumnId>,
+        key: Vec<DataValue>,
+    },
+    #[error("{0:?}")]
+    Other(#[from] anyhow::Error),
+}
+
+/// A range of values in an indexed column.
+pub type IndexRange = (Bound<DataValue>, Bound<DataValue>);
 
 pub type StorageResult<T> = std::result::Result<T, StorageError>;
 
@@ -22,5 +37,5 @@ pub type StorageRef = Arc<InMemoryStorage>;
 pub type StorageRef = Arc<DiskStorage>;
 
 pub fn err(error: impl Into<anyhow::Error>) -> StorageError {
-    StorageError(error.into())
+    StorageError::Other(error.into())
 }
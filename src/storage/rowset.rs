@@ -1,64 +1,226 @@
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use anyhow::anyhow;
 use itertools::Itertools;
 
-use super::column::{decode_int32_column, encode_int32_column};
+use super::column::{
+    decode_fixed_width_column, decode_string_column, decode_var_width_column,
+    encode_fixed_width_column, encode_string_column, encode_var_width_column,
+};
 use super::{err, StorageResult};
-use crate::array::{ArrayImpl, DataChunk};
-use crate::catalog::ColumnDesc;
+use crate::array::{
+    Array, ArrayBuilder, ArrayImpl, DataChunk, DictionaryArrayBuilder, PrimitiveArray,
+};
+use crate::catalog::{ColumnCatalog, ColumnId};
+use crate::types::{DataTypeKind, DataTypeKindExt, Date, Decimal, Interval, Timestamp};
 
-fn column_path(rowset_path: impl AsRef<Path>, column_id: usize) -> PathBuf {
+fn column_path(rowset_path: impl AsRef<Path>, column_id: ColumnId) -> PathBuf {
     rowset_path.as_ref().join(format!("{}.col", column_id))
 }
 
+fn index_path(rowset_path: impl AsRef<Path>, column_id: ColumnId) -> PathBuf {
+    rowset_path.as_ref().join(format!("{}.idx", column_id))
+}
+
+/// A set of rows written by a transaction. Each column is stored in a file named after its
+/// id, so that the rowset can still be read after columns are added to or dropped from the
+/// table.
+///
+/// An indexed column also has an index file, which has the offsets of the rows sorted by
+/// their values in the column.
 #[derive(Clone)]
 pub struct DiskRowset {
-    column_descs: Arc<[ColumnDesc]>,
-    #[allow(dead_code)]
+    columns: Arc<[ColumnCatalog]>,
+    /// The columns that have index files.
+    indexes: Vec<ColumnId>,
+    num_rows: usize,
     rowset_id: u32,
-    rowset_path: PathBuf,
+    /// The directory of the rowset, shared by its clones, e.g. in the snapshots of
+    /// transactions.
+    dir: Arc<RowsetDir>,
+}
+
+struct RowsetDir {
+    path: PathBuf,
+    /// Whether the rowset has been removed from its table, in which case its directory is
+    /// deleted when the last clone of it goes away.
+    dropped: AtomicBool,
+}
+
+impl Drop for RowsetDir {
+    fn drop(&mut self) {
+        if !*self.dropped.get_mut() {
+            return;
+        }
+        match std::fs::remove_dir_all(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                warn!("failed to delete dropped rowset {:?}: {}", self.path, e)
+            }
+            _ => {}
+        }
+    }
 }
 
 impl DiskRowset {
-    pub async fn as_chunk(&self) -> StorageResult<DataChunk> {
-        let mut columns = vec![];
-        for (idx, _) in self.column_descs.iter().enumerate() {
-            let column_path = column_path(&self.rowset_path, idx);
-            let data = tokio::fs::read(column_path).await.map_err(err)?;
-            columns.push(decode_int32_column(&data[..])?);
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn rowset_id(&self) -> u32 {
+        self.rowset_id
+    }
+
+    /// Mark the rowset as removed from its table. Its files are deleted once the transactions
+    /// reading it have finished.
+    pub fn set_dropped(&self) {
+        self.dir.dropped.store(true, Ordering::SeqCst);
+    }
+
+    /// Read the column of `id`, or returns `None` if the rowset was written before the column
+    /// was added.
+    pub async fn read_column(&self, id: ColumnId) -> StorageResult<Option<ArrayImpl>> {
+        let Some(column) = self.columns.iter().find(|col| col.id() == id) else {
+            return Ok(None);
+        };
+        let column_path = column_path(&self.dir.path, id);
+        let data = tokio::fs::read(column_path).await.map_err(err)?;
+        decode_column(column.datatype().kind(), &data).map(Some)
+    }
+
+    /// Read the row offsets sorted by the values in the column of `id`, or returns `None` if
+    /// the column is not indexed in the rowset, or the index has been dropped since.
+    pub async fn read_index(&self, id: ColumnId) -> StorageResult<Option<PrimitiveArray<u32>>> {
+        if !self.indexes.contains(&id) {
+            return Ok(None);
+        }
+        let data = match tokio::fs::read(index_path(&self.dir.path, id)).await {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(err(e)),
+        };
+        decode_fixed_width_column::<u32>(&data[..]).map(Some)
+    }
+
+    /// Write the index file of the column of `id`, and returns the rowset with the index.
+    /// The rowset is returned as it is if it does not have the column.
+    pub async fn add_index(&self, id: ColumnId) -> StorageResult<DiskRowset> {
+        if self.indexes.contains(&id) {
+            return Ok(self.clone());
+        }
+        let Some(column) = self.read_column(id).await? else {
+            return Ok(self.clone());
+        };
+        let mut buffer = vec![];
+        encode_fixed_width_column(&build_index(&column), &mut buffer)?;
+        tokio::fs::write(index_path(&self.dir.path, id), buffer)
+            .await
+            .map_err(err)?;
+        let mut rowset = self.clone();
+        rowset.indexes.push(id);
+        Ok(rowset)
+    }
+
+    /// Remove the index file of the column of `id`, and returns the rowset without the index.
+    pub async fn drop_index(&self, id: ColumnId) -> StorageResult<DiskRowset> {
+        if !self.indexes.contains(&id) {
+            return Ok(self.clone());
+        }
+        let mut rowset = self.clone();
+        rowset.indexes.retain(|&col| col != id);
+        match tokio::fs::remove_file(index_path(&self.dir.path, id)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(err(e)),
+            _ => Ok(rowset),
         }
-        Ok(columns.into_iter().map(ArrayImpl::Int32).collect())
     }
 }
 
+fn decode_column(kind: DataTypeKind, data: &[u8]) -> StorageResult<ArrayImpl> {
+    let column: ArrayImpl = match kind.canonical() {
+        DataTypeKind::SmallInt(_) => decode_fixed_width_column::<i16>(data)?.into(),
+        DataTypeKind::Int(_) => decode_fixed_width_column::<i32>(data)?.into(),
+        DataTypeKind::BigInt(_) => decode_fixed_width_column::<i64>(data)?.into(),
+        DataTypeKind::Decimal(_) => decode_fixed_width_column::<Decimal>(data)?.into(),
+        DataTypeKind::Varchar(_) => decode_string_column(data)?,
+        DataTypeKind::Bytea => decode_var_width_column::<[u8]>(data)?.into(),
+        DataTypeKind::Date => decode_fixed_width_column::<Date>(data)?.into(),
+        DataTypeKind::Timestamp(..) => decode_fixed_width_column::<Timestamp>(data)?.into(),
+        DataTypeKind::Interval => decode_fixed_width_column::<Interval>(data)?.into(),
+        kind => return Err(anyhow!("unsupported column type: {}", kind).into()),
+    };
+    Ok(column)
+}
+
+/// Returns the offsets of the rows sorted by their values in the column. Rows with equal
+/// values are kept in their order.
+fn build_index(column: &ArrayImpl) -> PrimitiveArray<u32> {
+    let values = (0..column.len()).map(|i| column.get(i)).collect_vec();
+    let mut offsets = (0..values.len() as u32).collect_vec();
+    offsets.sort_by(|&a, &b| {
+        let (a, b) = (&values[a as usize], &values[b as usize]);
+        a.partial_cmp(b).expect("incomparable values in column")
+    });
+    offsets.into_iter().collect()
+}
+
 pub struct RowsetBuilder {
-    column_descs: Arc<[ColumnDesc]>,
+    columns: Arc<[ColumnCatalog]>,
+    /// The columns to write index files for.
+    indexes: Vec<ColumnId>,
+    num_rows: usize,
     buffer: Vec<Vec<u8>>,
+    /// The string columns, which are encoded when the rowset is flushed, as the encoding
+    /// depends on the number of distinct strings.
+    strings: Vec<Option<DictionaryArrayBuilder>>,
 }
 
 impl RowsetBuilder {
-    pub fn new(column_descs: Arc<[ColumnDesc]>) -> Self {
+    pub fn new(columns: Arc<[ColumnCatalog]>, indexes: Vec<ColumnId>) -> Self {
         RowsetBuilder {
-            buffer: (0..column_descs.len()).map(|_| vec![]).collect_vec(),
-            column_descs,
+            indexes,
+            buffer: (0..columns.len()).map(|_| vec![]).collect_vec(),
+            strings: (0..columns.len()).map(|_| None).collect_vec(),
+            columns,
+            num_rows: 0,
         }
     }
 
     pub fn append(&mut self, chunk: DataChunk) -> StorageResult<()> {
+        let nullable = || anyhow!("nullable encoding not supported!");
+        self.num_rows += chunk.cardinality();
         for (idx, column) in chunk.arrays().iter().enumerate() {
-            if let ArrayImpl::Int32(column) = column {
-                encode_int32_column(column, &mut self.buffer[idx])?;
-            } else {
-                return Err(anyhow!("unsupported column type").into());
+            let buffer = &mut self.buffer[idx];
+            let strings = || DictionaryArrayBuilder::with_capacity(column.len());
+            match column {
+                ArrayImpl::Int16(column) => encode_fixed_width_column(column, buffer)?,
+                ArrayImpl::Int32(column) => encode_fixed_width_column(column, buffer)?,
+                ArrayImpl::Int64(column) => encode_fixed_width_column(column, buffer)?,
+                ArrayImpl::Decimal(column) => encode_fixed_width_column(column, buffer)?,
+                ArrayImpl::Utf8(column) => {
+                    let builder = self.strings[idx].get_or_insert_with(strings);
+                    for s in column.iter() {
+                        builder.push(Some(s.ok_or_else(nullable)?));
+                    }
+                }
+                ArrayImpl::Dictionary(column) => {
+                    if column.iter().any(|s| s.is_none()) {
+                        return Err(nullable().into());
+                    }
+                    self.strings[idx].get_or_insert_with(strings).append(column);
+                }
+                ArrayImpl::Bytes(column) => encode_var_width_column(column, buffer)?,
+                ArrayImpl::Date(column) => encode_fixed_width_column(column, buffer)?,
+                ArrayImpl::Timestamp(column) => encode_fixed_width_column(column, buffer)?,
+                ArrayImpl::Interval(column) => encode_fixed_width_column(column, buffer)?,
+                _ => return Err(anyhow!("unsupported column type").into()),
             }
         }
         Ok(())
     }
 
     pub async fn flush(
-        self,
+        mut self,
         rowset_id: u32,
         rowset_path: impl AsRef<Path>,
     ) -> StorageResult<DiskRowset> {
@@ -66,17 +228,36 @@ impl RowsetBuilder {
 
         tokio::fs::create_dir_all(rowset_path).await.map_err(err)?;
 
-        for (idx, _) in self.column_descs.iter().enumerate() {
-            let column_path = column_path(rowset_path, idx);
+        for (idx, column) in self.columns.iter().enumerate() {
+            if let Some(strings) = self.strings[idx].take() {
+                encode_string_column(&strings.finish(), &mut self.buffer[idx])?;
+            }
+            let column_path = column_path(rowset_path, column.id());
             tokio::fs::write(column_path, &self.buffer[idx])
                 .await
                 .map_err(err)?;
         }
+        self.indexes
+            .retain(|id| self.columns.iter().any(|col| col.id() == *id));
+        for id in self.indexes.iter() {
+            let idx = self.columns.iter().position(|col| col.id() == *id).unwrap();
+            let column = decode_column(self.columns[idx].datatype().kind(), &self.buffer[idx])?;
+            let mut buffer = vec![];
+            encode_fixed_width_column(&build_index(&column), &mut buffer)?;
+            tokio::fs::write(index_path(rowset_path, *id), buffer)
+                .await
+                .map_err(err)?;
+        }
 
         Ok(DiskRowset {
-            column_descs: self.column_descs,
+            columns: self.columns,
+            indexes: self.indexes,
+            num_rows: self.num_rows,
             rowset_id,
-            rowset_path: rowset_path.into(),
+            dir: Arc::new(RowsetDir {
+                path: rowset_path.into(),
+                dropped: AtomicBool::new(false),
+            }),
         })
     }
 }
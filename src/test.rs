@@ -1,19 +1,192 @@
 use std::path::Path;
 
-use test_case::test_case;
 use tempfile::tempdir;
+use test_case::test_case;
 
-use crate::{array::DataChunk, storage::StorageOptions, types::DataValue, Database, Error};
+use crate::array::*;
+use crate::function::Accumulator;
+use crate::storage::StorageOptions;
+use crate::types::{format_bytea, ConvertError, DataTypeExt, DataTypeKind, DataValue};
+use crate::{Database, Error};
 
 #[test_case("03-01.slt")]
 #[test_case("03-02.slt")]
+#[test_case("04-01.slt")]
+#[test_case("04-02.slt")]
+#[test_case("04-03.slt")]
+#[test_case("04-04.slt")]
+#[test_case("04-05.slt")]
+#[test_case("04-06.slt")]
+#[test_case("04-07.slt")]
+#[test_case("04-09.slt")]
+#[test_case("04-10.slt")]
+#[test_case("04-11.slt")]
+#[test_case("04-12.slt")]
+#[test_case("04-13.slt")]
+#[test_case("04-14.slt")]
+#[test_case("04-15.slt")]
+#[test_case("04-16.slt")]
+#[test_case("04-17.slt")]
+#[test_case("04-18.slt")]
+#[test_case("04-19.slt")]
+#[test_case("04-20.slt")]
+#[test_case("04-21.slt")]
+#[test_case("04-22.slt")]
+#[test_case("04-23.slt")]
+#[test_case("04-24.slt")]
 fn test(name: &str) {
+    run_script(name, |_| {});
+}
+
+#[test_case("04-08.slt")]
+fn test_udf(name: &str) {
+    run_script(name, register_udfs);
+}
+
+#[test]
+fn test_run_sql_arrow() {
+    use arrow::array::{as_primitive_array, as_string_array, Array};
+    use arrow::datatypes::{DataType, Decimal128Type, Int32Type};
+
+    init_logger();
+    let tempdir = tempdir().unwrap();
+    let db = Database::new(StorageOptions {
+        base_path: tempdir.path().into(),
+    });
+    db.run_sql("CREATE TABLE t (a INT NOT NULL, b VARCHAR NOT NULL, c DECIMAL(5, 2) NOT NULL)")
+        .unwrap();
+    db.run_sql("INSERT INTO t VALUES (1, 'x', 1.5), (2, 'y', 2.25), (3, 'x', 3)")
+        .unwrap();
+
+    let batches = db
+        .run_sql_arrow("SELECT a, b AS name, c, a + 1 FROM t WHERE a <> 2")
+        .unwrap();
+    assert_eq!(batches.len(), 1);
+    let batch = &batches[0];
+    let schema = batch.schema();
+    let names = schema.fields().iter().map(|f| f.name().as_str());
+    assert_eq!(names.collect::<Vec<_>>(), ["a", "name", "c", "?column?"]);
+    assert_eq!(schema.field(1).data_type(), &DataType::Utf8);
+    assert_eq!(schema.field(2).data_type(), &DataType::Decimal128(5, 2));
+    assert!(!schema.field(0).is_nullable());
+    let a = as_primitive_array::<Int32Type>(batch.column(0));
+    assert_eq!(a.values(), [1, 3]);
+    let name = as_string_array(batch.column(1));
+    assert_eq!(name.iter().collect::<Vec<_>>(), [Some("x"), Some("x")]);
+    let c = as_primitive_array::<Decimal128Type>(batch.column(2));
+    assert_eq!(c.values(), [150, 300]);
+
+    // a query without rows still returns its schema
+    let batches = db.run_sql_arrow("SELECT b FROM t WHERE a > 3").unwrap();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].num_rows(), 0);
+    assert_eq!(batches[0].column(0).data_type(), &DataType::Utf8);
+    assert_eq!(batches[0].schema().field(0).name(), "b");
+}
+
+#[test]
+fn test_drop_table_files() {
+    init_logger();
+    let tempdir = tempdir().unwrap();
+    let db = Database::new(StorageOptions {
+        base_path: tempdir.path().into(),
+    });
+    db.run_sql("CREATE TABLE t (v INT NOT NULL); INSERT INTO t VALUES (1)")
+        .unwrap();
+    let files = || std::fs::read_dir(tempdir.path()).unwrap().count();
+    assert_eq!(files(), 1);
+    db.run_sql("DROP TABLE t").unwrap();
+    assert_eq!(files(), 0);
+
+    db.run_sql("CREATE SCHEMA s; CREATE TABLE s.t (v INT NOT NULL); INSERT INTO s.t VALUES (1)")
+        .unwrap();
+    assert_eq!(files(), 1);
+    db.run_sql("DROP SCHEMA s CASCADE").unwrap();
+    assert_eq!(files(), 0);
+}
+
+#[test]
+fn test_register_function_without_arguments() {
+    let tempdir = tempdir().unwrap();
+    let db = Database::new(StorageOptions {
+        base_path: tempdir.path().into(),
+    });
+    let result = db.register_scalar_function("f", &[], DataTypeKind::Int(None).nullable(), |_| {
+        Ok(ArrayImpl::Int32([Some(1)].into_iter().collect()))
+    });
+    assert!(matches!(result, Err(Error::InvalidFunction(_))));
+}
+
+#[test]
+fn test_index_scan() {
+    init_logger();
+    let tempdir = tempdir().unwrap();
+    let db = Database::new(StorageOptions {
+        base_path: tempdir.path().into(),
+    });
+    db.run_sql("CREATE TABLE t (a INT NOT NULL, b INT NOT NULL); INSERT INTO t VALUES (1, 2)")
+        .unwrap();
+    db.run_sql("CREATE INDEX t_a ON t (a); INSERT INTO t VALUES (3, 4)")
+        .unwrap();
+    let index_files = || {
+        let files = walkdir(tempdir.path());
+        files.iter().filter(|f| f.ends_with("0.idx")).count()
+    };
+    assert_eq!(index_files(), 2);
+
+    let explain = |sql: &str| {
+        let chunks = db.run_sql(&format!("EXPLAIN {}", sql)).unwrap();
+        chunks.iter().map(datachunk_to_string).collect::<String>()
+    };
+    let plan = explain("SELECT b FROM t WHERE a = 1 AND b > 0");
+    assert!(plan.contains("IndexScan: table #0, columns: [0, 1], index column: 0, range: [1, 1]"));
+    let plan = explain("SELECT b FROM t WHERE 2 < a");
+    assert!(plan.contains("range: (2, +inf)"));
+    // no index on the column, or the column is not compared with a constant
+    assert!(!explain("SELECT b FROM t WHERE b = 1").contains("IndexScan"));
+    assert!(!explain("SELECT b FROM t WHERE a = b").contains("IndexScan"));
+    assert!(!explain("SELECT b FROM t WHERE a = 1 OR b = 1").contains("IndexScan"));
+
+    // filters on a view are pushed into its query
+    db.run_sql("CREATE VIEW v AS SELECT a, b, b * 2 AS c FROM t WHERE b > 0")
+        .unwrap();
+    let plan = explain("SELECT c FROM v WHERE a = 1");
+    assert!(plan.contains("range: [1, 1]"));
+    let plan = explain("SELECT c FROM (SELECT a, b, c FROM v) AS w WHERE a < 3 AND b < 5");
+    assert!(plan.contains("range: (-inf, 3)"));
+    // a filter that may fail is not evaluated on the rows removed by the view
+    let plan = explain("SELECT c FROM v WHERE a / b = 1");
+    assert!(!plan.contains("IndexScan"));
+    db.run_sql("DROP VIEW v").unwrap();
+
+    db.run_sql("DROP INDEX t_a").unwrap();
+    assert_eq!(index_files(), 0);
+    assert!(!explain("SELECT b FROM t WHERE a = 1").contains("IndexScan"));
+}
+
+/// Returns the paths of the files in the directory and its subdirectories.
+fn walkdir(dir: &Path) -> Vec<std::path::PathBuf> {
+    let mut files = vec![];
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        match path.is_dir() {
+            true => files.extend(walkdir(&path)),
+            false => files.push(path),
+        }
+    }
+    files
+}
+
+/// Run the script on a new database that is set up by `init`.
+fn run_script(name: &str, init: impl FnOnce(&Database)) {
     init_logger();
     let script = std::fs::read_to_string(Path::new("./sql").join(name)).unwrap();
     let tempdir = tempdir().unwrap();
-    let mut tester = sqllogictest::Runner::new(Database::new(StorageOptions {
+    let db = Database::new(StorageOptions {
         base_path: tempdir.path().into(),
-    }));
+    });
+    init(&db);
+    let mut tester = sqllogictest::Runner::new(db);
     if let Err(err) = tester.run_script(&script) {
         panic!("{}", err);
     }
@@ -45,10 +218,17 @@ fn datachunk_to_string(chunk: &DataChunk) -> String {
             match array.get(row) {
                 DataValue::Null => write!(string, "NULL"),
                 DataValue::Bool(v) => write!(string, "{}", v),
+                DataValue::Int16(v) => write!(string, "{}", v),
                 DataValue::Int32(v) => write!(string, "{}", v),
+                DataValue::Int64(v) => write!(string, "{}", v),
                 DataValue::Float64(v) => write!(string, "{}", v),
+                DataValue::Decimal(v) => write!(string, "{}", v),
                 DataValue::String(s) if s.is_empty() => write!(string, "(empty)"),
                 DataValue::String(s) => write!(string, "{}", s),
+                DataValue::Bytes(v) => write!(string, "{}", format_bytea(&v)),
+                DataValue::Date(v) => write!(string, "{}", v),
+                DataValue::Timestamp(v) => write!(string, "{}", v),
+                DataValue::Interval(v) => write!(string, "{}", v),
             }
             .unwrap();
         }
@@ -56,3 +236,105 @@ fn datachunk_to_string(chunk: &DataChunk) -> String {
     }
     string
 }
+
+fn register_udfs(db: &Database) {
+    const INT: DataTypeKind = DataTypeKind::Int(None);
+    const DOUBLE: DataTypeKind = DataTypeKind::Double;
+    const VARCHAR: DataTypeKind = DataTypeKind::Varchar(None);
+
+    db.register_scalar_function("twice", &[INT.nullable()], INT.nullable(), |args| {
+        let a: &I32Array = (&args[0]).try_into().unwrap();
+        let twice = |v: &i32| v.checked_mul(2).ok_or(ConvertError::Overflow);
+        Ok(unary_op::<_, I32Array, _>(a, twice)?.into())
+    })
+    .unwrap();
+    db.register_scalar_function("twice", &[VARCHAR.nullable()], VARCHAR.nullable(), |args| {
+        let a: &Utf8Array = (&args[0]).try_into().unwrap();
+        Ok(unary_op::<_, Utf8Array, _>(a, |v| Ok(v.repeat(2)))?.into())
+    })
+    .unwrap();
+    // NULL is treated as zero
+    db.register_scalar_function(
+        "add_or_zero",
+        &[INT.nullable(), INT.nullable()],
+        INT.not_null(),
+        |args| {
+            let (a, b): (&I32Array, &I32Array) = (
+                (&args[0]).try_into().unwrap(),
+                (&args[1]).try_into().unwrap(),
+            );
+            let sum = a.iter().zip(b.iter()).map(|(a, b)| {
+                a.unwrap_or(&0)
+                    .checked_add(*b.unwrap_or(&0))
+                    .ok_or(ConvertError::Overflow)
+            });
+            Ok(ArrayImpl::Int32(sum.collect::<Result<_, _>>()?))
+        },
+    )
+    .unwrap();
+    // functions that break the contract
+    db.register_scalar_function("wrong_type", &[INT.nullable()], INT.nullable(), |args| {
+        let a: &I32Array = (&args[0]).try_into().unwrap();
+        Ok(unary_op::<_, I64Array, _>(a, |v| Ok(*v as i64))?.into())
+    })
+    .unwrap();
+    db.register_scalar_function("wrong_length", &[INT.nullable()], INT.nullable(), |args| {
+        let a: &I32Array = (&args[0]).try_into().unwrap();
+        Ok(ArrayImpl::Int32(
+            a.iter().skip(1).map(|v| v.copied()).collect(),
+        ))
+    })
+    .unwrap();
+
+    #[derive(Default)]
+    struct Product(Option<i32>);
+
+    impl Accumulator for Product {
+        fn update(&mut self, args: &[DataValue]) -> Result<(), ConvertError> {
+            let DataValue::Int32(v) = args[0] else { panic!("expect int") };
+            let product = self.0.unwrap_or(1).checked_mul(v);
+            self.0 = Some(product.ok_or(ConvertError::Overflow)?);
+            Ok(())
+        }
+
+        fn output(&self) -> DataValue {
+            self.0.map_or(DataValue::Null, DataValue::Int32)
+        }
+    }
+    db.register_aggregate_function(
+        "product",
+        &[INT.nullable()],
+        INT.nullable(),
+        Product::default,
+    );
+
+    #[derive(Default)]
+    struct WeightedAvg {
+        sum: f64,
+        weight: f64,
+    }
+
+    impl Accumulator for WeightedAvg {
+        fn update(&mut self, args: &[DataValue]) -> Result<(), ConvertError> {
+            let (DataValue::Float64(v), DataValue::Float64(w)) = (&args[0], &args[1]) else {
+                panic!("expect double")
+            };
+            self.sum += v * w;
+            self.weight += w;
+            Ok(())
+        }
+
+        fn output(&self) -> DataValue {
+            match self.weight {
+                w if w == 0.0 => DataValue::Null,
+                w => DataValue::Float64(self.sum / w),
+            }
+        }
+    }
+    db.register_aggregate_function(
+        "weighted_avg",
+        &[DOUBLE.nullable(), DOUBLE.nullable()],
+        DOUBLE.nullable(),
+        WeightedAvg::default,
+    );
+}
@@ -0,0 +1,50 @@
+use std::fmt::Write;
+
+/// Formats a byte string in the hex format of PostgreSQL, e.g. `\x01ff`.
+pub fn format_bytea(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(2 + bytes.len() * 2);
+    s.push_str("\\x");
+    for b in bytes {
+        write!(s, "{:02x}", b).unwrap();
+    }
+    s
+}
+
+/// Parses the hex digits of a byte string, e.g. `01ff` or `01 FF`. Whitespace is allowed
+/// between bytes.
+pub fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(s.len() / 2);
+    let mut digits = s.chars().filter(|c| !c.is_ascii_whitespace());
+    while let Some(hi) = digits.next() {
+        let lo = digits.next()?;
+        bytes.push((hi.to_digit(16)? * 16 + lo.to_digit(16)?) as u8);
+    }
+    Some(bytes)
+}
+
+/// Parses the text representation of a byte string: either the hex format `\x01ff`, or the
+/// escape format where `\\` is a backslash and `\ooo` is an octal byte.
+pub fn parse_bytea(s: &str) -> Option<Vec<u8>> {
+    if let Some(hex) = s.strip_prefix("\\x") {
+        return parse_hex(hex);
+    }
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+        if b != b'\\' {
+            bytes.push(b);
+        } else if let Some(tail) = rest.strip_prefix(b"\\") {
+            bytes.push(b'\\');
+            rest = tail;
+        } else {
+            let octal = rest.get(..3)?;
+            if !matches!(octal, [b'0'..=b'3', b'0'..=b'7', b'0'..=b'7']) {
+                return None;
+            }
+            bytes.push(octal.iter().fold(0, |v, d| v * 8 + (d - b'0')));
+            rest = &rest[3..];
+        }
+    }
+    Some(bytes)
+}
@@ -0,0 +1,399 @@
+use rust_decimal::prelude::ToPrimitive;
+pub use rust_decimal::Decimal;
+use rust_decimal::RoundingStrategy;
+pub use sqlparser::ast::{DataType as DataTypeKind, ExactNumberInfo, TimezoneInfo};
+
+mod bytes;
+mod temporal;
+
+pub use self::bytes::*;
+pub use self::temporal::*;
+
+/// The maximum precision of `DECIMAL`, i.e. the number of digits that `Decimal` can hold.
+pub const MAX_DECIMAL_PRECISION: u64 = 28;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DataType {
+    kind: DataTypeKind,
+    nullable: bool,
+}
+
+impl DataType {
+    pub const fn new(kind: DataTypeKind, nullable: bool) -> Self {
+        DataType { kind, nullable }
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    pub fn kind(&self) -> DataTypeKind {
+        self.kind.clone()
+    }
+}
+
+impl std::fmt::Debug for DataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.kind)?;
+        if self.nullable {
+            write!(f, " (null)")?;
+        }
+        Ok(())
+    }
+}
+
+pub trait DataTypeExt {
+    fn nullable(self) -> DataType;
+
+    fn not_null(self) -> DataType;
+}
+
+impl DataTypeExt for DataTypeKind {
+    fn nullable(self) -> DataType {
+        DataType::new(self, true)
+    }
+
+    fn not_null(self) -> DataType {
+        DataType::new(self, false)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum DataValue {
+    // NOTE: Null comes first.
+    // => NULL is less than any non-NULL values
+    Null,
+    Bool(bool),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Float64(f64),
+    Decimal(Decimal),
+    String(String),
+    Bytes(Vec<u8>),
+    Date(Date),
+    Timestamp(Timestamp),
+    Interval(Interval),
+}
+
+// NOTE: `Float64` values are compared by value, so NaN is not equal to itself.
+impl Eq for DataValue {}
+
+// The hash is consistent with the derived `PartialEq`.
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for DataValue {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Null => {}
+            Self::Bool(v) => v.hash(state),
+            Self::Int16(v) => v.hash(state),
+            Self::Int32(v) => v.hash(state),
+            Self::Int64(v) => v.hash(state),
+            // 0.0 and -0.0 are equal, so they must have the same hash.
+            Self::Float64(v) if *v == 0.0 => 0.0f64.to_bits().hash(state),
+            Self::Float64(v) => v.to_bits().hash(state),
+            // equal decimals with different scales, e.g. 1.0 and 1.00, have the same hash
+            Self::Decimal(v) => v.hash(state),
+            Self::String(v) => v.hash(state),
+            Self::Bytes(v) => v.hash(state),
+            Self::Date(v) => v.hash(state),
+            Self::Timestamp(v) => v.hash(state),
+            // equal intervals with different parts, e.g. 1 mon and 30 days, have the same hash
+            Self::Interval(v) => v.hash(state),
+        }
+    }
+}
+
+impl ToString for DataValue {
+    fn to_string(&self) -> String {
+        match self {
+            Self::Null => String::from("NULL"),
+            Self::Bool(v) => v.to_string(),
+            Self::Int16(v) => v.to_string(),
+            Self::Int32(v) => v.to_string(),
+            Self::Int64(v) => v.to_string(),
+            Self::Float64(v) => v.to_string(),
+            Self::Decimal(v) => v.to_string(),
+            Self::String(v) => v.to_string(),
+            Self::Bytes(v) => format_bytea(v),
+            Self::Date(v) => v.to_string(),
+            Self::Timestamp(v) => v.to_string(),
+            Self::Interval(v) => v.to_string(),
+        }
+    }
+}
+
+impl DataValue {
+    pub fn datatype(&self) -> Option<DataType> {
+        match self {
+            Self::Bool(_) => Some(DataTypeKind::Boolean.not_null()),
+            Self::Int16(_) => Some(DataTypeKind::SmallInt(None).not_null()),
+            Self::Int32(_) => Some(DataTypeKind::Int(None).not_null()),
+            Self::Int64(_) => Some(DataTypeKind::BigInt(None).not_null()),
+            Self::Float64(_) => Some(DataTypeKind::Double.not_null()),
+            Self::Decimal(_) => Some(DataTypeKind::Decimal(ExactNumberInfo::None).not_null()),
+            Self::String(_) => Some(DataTypeKind::Varchar(None).not_null()),
+            Self::Bytes(_) => Some(DataTypeKind::Bytea.not_null()),
+            Self::Date(_) => Some(DataTypeKind::Date.not_null()),
+            Self::Timestamp(_) => {
+                Some(DataTypeKind::Timestamp(None, TimezoneInfo::None).not_null())
+            }
+            Self::Interval(_) => Some(DataTypeKind::Interval.not_null()),
+            Self::Null => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Returns the value of an integer, or `None` if it is not an integer.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Int16(v) => Some(*v as i64),
+            Self::Int32(v) => Some(*v as i64),
+            Self::Int64(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns an integer of type `ty`, or an error if it is out of range.
+    fn from_i64(v: i64, ty: &DataTypeKind) -> Result<Self, ConvertError> {
+        Ok(match ty.integer_bits() {
+            Some(16) => Self::Int16(v.try_into().map_err(|_| ConvertError::Overflow)?),
+            Some(64) => Self::Int64(v),
+            _ => Self::Int32(v.try_into().map_err(|_| ConvertError::Overflow)?),
+        })
+    }
+
+    /// Returns a decimal of type `ty`, rounded to its scale, or an error if it has more digits
+    /// than the precision allows.
+    fn from_decimal(mut v: Decimal, ty: &DataTypeKind) -> Result<Self, ConvertError> {
+        let (precision, scale) = ty.decimal_precision_scale().expect("not a decimal type");
+        if let Some(scale) = scale {
+            v.rescale(scale as u32);
+        }
+        if let Some(precision) = precision {
+            // the number of digits before the decimal point
+            let digits = match v.trunc().abs() {
+                int if int.is_zero() => 0,
+                int => int.to_string().len() as u64,
+            };
+            if digits + scale.unwrap_or(0) > precision {
+                return Err(ConvertError::Overflow);
+            }
+        }
+        Ok(Self::Decimal(v))
+    }
+
+    /// Cast the value to the given type.
+    pub fn cast(&self, ty: &DataTypeKind) -> Result<DataValue, ConvertError> {
+        let err = || ConvertError::Cast(self.to_string(), ty.clone());
+        if let Some(i) = self.as_i64() {
+            return Ok(match ty {
+                DataTypeKind::Boolean => Self::Bool(i != 0),
+                ty if ty.is_integer() => Self::from_i64(i, ty)?,
+                ty if ty.is_float() => Self::Float64(i as f64),
+                ty if ty.is_decimal() => Self::from_decimal(i.into(), ty)?,
+                ty if ty.is_string() => Self::String(i.to_string()),
+                _ => return Err(err()),
+            });
+        }
+        Ok(match (self, ty) {
+            (Self::Null, _) => Self::Null,
+            (Self::Bool(b), DataTypeKind::Boolean) => Self::Bool(*b),
+            (Self::Bool(b), ty) if ty.is_integer() => Self::from_i64(*b as i64, ty)?,
+            (Self::Bool(b), ty) if ty.is_string() => Self::String(b.to_string()),
+            (Self::Float64(f), ty) if ty.is_integer() => {
+                let f = f.round();
+                // `i64::MAX as f64` is rounded up to 2^63
+                if f < i64::MIN as f64 || f >= i64::MAX as f64 || f.is_nan() {
+                    return Err(ConvertError::Overflow);
+                }
+                Self::from_i64(f as i64, ty)?
+            }
+            (Self::Float64(f), ty) if ty.is_float() => Self::Float64(*f),
+            (Self::Float64(f), ty) if ty.is_decimal() => {
+                Self::from_decimal(Decimal::try_from(*f).map_err(|_| err())?, ty)?
+            }
+            (Self::Float64(f), ty) if ty.is_string() => Self::String(f.to_string()),
+            (Self::Decimal(d), ty) if ty.is_integer() => {
+                let i = d.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+                let i = i.to_i64().ok_or(ConvertError::Overflow)?;
+                Self::from_i64(i, ty)?
+            }
+            (Self::Decimal(d), ty) if ty.is_float() => Self::Float64(d.to_f64().ok_or_else(err)?),
+            (Self::Decimal(d), ty) if ty.is_decimal() => Self::from_decimal(*d, ty)?,
+            (Self::Decimal(d), ty) if ty.is_string() => Self::String(d.to_string()),
+            (Self::String(s), DataTypeKind::Boolean) => match s.to_lowercase().as_str() {
+                "t" | "true" | "y" | "yes" | "on" | "1" => Self::Bool(true),
+                "f" | "false" | "n" | "no" | "off" | "0" => Self::Bool(false),
+                _ => return Err(err()),
+            },
+            (Self::String(s), ty) if ty.is_integer() => {
+                let v: i64 = s.trim().parse().map_err(|_| err())?;
+                Self::from_i64(v, ty)?
+            }
+            (Self::String(s), ty) if ty.is_float() => {
+                Self::Float64(s.trim().parse().map_err(|_| err())?)
+            }
+            (Self::String(s), ty) if ty.is_decimal() => {
+                Self::from_decimal(s.trim().parse().map_err(|_| err())?, ty)?
+            }
+            (Self::String(s), ty) if ty.is_string() => Self::String(s.clone()),
+            (Self::String(s), DataTypeKind::Date) => Self::Date(s.parse().map_err(|_| err())?),
+            (Self::String(s), ty) if ty.is_timestamp() => {
+                Self::Timestamp(s.parse().map_err(|_| err())?)
+            }
+            (Self::String(s), DataTypeKind::Interval) => {
+                Self::Interval(s.parse().map_err(|_| err())?)
+            }
+            (Self::String(s), ty) if ty.is_binary() => Self::Bytes(parse_bytea(s).ok_or_else(err)?),
+            (Self::Bytes(b), ty) if ty.is_binary() => Self::Bytes(b.clone()),
+            (Self::Bytes(b), ty) if ty.is_string() => Self::String(format_bytea(b)),
+            (Self::Date(d), DataTypeKind::Date) => Self::Date(*d),
+            (Self::Date(d), ty) if ty.is_timestamp() => Self::Timestamp(d.to_timestamp()),
+            (Self::Date(d), ty) if ty.is_string() => Self::String(d.to_string()),
+            (Self::Timestamp(t), DataTypeKind::Date) => Self::Date(t.date()),
+            (Self::Timestamp(t), ty) if ty.is_timestamp() => Self::Timestamp(*t),
+            (Self::Timestamp(t), ty) if ty.is_string() => Self::String(t.to_string()),
+            (Self::Interval(i), DataTypeKind::Interval) => Self::Interval(*i),
+            (Self::Interval(i), ty) if ty.is_string() => Self::String(i.to_string()),
+            _ => return Err(err()),
+        })
+    }
+}
+
+/// Classification of the SQL types supported by the execution engine.
+pub trait DataTypeKindExt {
+    /// Returns the number of bits of an integer type, or `None` if it is not an integer type.
+    fn integer_bits(&self) -> Option<u32>;
+
+    fn is_integer(&self) -> bool {
+        self.integer_bits().is_some()
+    }
+
+    fn is_float(&self) -> bool;
+
+    fn is_string(&self) -> bool;
+
+    /// Returns true for `BYTEA`, `BLOB`, `BINARY` and `VARBINARY`.
+    fn is_binary(&self) -> bool;
+
+    /// Returns true for `DECIMAL`, `DEC` and `NUMERIC`.
+    fn is_decimal(&self) -> bool {
+        self.decimal_precision_scale().is_some()
+    }
+
+    /// Returns the precision and scale of a decimal type, or `None` if it is not a decimal
+    /// type. `DECIMAL` has neither and `DECIMAL(p)` has a scale of 0.
+    fn decimal_precision_scale(&self) -> Option<(Option<u64>, Option<u64>)>;
+
+    fn is_number(&self) -> bool {
+        self.is_integer() || self.is_float() || self.is_decimal()
+    }
+
+    /// Returns true for `TIMESTAMP` and `DATETIME`.
+    fn is_timestamp(&self) -> bool;
+
+    /// Returns true for `DATE`, `TIMESTAMP` and `INTERVAL`.
+    fn is_temporal(&self) -> bool;
+
+    /// Returns the kind of the values of this type in the execution engine, e.g. `Int(None)`
+    /// for `Integer(Some(4))`. Values of types with the same canonical kind need no casts.
+    fn canonical(&self) -> DataTypeKind;
+}
+
+impl DataTypeKindExt for DataTypeKind {
+    fn integer_bits(&self) -> Option<u32> {
+        match self {
+            Self::TinyInt(_) | Self::SmallInt(_) => Some(16),
+            Self::MediumInt(_) | Self::Int(_) | Self::Integer(_) => Some(32),
+            Self::BigInt(_) => Some(64),
+            _ => None,
+        }
+    }
+
+    fn is_float(&self) -> bool {
+        matches!(
+            self,
+            Self::Float(_) | Self::Real | Self::Double | Self::DoublePrecision
+        )
+    }
+
+    fn is_string(&self) -> bool {
+        matches!(
+            self,
+            Self::Char(_)
+                | Self::Character(_)
+                | Self::Varchar(_)
+                | Self::CharVarying(_)
+                | Self::CharacterVarying(_)
+                | Self::String
+                | Self::Text
+        )
+    }
+
+    fn is_binary(&self) -> bool {
+        matches!(
+            self,
+            Self::Bytea | Self::Blob(_) | Self::Binary(_) | Self::Varbinary(_)
+        )
+    }
+
+    fn is_timestamp(&self) -> bool {
+        matches!(self, Self::Timestamp(..) | Self::Datetime(_))
+    }
+
+    fn is_temporal(&self) -> bool {
+        matches!(self, Self::Date | Self::Interval) || self.is_timestamp()
+    }
+
+    fn decimal_precision_scale(&self) -> Option<(Option<u64>, Option<u64>)> {
+        match self {
+            Self::Decimal(info) | Self::Dec(info) | Self::Numeric(info) => Some(match info {
+                ExactNumberInfo::None => (None, None),
+                ExactNumberInfo::Precision(p) => (Some(*p), Some(0)),
+                ExactNumberInfo::PrecisionAndScale(p, s) => (Some(*p), Some(*s)),
+            }),
+            _ => None,
+        }
+    }
+
+    fn canonical(&self) -> DataTypeKind {
+        match self.integer_bits() {
+            Some(16) => return Self::SmallInt(None),
+            Some(32) => return Self::Int(None),
+            Some(64) => return Self::BigInt(None),
+            _ => {}
+        }
+        if self.is_float() {
+            Self::Double
+        } else if let Self::Dec(info) | Self::Numeric(info) = self {
+            // the precision and scale are kept as values are rounded to them
+            Self::Decimal(info.clone())
+        } else if self.is_string() {
+            Self::Varchar(None)
+        } else if self.is_binary() {
+            Self::Bytea
+        } else if self.is_timestamp() {
+            // the precision is ignored as values are stored in microseconds
+            Self::Timestamp(None, TimezoneInfo::None)
+        } else {
+            self.clone()
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum ConvertError {
+    #[error("failed to cast {0} to {1}")]
+    Cast(String, DataTypeKind),
+    #[error("numeric value out of range")]
+    Overflow,
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+}
@@ -0,0 +1,606 @@
+//! Date, timestamp and interval values.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{Datelike, Months, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use rust_decimal::Decimal;
+
+use super::ConvertError;
+
+const MICROS_PER_SEC: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SEC;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+/// The number of days from 0001-01-01 to 1970-01-01.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// A date, stored as the number of days since 1970-01-01.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date(i32);
+
+impl Date {
+    /// Returns the date `days` days after 1970-01-01, or `None` if it is out of range.
+    pub fn from_days(days: i32) -> Option<Self> {
+        let days_from_ce = days.checked_add(UNIX_EPOCH_DAYS_FROM_CE)?;
+        NaiveDate::from_num_days_from_ce_opt(days_from_ce).map(|_| Date(days))
+    }
+
+    /// Returns the number of days since 1970-01-01.
+    pub fn days(self) -> i32 {
+        self.0
+    }
+
+    pub fn checked_add_days(self, days: i32) -> Option<Self> {
+        Self::from_days(self.0.checked_add(days)?)
+    }
+
+    pub fn checked_sub_days(self, days: i32) -> Option<Self> {
+        Self::from_days(self.0.checked_sub(days)?)
+    }
+
+    /// Returns the number of days from `other` to `self`.
+    pub fn days_since(self, other: Self) -> i32 {
+        // the range of dates is much smaller than that of `i32`
+        self.0 - other.0
+    }
+
+    /// Returns the midnight at the beginning of the date.
+    pub fn to_timestamp(self) -> Timestamp {
+        Timestamp(self.0 as i64 * MICROS_PER_DAY)
+    }
+
+    fn from_naive(date: NaiveDate) -> Self {
+        Date(date.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE)
+    }
+
+    fn to_naive(self) -> NaiveDate {
+        NaiveDate::from_num_days_from_ce_opt(self.0 + UNIX_EPOCH_DAYS_FROM_CE)
+            .expect("date out of range")
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_naive())
+    }
+}
+
+impl FromStr for Date {
+    type Err = ConvertError;
+
+    /// Parse a date in the format `YYYY-MM-DD`. The time of a timestamp is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_datetime(s) {
+            Some(datetime) => Ok(Self::from_naive(datetime.date())),
+            None => Err(ConvertError::InvalidArgument(format!(
+                "invalid date {:?}",
+                s
+            ))),
+        }
+    }
+}
+
+/// A timestamp without time zone, stored as the number of microseconds since
+/// 1970-01-01 00:00:00.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    /// Returns the timestamp `micros` microseconds after 1970-01-01 00:00:00, or `None` if it
+    /// is out of range.
+    pub fn from_micros(micros: i64) -> Option<Self> {
+        let days = micros.div_euclid(MICROS_PER_DAY) as i32;
+        Date::from_days(days).map(|_| Timestamp(micros))
+    }
+
+    /// Returns the number of microseconds since 1970-01-01 00:00:00.
+    pub fn micros(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the current time in UTC.
+    pub fn now() -> Self {
+        let elapsed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time before 1970");
+        Timestamp(elapsed.as_micros() as i64)
+    }
+
+    /// Returns the date of the timestamp.
+    pub fn date(self) -> Date {
+        Date(self.0.div_euclid(MICROS_PER_DAY) as i32)
+    }
+
+    /// Add an interval. Months are added first, and the day is clamped to the end of the
+    /// month, e.g. 2021-01-31 plus 1 month is 2021-02-28.
+    pub fn checked_add_interval(self, interval: Interval) -> Option<Self> {
+        let datetime = self.to_naive();
+        let datetime = match interval.months {
+            m if m >= 0 => datetime.checked_add_months(Months::new(m as u32))?,
+            m => datetime.checked_sub_months(Months::new(m.unsigned_abs()))?,
+        };
+        let micros = (Self::from_naive(datetime).0)
+            .checked_add(interval.days as i64 * MICROS_PER_DAY)?
+            .checked_add(interval.micros)?;
+        Self::from_micros(micros)
+    }
+
+    pub fn checked_sub_interval(self, interval: Interval) -> Option<Self> {
+        self.checked_add_interval(interval.checked_neg()?)
+    }
+
+    /// Returns the interval from `other` to `self` in days and microseconds.
+    pub fn checked_sub(self, other: Self) -> Option<Interval> {
+        let micros = self.0.checked_sub(other.0)?;
+        Some(Interval {
+            months: 0,
+            days: (micros / MICROS_PER_DAY) as i32,
+            micros: micros % MICROS_PER_DAY,
+        })
+    }
+
+    /// Returns the value of a field, e.g. the year or the day of week.
+    pub fn extract(self, field: TemporalField) -> Result<Decimal, ConvertError> {
+        use TemporalField::*;
+        let datetime = self.to_naive();
+        let year = datetime.year() as i64;
+        // the seconds with the fraction in microseconds
+        let second = datetime.second() as i64 * MICROS_PER_SEC + self.0.rem_euclid(MICROS_PER_SEC);
+        let value = match field {
+            Microsecond => second.into(),
+            Millisecond => Decimal::new(second, 3),
+            Second => Decimal::new(second, 6),
+            Minute => datetime.minute().into(),
+            Hour => datetime.hour().into(),
+            Day => datetime.day().into(),
+            Week => datetime.iso_week().week().into(),
+            Month => datetime.month().into(),
+            Quarter => ((datetime.month() - 1) / 3 + 1).into(),
+            Year => year.into(),
+            Decade => year.div_euclid(10).into(),
+            // there is no year 0, so the first century is from year 1 to 100
+            Century if year > 0 => ((year + 99) / 100).into(),
+            Century => (-((99 - (year - 1)) / 100)).into(),
+            Millennium if year > 0 => ((year + 999) / 1000).into(),
+            Millennium => (-((999 - (year - 1)) / 1000)).into(),
+            Dow => datetime.weekday().num_days_from_sunday().into(),
+            IsoDow => datetime.weekday().number_from_monday().into(),
+            Doy => datetime.ordinal().into(),
+            IsoYear => datetime.iso_week().year().into(),
+            Epoch => Decimal::new(self.0, 6),
+        };
+        Ok(value.normalize())
+    }
+
+    /// Returns the timestamp truncated to the precision of `field`, e.g. the first day of the
+    /// month for `month`.
+    pub fn truncate(self, field: TemporalField) -> Result<Self, ConvertError> {
+        use TemporalField::*;
+        let floor = |unit: i64| Ok(Timestamp(self.0 - self.0.rem_euclid(unit)));
+        let date = self.date().to_naive();
+        let first_day = |year: i32, month: u32| {
+            NaiveDate::from_ymd_opt(year, month, 1)
+                .map(|date| Date::from_naive(date).to_timestamp())
+                .ok_or(ConvertError::Overflow)
+        };
+        match field {
+            Microsecond => Ok(self),
+            Millisecond => floor(1000),
+            Second => floor(MICROS_PER_SEC),
+            Minute => floor(MICROS_PER_MINUTE),
+            Hour => floor(MICROS_PER_HOUR),
+            Day => floor(MICROS_PER_DAY),
+            Week => {
+                let monday = self
+                    .date()
+                    .checked_sub_days(date.weekday().num_days_from_monday() as i32)
+                    .ok_or(ConvertError::Overflow)?;
+                Ok(monday.to_timestamp())
+            }
+            Month => first_day(date.year(), date.month()),
+            Quarter => first_day(date.year(), (date.month() - 1) / 3 * 3 + 1),
+            Year => first_day(date.year(), 1),
+            Decade => first_day(date.year().div_euclid(10) * 10, 1),
+            Century => first_day((date.year() - 1).div_euclid(100) * 100 + 1, 1),
+            Millennium => first_day((date.year() - 1).div_euclid(1000) * 1000 + 1, 1),
+            Dow | IsoDow | Doy | IsoYear | Epoch => Err(field.unsupported("timestamp")),
+        }
+    }
+
+    fn from_naive(datetime: NaiveDateTime) -> Self {
+        let micros = datetime.num_seconds_from_midnight() as i64 * MICROS_PER_SEC
+            + datetime.nanosecond() as i64 / 1000;
+        Timestamp(Date::from_naive(datetime.date()).to_timestamp().0 + micros)
+    }
+
+    fn to_naive(self) -> NaiveDateTime {
+        let micros = self.0.rem_euclid(MICROS_PER_DAY);
+        let secs = (micros / MICROS_PER_SEC) as u32;
+        let nanos = (micros % MICROS_PER_SEC * 1000) as u32;
+        let time = NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos).unwrap();
+        self.date().to_naive().and_time(time)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let datetime = self.to_naive();
+        write!(f, "{}", datetime.format("%Y-%m-%d %H:%M:%S"))?;
+        write_fraction(f, self.0.rem_euclid(MICROS_PER_SEC))
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = ConvertError;
+
+    /// Parse a timestamp in the format `YYYY-MM-DD[( |T)HH:MM[:SS[.FFFFFF]]]`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_datetime(s) {
+            Some(datetime) => Ok(Self::from_naive(datetime)),
+            None => Err(ConvertError::InvalidArgument(format!(
+                "invalid timestamp {:?}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Parse a date with an optional time, which is midnight if omitted.
+fn parse_datetime(s: &str) -> Option<NaiveDateTime> {
+    const FORMATS: [&str; 4] = [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ];
+    let s = s.trim();
+    let datetime = FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok());
+    datetime.or_else(|| {
+        let date = NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()?;
+        date.and_hms_opt(0, 0, 0)
+    })
+}
+
+/// A span of time in months, days and microseconds, which are kept apart as the number of
+/// days in a month and the length of a day vary.
+///
+/// Intervals are compared by their length with 30 days in a month, so `1 mon` equals
+/// `30 days`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Interval {
+    months: i32,
+    days: i32,
+    micros: i64,
+}
+
+impl Interval {
+    pub fn new(months: i32, days: i32, micros: i64) -> Self {
+        Interval {
+            months,
+            days,
+            micros,
+        }
+    }
+
+    pub fn months(self) -> i32 {
+        self.months
+    }
+
+    pub fn days(self) -> i32 {
+        self.days
+    }
+
+    pub fn micros(self) -> i64 {
+        self.micros
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        Some(Interval {
+            months: self.months.checked_add(other.months)?,
+            days: self.days.checked_add(other.days)?,
+            micros: self.micros.checked_add(other.micros)?,
+        })
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Interval {
+            months: self.months.checked_neg()?,
+            days: self.days.checked_neg()?,
+            micros: self.micros.checked_neg()?,
+        })
+    }
+
+    pub fn checked_mul(self, factor: f64) -> Option<Self> {
+        self.scale(|v| v * factor)
+    }
+
+    pub fn checked_div(self, divisor: f64) -> Option<Self> {
+        self.scale(|v| v / divisor)
+    }
+
+    /// Apply `f` on each part. The fractions of months and days are carried to the smaller
+    /// parts, e.g. half a month is 15 days.
+    fn scale(self, f: impl Fn(f64) -> f64) -> Option<Self> {
+        let months = f(self.months as f64);
+        let days = f(self.days as f64) + months.fract() * 30.0;
+        let mut day_micros = (days.fract() * MICROS_PER_DAY as f64).round() as i64;
+        let mut days = days.trunc();
+        // rounding may make a whole day, e.g. 9.99999999 days
+        if day_micros.abs() >= MICROS_PER_DAY {
+            days += (day_micros / MICROS_PER_DAY) as f64;
+            day_micros %= MICROS_PER_DAY;
+        }
+        let micros = f(self.micros as f64).round() + day_micros as f64;
+        Some(Interval {
+            months: f64_to_int(months.trunc())?,
+            days: f64_to_int(days)?,
+            micros: f64_to_int(micros)?,
+        })
+    }
+
+    /// Returns the value of a field, e.g. the number of hours without the days.
+    pub fn extract(self, field: TemporalField) -> Result<Decimal, ConvertError> {
+        use TemporalField::*;
+        let years = (self.months / 12) as i64;
+        let months = (self.months % 12) as i64;
+        let second = self.micros % MICROS_PER_MINUTE;
+        let value = match field {
+            Microsecond => second.into(),
+            Millisecond => Decimal::new(second, 3),
+            Second => Decimal::new(second, 6),
+            Minute => (self.micros / MICROS_PER_MINUTE % 60).into(),
+            Hour => (self.micros / MICROS_PER_HOUR).into(),
+            Day => self.days.into(),
+            Month => months.into(),
+            Quarter => (months / 3 + 1).into(),
+            Year => years.into(),
+            Decade => (years / 10).into(),
+            Century => (years / 100).into(),
+            Millennium => (years / 1000).into(),
+            Epoch => {
+                // a year is 365.25 days and a month is 30 days
+                let days = Decimal::from(years) * Decimal::new(36525, 2)
+                    + Decimal::from(months * 30 + self.days as i64);
+                days * Decimal::from(86400) + Decimal::new(self.micros, 6)
+            }
+            Week | Dow | IsoDow | Doy | IsoYear => return Err(field.unsupported("interval")),
+        };
+        Ok(value.normalize())
+    }
+
+    /// Returns the length with 30 days in a month, which is used for comparison.
+    fn normalized_micros(self) -> i128 {
+        (self.months as i128 * 30 + self.days as i128) * MICROS_PER_DAY as i128
+            + self.micros as i128
+    }
+}
+
+impl PartialEq for Interval {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized_micros() == other.normalized_micros()
+    }
+}
+
+impl Eq for Interval {}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.normalized_micros().cmp(&other.normalized_micros())
+    }
+}
+
+impl std::hash::Hash for Interval {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.normalized_micros().hash(state);
+    }
+}
+
+impl fmt::Display for Interval {
+    /// Format like PostgreSQL, e.g. `1 year 2 mons 3 days 04:05:06.5`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = vec![];
+        let mut push = |n: i64, unit: &str| match n {
+            0 => {}
+            1 => parts.push(format!("1 {}", unit)),
+            n => parts.push(format!("{} {}s", n, unit)),
+        };
+        push((self.months / 12) as i64, "year");
+        push((self.months % 12) as i64, "mon");
+        push(self.days as i64, "day");
+        if self.micros == 0 && !parts.is_empty() {
+            return write!(f, "{}", parts.join(" "));
+        }
+        for part in parts {
+            write!(f, "{} ", part)?;
+        }
+        let sign = if self.micros < 0 { "-" } else { "" };
+        let micros = self.micros.unsigned_abs() as i64;
+        let secs = micros / MICROS_PER_SEC;
+        write!(
+            f,
+            "{}{:02}:{:02}:{:02}",
+            sign,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        )?;
+        write_fraction(f, micros % MICROS_PER_SEC)
+    }
+}
+
+impl FromStr for Interval {
+    type Err = ConvertError;
+
+    /// Parse a list of quantities and units optionally followed by `ago`, e.g.
+    /// `1 year 2 months -3 days 04:05:06 ago`. A quantity without a unit is in seconds.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ConvertError::InvalidArgument(format!("invalid interval {:?}", s));
+        let mut interval = Interval::default();
+        let mut tokens = s.split_whitespace().peekable();
+        while let Some(token) = tokens.next() {
+            let part = if token.contains(':') {
+                parse_time(token).ok_or_else(err)?
+            } else if token.eq_ignore_ascii_case("ago") && tokens.peek().is_none() {
+                interval = interval.checked_neg().ok_or(ConvertError::Overflow)?;
+                break;
+            } else {
+                let quantity: f64 = token.parse().map_err(|_| err())?;
+                let unit = match tokens.next() {
+                    Some(unit) => unit.parse::<TemporalField>()?,
+                    None => TemporalField::Second,
+                };
+                (unit.to_interval()?)
+                    .checked_mul(quantity)
+                    .ok_or(ConvertError::Overflow)?
+            };
+            interval = interval.checked_add(part).ok_or(ConvertError::Overflow)?;
+        }
+        Ok(interval)
+    }
+}
+
+/// Parse a time span like `-04:05:06.5` or `04:05`.
+fn parse_time(s: &str) -> Option<Interval> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let parts = s.split(':').collect::<Vec<_>>();
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [h, m] => (h.parse::<i64>().ok()?, m.parse::<i64>().ok()?, 0.0),
+        [h, m, s] => (
+            h.parse::<i64>().ok()?,
+            m.parse::<i64>().ok()?,
+            s.parse::<f64>().ok()?,
+        ),
+        _ => return None,
+    };
+    let micros = (hours.checked_mul(60)?.checked_add(minutes)?)
+        .checked_mul(MICROS_PER_MINUTE)?
+        .checked_add(f64_to_int((seconds * MICROS_PER_SEC as f64).round())?)?;
+    Some(Interval::new(0, 0, if negative { -micros } else { micros }))
+}
+
+/// Write the fraction of a second, e.g. `.5` for 500000 microseconds, or nothing if it is 0.
+fn write_fraction(f: &mut fmt::Formatter<'_>, micros: i64) -> fmt::Result {
+    if micros == 0 {
+        return Ok(());
+    }
+    let fraction = format!("{:06}", micros);
+    write!(f, ".{}", fraction.trim_end_matches('0'))
+}
+
+/// Convert a whole number in `f64` to an integer, or `None` if it is out of range.
+fn f64_to_int<T: TryFrom<i64>>(v: f64) -> Option<T> {
+    // `i64::MAX as f64` is rounded up to 2^63
+    if !(v >= i64::MIN as f64 && v < i64::MAX as f64) {
+        return None;
+    }
+    (v as i64).try_into().ok()
+}
+
+/// A field of a date, timestamp or interval, e.g. in `EXTRACT(field FROM ...)`, or the unit
+/// of a quantity in an interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemporalField {
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+    Decade,
+    Century,
+    Millennium,
+    /// The day of week from Sunday (0) to Saturday (6).
+    Dow,
+    /// The day of week from Monday (1) to Sunday (7).
+    IsoDow,
+    /// The day of year from 1 to 366.
+    Doy,
+    /// The ISO 8601 week-numbering year.
+    IsoYear,
+    /// The number of seconds since 1970-01-01 00:00:00, or the length of an interval in
+    /// seconds.
+    Epoch,
+}
+
+impl TemporalField {
+    /// Returns an interval of one unit of the field.
+    fn to_interval(self) -> Result<Interval, ConvertError> {
+        use TemporalField::*;
+        Ok(match self {
+            Microsecond => Interval::new(0, 0, 1),
+            Millisecond => Interval::new(0, 0, 1000),
+            Second => Interval::new(0, 0, MICROS_PER_SEC),
+            Minute => Interval::new(0, 0, MICROS_PER_MINUTE),
+            Hour => Interval::new(0, 0, MICROS_PER_HOUR),
+            Day => Interval::new(0, 1, 0),
+            Week => Interval::new(0, 7, 0),
+            Month => Interval::new(1, 0, 0),
+            Quarter => Interval::new(3, 0, 0),
+            Year => Interval::new(12, 0, 0),
+            Decade => Interval::new(120, 0, 0),
+            Century => Interval::new(1200, 0, 0),
+            Millennium => Interval::new(12000, 0, 0),
+            Dow | IsoDow | Doy | IsoYear | Epoch => return Err(self.unsupported("interval")),
+        })
+    }
+
+    fn unsupported(self, ty: &str) -> ConvertError {
+        ConvertError::InvalidArgument(format!("unit {:?} not supported for type {}", self, ty))
+    }
+}
+
+impl FromStr for TemporalField {
+    type Err = ConvertError;
+
+    /// Parse a field name, which is case-insensitive and may be plural or abbreviated.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use TemporalField::*;
+        Ok(match s.to_lowercase().as_str() {
+            "microsecond" | "microseconds" | "us" | "usec" | "usecs" => Microsecond,
+            "millisecond" | "milliseconds" | "ms" | "msec" | "msecs" => Millisecond,
+            "second" | "seconds" | "s" | "sec" | "secs" => Second,
+            "minute" | "minutes" | "m" | "min" | "mins" => Minute,
+            "hour" | "hours" | "h" | "hr" | "hrs" => Hour,
+            "day" | "days" | "d" => Day,
+            "week" | "weeks" | "w" => Week,
+            "month" | "months" | "mon" | "mons" => Month,
+            "quarter" | "qtr" => Quarter,
+            "year" | "years" | "y" | "yr" | "yrs" => Year,
+            "decade" | "decades" => Decade,
+            "century" | "centuries" => Century,
+            "millennium" | "millennia" | "millenium" => Millennium,
+            "dow" => Dow,
+            "isodow" => IsoDow,
+            "doy" => Doy,
+            "isoyear" => IsoYear,
+            "epoch" => Epoch,
+            _ => {
+                return Err(ConvertError::InvalidArgument(format!(
+                    "unit {:?} not recognized",
+                    s
+                )))
+            }
+        })
+    }
+}